@@ -0,0 +1,369 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    net::IpAddr,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{self, forward_ready, ServiceRequest, ServiceResponse, Transform},
+    http::{header, Method},
+    HttpMessage, ResponseError,
+};
+use anyhow::Result;
+use async_graphql::{Context, Guard};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use rand::RngCore;
+use service::{
+    model::{ApiKey, ApiScope},
+    Service, ServiceImpl,
+};
+use sha2::{Digest, Sha256};
+
+use crate::rest::RestError;
+
+pub const DEFAULT_RATE_LIMIT: u64 = 600;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+const KEY_CACHE_TTL: Duration = Duration::from_secs(30);
+// Requests without a valid key are limited per client address, so guessing keys can't hammer the database
+const UNAUTHENTICATED_RATE_LIMIT: u64 = 60;
+const MAX_TRACKED_ENTRIES: usize = 10000;
+const PUBLIC_PATHS: &[&str] = &["/metrics", "/health/live", "/health/ready"];
+
+pub fn generate_api_key() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("ik_{}", hex::encode(bytes))
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+pub struct ScopeGuard(pub ApiScope);
+
+#[async_trait::async_trait]
+impl Guard for ScopeGuard {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        // Requests only carry no key when authentication is disabled
+        match ctx.data_opt::<ApiKey>() {
+            Some(key) if !key.has_scope(self.0) => {
+                Err(format!("API key {} does not have the {} scope", key.name, self.0).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct AuthState {
+    service: Arc<ServiceImpl>,
+    keys: Mutex<HashMap<String, (Option<ApiKey>, Instant)>>,
+    windows: Mutex<HashMap<String, (Instant, u64)>>,
+    failures: Mutex<HashMap<IpAddr, (Instant, u64)>>,
+    usage: Mutex<HashMap<String, u64>>,
+}
+
+impl AuthState {
+    pub fn new(service: Arc<ServiceImpl>) -> Self {
+        Self {
+            service,
+            keys: Mutex::new(HashMap::new()),
+            windows: Mutex::new(HashMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Usage is counted in memory and written in batches so authenticated requests don't each cost a database write
+    pub async fn flush_usage(&self) -> Result<()> {
+        let usage = std::mem::take(&mut *self.usage.lock().unwrap())
+            .into_iter()
+            .collect::<Vec<_>>();
+        if let Err(err) = self.service.record_api_key_usage(&usage).await {
+            let mut pending = self.usage.lock().unwrap();
+            for (name, count) in usage {
+                *pending.entry(name).or_default() += count;
+            }
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    async fn authorize(&self, req: &ServiceRequest) -> Result<ApiKey, RestError> {
+        let client = req.peer_addr().map(|addr| addr.ip());
+        if let Some(client) = client {
+            let failures = self.failures.lock().unwrap();
+            if let Some(retry_after) = exceeded(&failures, &client, UNAUTHENTICATED_RATE_LIMIT) {
+                return Err(RestError::TooManyRequests(
+                    "Too many unauthenticated requests".to_string(),
+                    retry_after,
+                ));
+            }
+        }
+        let result = self.authenticate(req).await;
+        if let (Err(RestError::Unauthorized(_)), Some(client)) = (&result, client) {
+            record(&mut self.failures.lock().unwrap(), client);
+        }
+        let key = result?;
+        if !key.has_scope(ApiScope::Read) {
+            return Err(RestError::Forbidden(format!(
+                "API key {} does not have the read scope",
+                key.name
+            )));
+        }
+        self.check_rate_limit(&key)?;
+        *self.usage.lock().unwrap().entry(key.name.clone()).or_default() += 1;
+        Ok(key)
+    }
+
+    async fn authenticate(&self, req: &ServiceRequest) -> Result<ApiKey, RestError> {
+        let key = request_key(req).ok_or(RestError::Unauthorized("Missing API key".to_string()))?;
+        let key = self
+            .lookup(&hash_api_key(&key))
+            .await?
+            .ok_or(RestError::Unauthorized("Invalid API key".to_string()))?;
+        if key.revoked {
+            return Err(RestError::Unauthorized("API key has been revoked".to_string()));
+        }
+        Ok(key)
+    }
+
+    // Unknown keys are cached too, so a bogus key costs one query per TTL rather than one per request
+    async fn lookup(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        if let Some((key, fetched_at)) = self.keys.lock().unwrap().get(key_hash) {
+            if fetched_at.elapsed() < KEY_CACHE_TTL {
+                return Ok(key.clone());
+            }
+        }
+        let key = self.service.get_api_key_by_hash(key_hash).await?;
+        let mut keys = self.keys.lock().unwrap();
+        if keys.len() >= MAX_TRACKED_ENTRIES {
+            keys.retain(|_, (_, fetched_at)| fetched_at.elapsed() < KEY_CACHE_TTL);
+        }
+        if key.is_some() || keys.len() < MAX_TRACKED_ENTRIES {
+            keys.insert(key_hash.to_string(), (key.clone(), Instant::now()));
+        }
+        Ok(key)
+    }
+
+    fn check_rate_limit(&self, key: &ApiKey) -> Result<(), RestError> {
+        let mut windows = self.windows.lock().unwrap();
+        if let Some(retry_after) = exceeded(&windows, &key.name, key.rate_limit) {
+            return Err(RestError::TooManyRequests(
+                "Rate limit exceeded".to_string(),
+                retry_after,
+            ));
+        }
+        record(&mut windows, key.name.clone());
+        Ok(())
+    }
+}
+
+// Returns the seconds until the window resets when it already holds `limit` requests
+fn exceeded<K: Hash + Eq>(windows: &HashMap<K, (Instant, u64)>, key: &K, limit: u64) -> Option<u64> {
+    let (started_at, count) = windows.get(key)?;
+    let elapsed = started_at.elapsed();
+    (elapsed < RATE_LIMIT_WINDOW && *count >= limit).then(|| RATE_LIMIT_WINDOW.saturating_sub(elapsed).as_secs().max(1))
+}
+
+fn record<K: Hash + Eq>(windows: &mut HashMap<K, (Instant, u64)>, key: K) {
+    if windows.len() >= MAX_TRACKED_ENTRIES {
+        windows.retain(|_, (started_at, _)| started_at.elapsed() < RATE_LIMIT_WINDOW);
+    }
+    let now = Instant::now();
+    let (started_at, count) = windows.entry(key).or_insert((now, 0));
+    if now.duration_since(*started_at) >= RATE_LIMIT_WINDOW {
+        *started_at = now;
+        *count = 0;
+    }
+    *count += 1;
+}
+
+fn request_key(req: &ServiceRequest) -> Option<String> {
+    if let Some(key) = req.headers().get("x-api-key").and_then(|value| value.to_str().ok()) {
+        return Some(key.to_string());
+    }
+    // Keys are never read from the query string, where they would end up in access logs and browser history
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string)
+}
+
+fn is_public(req: &ServiceRequest) -> bool {
+    req.method() == Method::GET
+        && (PUBLIC_PATHS.contains(&req.path()) || (req.path() == "/" && !req.headers().contains_key(header::UPGRADE)))
+}
+
+pub struct ApiKeyAuth {
+    state: Arc<AuthState>,
+}
+
+impl ApiKeyAuth {
+    pub fn new(state: Arc<AuthState>) -> Self {
+        Self { state }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiKeyAuth
+where
+    S: dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = ApiKeyAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiKeyAuthMiddleware {
+            service: Rc::new(service),
+            state: self.state.clone(),
+        }))
+    }
+}
+
+pub struct ApiKeyAuthMiddleware<S> {
+    service: Rc<S>,
+    state: Arc<AuthState>,
+}
+
+impl<S, B> dev::Service<ServiceRequest> for ApiKeyAuthMiddleware<S>
+where
+    S: dev::Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let state = self.state.clone();
+        Box::pin(async move {
+            if is_public(&req) {
+                return Ok(service.call(req).await?.map_into_left_body());
+            }
+            match state.authorize(&req).await {
+                Ok(key) => {
+                    req.extensions_mut().insert(key);
+                    Ok(service.call(req).await?.map_into_left_body())
+                }
+                Err(err) => Ok(req.into_response(err.error_response()).map_into_right_body()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use actix_web::{http::StatusCode, test, web, App};
+    use store::DbConfig;
+
+    use super::*;
+
+    const CLIENT: &str = "10.0.0.1:4000";
+
+    // Keys are seeded into the cache, so no request reaches the database the pool points at
+    async fn auth_state(keys: &[(&str, ApiKey)]) -> Arc<AuthState> {
+        let pool = store::connect(DbConfig {
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            username: "test".to_string(),
+            password: "test".to_string(),
+            database: "test".to_string(),
+        })
+        .await;
+        let state = AuthState::new(Arc::new(ServiceImpl::new(store::repositories(pool))));
+        for (key, api_key) in keys {
+            state
+                .keys
+                .lock()
+                .unwrap()
+                .insert(hash_api_key(key), (Some(api_key.clone()), Instant::now()));
+        }
+        Arc::new(state)
+    }
+
+    fn api_key(scopes: Vec<ApiScope>, rate_limit: u64) -> ApiKey {
+        ApiKey {
+            name: "test".to_string(),
+            scopes,
+            rate_limit,
+            request_count: 0,
+            last_used_at: None,
+            revoked: false,
+        }
+    }
+
+    async fn status(state: &Arc<AuthState>, request: test::TestRequest) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .wrap(ApiKeyAuth::new(state.clone()))
+                .route("/v1/epochs", web::get().to(|| async { "ok" })),
+        )
+        .await;
+        let request = request.peer_addr(CLIENT.parse::<SocketAddr>().unwrap());
+        test::call_service(&app, request.to_request()).await.status()
+    }
+
+    #[actix_web::test]
+    async fn valid_key_is_accepted() {
+        let state = auth_state(&[("ik_valid", api_key(vec![ApiScope::Read], 10))]).await;
+        let request = test::TestRequest::get()
+            .uri("/v1/epochs")
+            .insert_header(("x-api-key", "ik_valid"));
+        assert_eq!(status(&state, request).await, StatusCode::OK);
+        let request = test::TestRequest::get()
+            .uri("/v1/epochs")
+            .insert_header((header::AUTHORIZATION, "Bearer ik_valid"));
+        assert_eq!(status(&state, request).await, StatusCode::OK);
+        assert_eq!(state.usage.lock().unwrap().get("test"), Some(&2));
+    }
+
+    #[actix_web::test]
+    async fn missing_key_is_unauthorized() {
+        let state = auth_state(&[("ik_valid", api_key(vec![ApiScope::Read], 10))]).await;
+        let request = test::TestRequest::get().uri("/v1/epochs");
+        assert_eq!(status(&state, request).await, StatusCode::UNAUTHORIZED);
+        let request = test::TestRequest::get().uri("/v1/epochs?api_key=ik_valid");
+        assert_eq!(status(&state, request).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn key_without_read_scope_is_forbidden() {
+        let state = auth_state(&[("ik_none", api_key(vec![], 10))]).await;
+        let request = test::TestRequest::get()
+            .uri("/v1/epochs")
+            .insert_header(("x-api-key", "ik_none"));
+        assert_eq!(status(&state, request).await, StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn requests_over_the_limit_are_rejected() {
+        let state = auth_state(&[("ik_valid", api_key(vec![ApiScope::Read], 1))]).await;
+        let keyed = || {
+            test::TestRequest::get()
+                .uri("/v1/epochs")
+                .insert_header(("x-api-key", "ik_valid"))
+        };
+        assert_eq!(status(&state, keyed()).await, StatusCode::OK);
+        assert_eq!(status(&state, keyed()).await, StatusCode::TOO_MANY_REQUESTS);
+
+        for _ in 0..UNAUTHENTICATED_RATE_LIMIT {
+            let request = test::TestRequest::get().uri("/v1/epochs");
+            assert_eq!(status(&state, request).await, StatusCode::UNAUTHORIZED);
+        }
+        let request = test::TestRequest::get().uri("/v1/epochs");
+        assert_eq!(status(&state, request).await, StatusCode::TOO_MANY_REQUESTS);
+    }
+}
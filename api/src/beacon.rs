@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use actix_web::web;
+use client::model::{
+    block::{BlockHeaderData, BlockHeaderResponse, BlockHeadersResponse, BlockId},
+    committee::{Committee, CommitteeResponse},
+    proposer::{Proposer, ProposerResponse},
+    state::StateId,
+};
+use serde::Deserialize;
+use service::{model::BlockHeader, Service, ServiceImpl};
+
+use crate::rest::{not_found, path_config, query_config, RestError, RestResult};
+
+#[derive(Deserialize)]
+pub struct HeadersQuery {
+    slot: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct CommitteesQuery {
+    epoch: Option<u64>,
+    index: Option<u8>,
+    slot: Option<u64>,
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/eth/v1")
+            .app_data(path_config())
+            .app_data(query_config())
+            .route("/beacon/headers", web::get().to(headers))
+            .route("/beacon/headers/{block_id}", web::get().to(header))
+            .route("/beacon/states/{state_id}/committees", web::get().to(committees))
+            .route("/validator/duties/proposer/{epoch}", web::get().to(proposer_duties))
+            .default_service(web::to(not_found)),
+    );
+}
+
+async fn headers(
+    service: web::Data<Arc<ServiceImpl>>,
+    query: web::Query<HeadersQuery>,
+) -> RestResult<BlockHeadersResponse> {
+    let header = match query.slot {
+        Some(slot) => service.get_block_header(slot).await?,
+        None => service.get_latest_block_header().await?,
+    };
+    Ok(web::Json(BlockHeadersResponse {
+        data: header.into_iter().map(BlockHeaderData::from).collect(),
+        execution_optimistic: false,
+        finalized: false,
+    }))
+}
+
+async fn header(service: web::Data<Arc<ServiceImpl>>, path: web::Path<String>) -> RestResult<BlockHeaderResponse> {
+    let block_id = path
+        .parse::<BlockId>()
+        .map_err(|err| RestError::BadRequest(err.to_string()))?;
+    let header = match &block_id {
+        BlockId::Head => service.get_latest_block_header().await?,
+        BlockId::Genesis => service.get_block_header(0).await?,
+        BlockId::Slot(slot) => service.get_block_header(*slot).await?,
+        BlockId::BlockRoot(root) => service.get_block_header_by_root(root).await?,
+        BlockId::Finalized | BlockId::Justified => {
+            return Err(RestError::BadRequest(format!("Unsupported block id: {block_id}")))
+        }
+    }
+    .ok_or(RestError::NotFound(format!("Block {block_id} not found")))?;
+    Ok(web::Json(BlockHeaderResponse {
+        data: header.into(),
+        execution_optimistic: false,
+        finalized: false,
+    }))
+}
+
+async fn committees(
+    service: web::Data<Arc<ServiceImpl>>,
+    path: web::Path<String>,
+    query: web::Query<CommitteesQuery>,
+) -> RestResult<CommitteeResponse> {
+    let slot = state_slot(&service, &path).await?;
+    let epoch = query.epoch.unwrap_or(slot / 32);
+    if let Some(slot) = query.slot {
+        if slot / 32 != epoch {
+            return Err(RestError::BadRequest(format!("Slot {slot} is not in epoch {epoch}")));
+        }
+    }
+    let committees = service
+        .get_committees_for_epoch(epoch)
+        .await?
+        .into_iter()
+        .filter(|committee| query.index.is_none_or(|index| committee.index == index))
+        .filter(|committee| query.slot.is_none_or(|slot| committee.slot == slot))
+        .map(|committee| Committee {
+            index: committee.index,
+            slot: committee.slot,
+            validators: committee.validators,
+        })
+        .collect();
+    Ok(web::Json(CommitteeResponse {
+        data: committees,
+        execution_optimistic: false,
+        finalized: false,
+    }))
+}
+
+async fn proposer_duties(service: web::Data<Arc<ServiceImpl>>, path: web::Path<u64>) -> RestResult<ProposerResponse> {
+    let epoch = path.into_inner();
+    let duties = service.get_proposer_duties_for_epoch(epoch).await?;
+    if duties.is_empty() {
+        return Err(RestError::NotFound(format!("No proposer duties for epoch {epoch}")));
+    }
+    let indices = duties.iter().map(|duty| duty.validator_index).collect::<Vec<_>>();
+    let validators = service.get_validators(&indices).await?;
+    let dependent_root = dependent_header(&service, epoch)
+        .await?
+        .ok_or(RestError::NotFound(format!("No dependent block for epoch {epoch}")))?
+        .root;
+    let data = duties
+        .into_iter()
+        .map(|duty| {
+            let pubkey = validators
+                .iter()
+                .find(|validator| validator.index == duty.validator_index)
+                .map(|validator| validator.pubkey.clone())
+                .ok_or(RestError::NotFound(format!(
+                    "Validator {} not found",
+                    duty.validator_index
+                )))?;
+            Ok(Proposer {
+                pubkey,
+                validator_index: duty.validator_index,
+                slot: duty.slot,
+            })
+        })
+        .collect::<Result<Vec<_>, RestError>>()?;
+    Ok(web::Json(ProposerResponse {
+        data,
+        execution_optimistic: false,
+        dependent_root,
+    }))
+}
+
+// Proposer shuffling depends on the last block before the epoch starts, or the genesis block for epoch 0
+async fn dependent_header(service: &ServiceImpl, epoch: u64) -> Result<Option<BlockHeader>, RestError> {
+    match service.get_block_header_before(epoch * 32).await? {
+        Some(header) => Ok(Some(header)),
+        None => Ok(service.get_block_header(0).await?),
+    }
+}
+
+async fn state_slot(service: &ServiceImpl, state_id: &str) -> Result<u64, RestError> {
+    let state_id = state_id
+        .parse::<StateId>()
+        .map_err(|err| RestError::BadRequest(err.to_string()))?;
+    let latest = service
+        .get_latest_block_header()
+        .await?
+        .ok_or(RestError::NotFound("No blocks indexed".to_string()))?;
+    match &state_id {
+        StateId::Head => Ok(latest.slot),
+        StateId::Genesis => Ok(0),
+        StateId::Slot(slot) if *slot <= latest.slot => Ok(*slot),
+        StateId::Slot(_) => Err(RestError::NotFound(format!("State {state_id} not found"))),
+        StateId::StateRoot(root) => service
+            .get_block_header_by_state_root(root)
+            .await?
+            .map(|header| header.slot)
+            .ok_or(RestError::NotFound(format!("State {state_id} not found"))),
+        StateId::Finalized | StateId::Justified => {
+            Err(RestError::BadRequest(format!("Unsupported state id: {state_id}")))
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::{anyhow, Result};
+use api::auth::{generate_api_key, hash_api_key, DEFAULT_RATE_LIMIT};
+use envconfig::Envconfig;
+use logging::LogConfig;
+use service::{
+    model::{ApiKey, ApiScope},
+    Service, ServiceImpl,
+};
+use store::DbConfig;
+
+const USAGE: &str = "Usage: api-key create <name> [read,admin] [rate_limit] | api-key list | api-key revoke <name>";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _log_guard = logging::init(&LogConfig::init_from_env()?, "api-key")?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if matches!(args.first().map(String::as_str), Some("-h" | "--help" | "help")) {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let db_config = DbConfig::init_from_env()?;
+    let db_pool = store::connect(db_config).await;
+    let service = ServiceImpl::new(store::repositories(db_pool));
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        // A name like --help is almost certainly a mistyped flag, and could not be revoked without escaping it
+        ["create", name, ..] if name.starts_with('-') => {
+            return Err(anyhow!(
+                "Invalid API key name {name}: names can't start with '-'\n{USAGE}"
+            ));
+        }
+        ["create", name, rest @ ..] => {
+            let scopes = rest
+                .first()
+                .map(|scopes| scopes.split(',').map(str::parse).collect::<Result<Vec<ApiScope>>>())
+                .transpose()?
+                .unwrap_or(vec![ApiScope::Read]);
+            let rate_limit = rest
+                .get(1)
+                .map(|limit| limit.parse())
+                .transpose()?
+                .unwrap_or(DEFAULT_RATE_LIMIT);
+            let key = generate_api_key();
+            let api_key = ApiKey {
+                name: name.to_string(),
+                scopes,
+                rate_limit,
+                request_count: 0,
+                last_used_at: None,
+                revoked: false,
+            };
+            if !service.create_api_key(&api_key, &hash_api_key(&key)).await? {
+                return Err(anyhow!("API key {name} already exists"));
+            }
+            // Only the hash is stored, so this is the only time the key can be shown
+            println!("{key}");
+        }
+        ["list"] => {
+            for key in service.get_api_keys().await? {
+                let scopes = key.scopes.iter().map(ApiScope::to_string).collect::<Vec<_>>();
+                println!(
+                    "{}\t{}\t{}/min\t{} requests\t{}",
+                    key.name,
+                    scopes.join(","),
+                    key.rate_limit,
+                    key.request_count,
+                    if key.revoked { "revoked" } else { "active" }
+                );
+            }
+        }
+        ["revoke", name] => {
+            if !service.revoke_api_key(name).await? {
+                return Err(anyhow!("No active API key named {name}"));
+            }
+        }
+        _ => return Err(anyhow!(USAGE)),
+    }
+
+    Ok(())
+}
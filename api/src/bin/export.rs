@@ -0,0 +1,49 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use anyhow::{anyhow, Result};
+use api::export::{export, ExportFormat};
+use envconfig::Envconfig;
+use futures_util::StreamExt;
+use logging::LogConfig;
+use service::ServiceImpl;
+use store::DbConfig;
+
+const USAGE: &str =
+    "Usage: export <attestations|epochs|proposers|validator_history> <from_epoch> <to_epoch> [csv|parquet] [output]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _log_guard = logging::init(&LogConfig::init_from_env()?, "export")?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let [dataset, from_epoch, to_epoch, rest @ ..] = args.as_slice() else {
+        return Err(anyhow!(USAGE));
+    };
+    let dataset = dataset.parse()?;
+    let from_epoch = from_epoch.parse()?;
+    let to_epoch = to_epoch.parse()?;
+    let format = rest
+        .first()
+        .map(|format| format.parse::<ExportFormat>())
+        .transpose()?
+        .unwrap_or_default();
+
+    let db_config = DbConfig::init_from_env()?;
+    let db_pool = store::connect(db_config).await;
+    let service = ServiceImpl::new(store::repositories(db_pool));
+
+    let mut output: Box<dyn Write> = match rest.get(1) {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+    let mut chunks = export(&service, dataset, format, from_epoch, to_epoch).await?;
+    while let Some(chunk) = chunks.next().await {
+        output.write_all(&chunk?)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
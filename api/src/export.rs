@@ -0,0 +1,586 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Result};
+use futures_util::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+use parquet::{
+    basic::Compression,
+    data_type::{BoolType, ByteArray, ByteArrayType, Int64Type},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::parser::parse_message_type,
+};
+use service::{
+    model::{AttestationData, Epoch, Slot, ValidatorHistory},
+    Service, ServiceImpl,
+};
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
+
+const ROWS_PER_CHUNK: usize = 8192;
+pub const EXPORT_RETRY_AFTER_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportDataset {
+    Attestations,
+    Epochs,
+    Proposers,
+    ValidatorHistory,
+}
+
+impl FromStr for ExportDataset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "attestations" => Ok(Self::Attestations),
+            "epochs" => Ok(Self::Epochs),
+            "proposers" => Ok(Self::Proposers),
+            "validator_history" => Ok(Self::ValidatorHistory),
+            _ => Err(anyhow!("Invalid export dataset: {}", s)),
+        }
+    }
+}
+
+impl Display for ExportDataset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportDataset::Attestations => write!(f, "attestations"),
+            ExportDataset::Epochs => write!(f, "epochs"),
+            ExportDataset::Proposers => write!(f, "proposers"),
+            ExportDataset::ValidatorHistory => write!(f, "validator_history"),
+        }
+    }
+}
+
+impl ExportDataset {
+    fn columns(&self) -> &'static [Column] {
+        match self {
+            ExportDataset::Attestations => ATTESTATION_COLUMNS,
+            ExportDataset::Epochs => EPOCH_COLUMNS,
+            ExportDataset::Proposers => PROPOSER_COLUMNS,
+            ExportDataset::ValidatorHistory => VALIDATOR_HISTORY_COLUMNS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(anyhow!("Invalid export format: {}", s)),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "csv"),
+            ExportFormat::Parquet => write!(f, "parquet"),
+        }
+    }
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ColumnKind {
+    Integer,
+    Boolean,
+    Text,
+}
+
+struct Column {
+    name: &'static str,
+    kind: ColumnKind,
+}
+
+impl Column {
+    const fn integer(name: &'static str) -> Self {
+        Self {
+            name,
+            kind: ColumnKind::Integer,
+        }
+    }
+
+    const fn boolean(name: &'static str) -> Self {
+        Self {
+            name,
+            kind: ColumnKind::Boolean,
+        }
+    }
+
+    const fn text(name: &'static str) -> Self {
+        Self {
+            name,
+            kind: ColumnKind::Text,
+        }
+    }
+}
+
+const ATTESTATION_COLUMNS: &[Column] = &[
+    Column::integer("epoch"),
+    Column::integer("slot"),
+    Column::integer("validator_index"),
+    Column::integer("committee_index"),
+    Column::boolean("attested"),
+];
+
+const EPOCH_COLUMNS: &[Column] = &[
+    Column::integer("epoch"),
+    Column::integer("active_validators"),
+    Column::integer("total_validators"),
+    Column::integer("attestations"),
+];
+
+const PROPOSER_COLUMNS: &[Column] = &[
+    Column::integer("slot"),
+    Column::integer("epoch"),
+    Column::integer("proposer_index"),
+    Column::text("status"),
+];
+
+const VALIDATOR_HISTORY_COLUMNS: &[Column] = &[
+    Column::integer("epoch"),
+    Column::integer("validator_index"),
+    Column::boolean("attested"),
+    Column::integer("proposed"),
+    Column::integer("missed"),
+];
+
+enum Value {
+    Integer(u64),
+    Boolean(bool),
+    Text(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(value) => write!(f, "{value}"),
+            Value::Boolean(value) => write!(f, "{value}"),
+            Value::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+trait ExportRecord {
+    fn values(self) -> Vec<Value>;
+}
+
+impl ExportRecord for AttestationData {
+    fn values(self) -> Vec<Value> {
+        vec![
+            Value::Integer(self.epoch),
+            Value::Integer(self.slot),
+            Value::Integer(self.validator),
+            Value::Integer(self.committee_index.into()),
+            Value::Boolean(self.attested),
+        ]
+    }
+}
+
+impl ExportRecord for Epoch {
+    fn values(self) -> Vec<Value> {
+        vec![
+            Value::Integer(self.index),
+            Value::Integer(self.active_validators),
+            Value::Integer(self.total_validators),
+            Value::Integer(self.attestations),
+        ]
+    }
+}
+
+impl ExportRecord for Slot {
+    fn values(self) -> Vec<Value> {
+        vec![
+            Value::Integer(self.slot),
+            Value::Integer(self.epoch),
+            Value::Integer(self.proposer_index),
+            Value::Text(self.status.to_string()),
+        ]
+    }
+}
+
+impl ExportRecord for ValidatorHistory {
+    fn values(self) -> Vec<Value> {
+        vec![
+            Value::Integer(self.epoch),
+            Value::Integer(self.validator_index),
+            Value::Boolean(self.attested),
+            Value::Integer(self.proposed),
+            Value::Integer(self.missed),
+        ]
+    }
+}
+
+pub async fn export(
+    service: &ServiceImpl,
+    dataset: ExportDataset,
+    format: ExportFormat,
+    from_epoch: u64,
+    to_epoch: u64,
+) -> Result<BoxStream<'static, Result<Vec<u8>>>> {
+    if from_epoch > to_epoch {
+        bail!("Invalid epoch range: {from_epoch} > {to_epoch}");
+    }
+    let rows = match dataset {
+        ExportDataset::Attestations => service
+            .stream_attestations(from_epoch, to_epoch)
+            .await?
+            .map_ok(ExportRecord::values)
+            .boxed(),
+        ExportDataset::Epochs => service
+            .stream_epochs(from_epoch, to_epoch)
+            .await?
+            .map_ok(ExportRecord::values)
+            .boxed(),
+        ExportDataset::Proposers => service
+            .stream_slots(from_epoch, to_epoch)
+            .await?
+            .map_ok(ExportRecord::values)
+            .boxed(),
+        ExportDataset::ValidatorHistory => service
+            .stream_validator_history(from_epoch, to_epoch)
+            .await?
+            .map_ok(ExportRecord::values)
+            .boxed(),
+    };
+    let encoder: Box<dyn Encoder> = match format {
+        ExportFormat::Csv => Box::new(CsvEncoder::new(dataset.columns())?),
+        ExportFormat::Parquet => Box::new(ParquetEncoder::new(dataset)?),
+    };
+    Ok(encode(rows, encoder))
+}
+
+// Each running export holds a database connection until it finishes, so only a few may run at once
+#[derive(Clone)]
+pub struct ExportLimits {
+    permits: Arc<Semaphore>,
+    idle_timeout: Duration,
+}
+
+impl ExportLimits {
+    pub fn new(max_concurrent: usize, idle_timeout: Duration) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            idle_timeout,
+        }
+    }
+
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.permits.clone().try_acquire_owned().ok()
+    }
+
+    // A reader that stops consuming never polls the stream again, so chunks are pushed from a task that gives up once
+    // one has waited longer than the idle timeout, releasing the connection and the permit with it
+    pub fn guard(
+        &self,
+        permit: OwnedSemaphorePermit,
+        mut chunks: BoxStream<'static, Result<Vec<u8>>>,
+    ) -> BoxStream<'static, Result<Vec<u8>>> {
+        let (sender, receiver) = mpsc::channel(1);
+        let idle = Arc::new(AtomicBool::new(false));
+        let idle_timeout = self.idle_timeout;
+        let task_idle = idle.clone();
+        tokio::spawn(async move {
+            let _permit = permit;
+            while let Some(chunk) = chunks.next().await {
+                match tokio::time::timeout(idle_timeout, sender.send(chunk)).await {
+                    Ok(Ok(())) => {}
+                    // The response was dropped, usually because the client disconnected
+                    Ok(Err(_)) => break,
+                    Err(_) => {
+                        task_idle.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                }
+            }
+        });
+        // Fails the response instead of ending it, so a reader that comes back doesn't take a cut off export as whole
+        stream::unfold(Some(receiver), move |receiver| {
+            let idle = idle.clone();
+            async move {
+                let mut receiver = receiver?;
+                match receiver.recv().await {
+                    Some(chunk) => Some((chunk, Some(receiver))),
+                    None if idle.load(Ordering::Relaxed) => Some((
+                        Err(anyhow!("Export reader was idle for {}s", idle_timeout.as_secs())),
+                        None,
+                    )),
+                    None => None,
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+// Encodes one chunk of rows at a time, so memory use is bounded by the chunk size rather than the export range
+fn encode(
+    rows: BoxStream<'static, Result<Vec<Value>>>,
+    encoder: Box<dyn Encoder>,
+) -> BoxStream<'static, Result<Vec<u8>>> {
+    stream::try_unfold(Some((rows.chunks(ROWS_PER_CHUNK), encoder)), |state| async move {
+        let Some((mut chunks, mut encoder)) = state else {
+            return Ok(None);
+        };
+        match chunks.next().await {
+            Some(chunk) => {
+                let rows = chunk.into_iter().collect::<Result<Vec<_>>>()?;
+                let bytes = encoder.write(&rows)?;
+                Ok(Some((bytes, Some((chunks, encoder)))))
+            }
+            None => Ok(Some((encoder.finish()?, None))),
+        }
+    })
+    .boxed()
+}
+
+trait Encoder: Send {
+    fn write(&mut self, rows: &[Vec<Value>]) -> Result<Vec<u8>>;
+    fn finish(self: Box<Self>) -> Result<Vec<u8>>;
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Result<Vec<u8>> {
+        let mut buffer = self.0.lock().map_err(|_| anyhow!("Export buffer poisoned"))?;
+        Ok(std::mem::take(&mut *buffer))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("Export buffer poisoned"))?
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct CsvEncoder {
+    writer: csv::Writer<SharedBuffer>,
+    buffer: SharedBuffer,
+}
+
+impl CsvEncoder {
+    fn new(columns: &[Column]) -> Result<Self> {
+        let buffer = SharedBuffer::default();
+        let mut writer = csv::Writer::from_writer(buffer.clone());
+        writer.write_record(columns.iter().map(|column| column.name))?;
+        Ok(Self { writer, buffer })
+    }
+}
+
+impl Encoder for CsvEncoder {
+    fn write(&mut self, rows: &[Vec<Value>]) -> Result<Vec<u8>> {
+        for row in rows {
+            self.writer.write_record(row.iter().map(Value::to_string))?;
+        }
+        self.writer.flush()?;
+        self.buffer.take()
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Vec<u8>> {
+        self.writer.flush()?;
+        self.buffer.take()
+    }
+}
+
+struct ParquetEncoder {
+    writer: SerializedFileWriter<SharedBuffer>,
+    buffer: SharedBuffer,
+    columns: &'static [Column],
+}
+
+impl ParquetEncoder {
+    fn new(dataset: ExportDataset) -> Result<Self> {
+        let columns = dataset.columns();
+        let fields = columns
+            .iter()
+            .map(|column| match column.kind {
+                ColumnKind::Integer => format!("REQUIRED INT64 {} (INTEGER(64,false));", column.name),
+                ColumnKind::Boolean => format!("REQUIRED BOOLEAN {};", column.name),
+                ColumnKind::Text => format!("REQUIRED BYTE_ARRAY {} (UTF8);", column.name),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let schema = parse_message_type(&format!("message {dataset} {{ {fields} }}"))?;
+        let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+        let buffer = SharedBuffer::default();
+        let writer = SerializedFileWriter::new(buffer.clone(), Arc::new(schema), Arc::new(properties))?;
+        Ok(Self {
+            writer,
+            buffer,
+            columns,
+        })
+    }
+}
+
+impl Encoder for ParquetEncoder {
+    fn write(&mut self, rows: &[Vec<Value>]) -> Result<Vec<u8>> {
+        let mut row_group = self.writer.next_row_group()?;
+        for (index, column) in self.columns.iter().enumerate() {
+            let mut writer = row_group
+                .next_column()?
+                .ok_or(anyhow!("Missing parquet column {}", column.name))?;
+            let values = rows.iter().map(|row| &row[index]);
+            match column.kind {
+                ColumnKind::Integer => {
+                    let values = values
+                        .map(|value| match value {
+                            // Stored as the bit pattern of the unsigned value, which the UINT_64 annotation restores
+                            Value::Integer(value) => Ok(*value as i64),
+                            _ => Err(anyhow!("Expected integer for column {}", column.name)),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    writer.typed::<Int64Type>().write_batch(&values, None, None)?;
+                }
+                ColumnKind::Boolean => {
+                    let values = values
+                        .map(|value| match value {
+                            Value::Boolean(value) => Ok(*value),
+                            _ => Err(anyhow!("Expected boolean for column {}", column.name)),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    writer.typed::<BoolType>().write_batch(&values, None, None)?;
+                }
+                ColumnKind::Text => {
+                    let values = values
+                        .map(|value| match value {
+                            Value::Text(value) => Ok(ByteArray::from(value.as_str())),
+                            _ => Err(anyhow!("Expected text for column {}", column.name)),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    writer.typed::<ByteArrayType>().write_batch(&values, None, None)?;
+                }
+            }
+            writer.close()?;
+        }
+        row_group.close()?;
+        self.buffer.take()
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<u8>> {
+        self.writer.close()?;
+        self.buffer.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::web::Bytes;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::RowAccessor,
+    };
+    use service::model::SlotStatus;
+
+    use super::*;
+
+    fn proposer_rows() -> Vec<Vec<Value>> {
+        [(64, SlotStatus::Proposed), (65, SlotStatus::Missed)]
+            .into_iter()
+            .map(|(slot, status)| {
+                Slot {
+                    slot,
+                    epoch: 2,
+                    proposer_index: u64::MAX - slot,
+                    status,
+                }
+                .values()
+            })
+            .collect()
+    }
+
+    async fn encode_rows(rows: Vec<Vec<Value>>, encoder: Box<dyn Encoder>) -> Vec<u8> {
+        let rows = stream::iter(rows.into_iter().map(Ok)).boxed();
+        let chunks = encode(rows, encoder).try_collect::<Vec<_>>().await.unwrap();
+        chunks.concat()
+    }
+
+    #[tokio::test]
+    async fn csv_round_trip() {
+        let encoder = CsvEncoder::new(ExportDataset::Proposers.columns()).unwrap();
+        let bytes = encode_rows(proposer_rows(), Box::new(encoder)).await;
+        let mut reader = csv::Reader::from_reader(bytes.as_slice());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["slot", "epoch", "proposer_index", "status"]
+        );
+        let records = reader.records().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], vec!["64", "2", &(u64::MAX - 64).to_string(), "proposed"]);
+        assert_eq!(records[1], vec!["65", "2", &(u64::MAX - 65).to_string(), "missed"]);
+    }
+
+    #[tokio::test]
+    async fn parquet_round_trip() {
+        let encoder = ParquetEncoder::new(ExportDataset::Proposers).unwrap();
+        let bytes = encode_rows(proposer_rows(), Box::new(encoder)).await;
+        let reader = SerializedFileReader::new(Bytes::from(bytes)).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows[0].get_ulong(0).unwrap(), 64);
+        assert_eq!(rows[0].get_ulong(1).unwrap(), 2);
+        assert_eq!(rows[0].get_ulong(2).unwrap(), u64::MAX - 64);
+        assert_eq!(rows[0].get_string(3).unwrap(), "proposed");
+        assert_eq!(rows[1].get_ulong(0).unwrap(), 65);
+        assert_eq!(rows[1].get_string(3).unwrap(), "missed");
+    }
+
+    #[tokio::test]
+    async fn idle_reader_gets_an_error() {
+        let limits = ExportLimits::new(1, Duration::from_millis(50));
+        let permit = limits.try_acquire().unwrap();
+        assert!(limits.try_acquire().is_none());
+        let chunks = stream::iter((0..10u8).map(|chunk| Ok(vec![chunk]))).boxed();
+        let mut guarded = limits.guard(permit, chunks);
+        assert_eq!(guarded.next().await.unwrap().unwrap(), vec![0]);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let rest = guarded.collect::<Vec<_>>().await;
+        assert!(rest.len() < 9);
+        let error = rest.last().unwrap().as_ref().unwrap_err();
+        assert!(error.to_string().contains("idle"));
+        assert!(limits.try_acquire().is_some());
+    }
+}
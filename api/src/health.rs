@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+use store::{DbHealth, Pool};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize)]
+struct Readiness {
+    status: &'static str,
+    database: DbHealth,
+}
+
+pub async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "status": "ok" }))
+}
+
+// An exhausted pool shows up as a timeout here, so the orchestrator stops routing traffic until connections free up
+pub async fn ready(pool: web::Data<Pool>) -> HttpResponse {
+    let database = store::check_health(&pool, CHECK_TIMEOUT).await;
+    if database.connected {
+        HttpResponse::Ok().json(Readiness { status: "ok", database })
+    } else {
+        HttpResponse::ServiceUnavailable().json(Readiness {
+            status: "unavailable",
+            database,
+        })
+    }
+}
@@ -1,36 +1,388 @@
 use std::sync::Arc;
 
-use actix_web::web;
+use actix_web::{web, HttpMessage};
 use async_graphql::{
+    connection::Connection,
     http::{playground_source, GraphQLPlaygroundConfig},
-    Context, EmptyMutation, EmptySubscription, FieldResult, MergedObject, Object, Schema,
+    Context, Data, FieldResult, MergedObject, Object, Schema, Subscription,
 };
-use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
-use service::{Service, ServiceImpl};
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
+use auth::ScopeGuard;
+use futures_util::{stream, Stream, StreamExt};
+use limit::{page_cost, AGGREGATE_COST, GROUP_AGGREGATE_COST};
+use model::{
+    connection, Attestation, BlobSidecar, BlobThroughput, BlockProduction, BlsToExecutionChange, Checkpoint, Committee,
+    ConnectionFields, Deposit, DutyOutcome, Epoch, ExecutionPayload, FeeRecipient, GroupMemberInput, MevIncome,
+    ProposalOutcome, RelayPayload, RelayUsage, Slashing, SlashingStatus, Slot, SlotFilter, SortDirection, Validator,
+    ValidatorFilter, ValidatorGroup, ValidatorSortField, VoluntaryExit, Withdrawal,
+};
+use service::{
+    model::{ApiKey, ApiScope, ChainEvent, PageRequest},
+    Service, ServiceImpl,
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::Instrument;
+
+pub mod auth;
+pub mod beacon;
+pub mod export;
+pub mod health;
+pub mod limit;
+pub mod loader;
+pub mod model;
+pub mod rest;
+pub mod telemetry;
+
+#[derive(MergedObject, Default)]
+pub struct Query(
+    AttestationQuery,
+    ProposalQuery,
+    SlashingQuery,
+    ExitQuery,
+    BalanceFlowQuery,
+    ExecutionQuery,
+    RelayQuery,
+    BlobQuery,
+    GroupQuery,
+    ValidatorQuery,
+    ChainQuery,
+);
 
 #[derive(MergedObject, Default)]
-pub struct Query(AttestationQuery);
+pub struct Mutation(GroupMutation);
+
+#[derive(Default)]
+pub struct Subscription;
 
 #[derive(Default)]
 pub struct AttestationQuery;
 
-pub type IndexerSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+#[derive(Default)]
+pub struct ProposalQuery;
+
+#[derive(Default)]
+pub struct SlashingQuery;
+
+#[derive(Default)]
+pub struct ExitQuery;
+
+#[derive(Default)]
+pub struct BalanceFlowQuery;
+
+#[derive(Default)]
+pub struct ExecutionQuery;
+
+#[derive(Default)]
+pub struct RelayQuery;
+
+#[derive(Default)]
+pub struct BlobQuery;
+
+#[derive(Default)]
+pub struct GroupQuery;
+
+#[derive(Default)]
+pub struct ValidatorQuery;
+
+#[derive(Default)]
+pub struct ChainQuery;
+
+#[derive(Default)]
+pub struct GroupMutation;
+
+pub type IndexerSchema = Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl AttestationQuery {
-    async fn participation_rate_for_epoch<'a>(&self, ctx: &'a Context<'_>, epoch: u64) -> FieldResult<f64> {
+    #[graphql(complexity = "AGGREGATE_COST")]
+    async fn participation_rate_for_epoch(&self, ctx: &Context<'_>, epoch: u64) -> FieldResult<f64> {
         let service = ctx.data::<Arc<ServiceImpl>>()?;
         Ok(service.get_participation_rate_for_epoch(epoch).await?)
     }
 
-    async fn participation_rate_for_validator<'a>(&self, ctx: &'a Context<'_>, validator: u64) -> FieldResult<f64> {
+    #[graphql(complexity = "AGGREGATE_COST")]
+    async fn participation_rate_for_validator(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<f64> {
         let service = ctx.data::<Arc<ServiceImpl>>()?;
         Ok(service.get_participation_rate_for_validator(validator).await?)
     }
 }
 
-pub async fn index(schema: web::Data<IndexerSchema>, req: GraphQLRequest) -> GraphQLResponse {
-    schema.execute(req.into_inner()).await.into()
+#[Object]
+impl ProposalQuery {
+    async fn block_count_for_epoch(&self, ctx: &Context<'_>, epoch: u64) -> FieldResult<u64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.block_count_for_epoch(epoch).await?)
+    }
+
+    async fn block_production_for_epoch(&self, ctx: &Context<'_>, epoch: u64) -> FieldResult<BlockProduction> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_block_production_for_epoch(epoch).await?.into())
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn missed_proposals_for_epoch(
+        &self,
+        ctx: &Context<'_>,
+        epoch: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, u64, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let slots = service.missed_proposals_for_epoch(epoch, &page).await?;
+        Ok(connection(slots.map(|slot| slot.slot)))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn missed_proposals_for_validator(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, u64, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let slots = service.missed_proposals_for_validator(validator, &page).await?;
+        Ok(connection(slots.map(|slot| slot.slot)))
+    }
+
+    async fn proposal_outcome(&self, ctx: &Context<'_>, slot: u64) -> FieldResult<Option<ProposalOutcome>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_proposal_outcome(slot).await?.map(ProposalOutcome::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn proposal_outcomes_for_validator(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, ProposalOutcome, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let outcomes = service
+            .get_proposal_outcome_page_for_validator(validator, &page)
+            .await?;
+        Ok(connection(outcomes))
+    }
+
+    async fn proposal_success_rate_for_validator(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<f64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_proposal_success_rate_for_validator(validator).await?)
+    }
+}
+
+#[Object]
+impl SlashingQuery {
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn slashings(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Slashing, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_slashings(&page).await?))
+    }
+
+    async fn slashing_status(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<Option<SlashingStatus>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_slashing_status(validator).await?.map(SlashingStatus::from))
+    }
+}
+
+#[Object]
+impl ExitQuery {
+    async fn voluntary_exit(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<Option<VoluntaryExit>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_voluntary_exit(validator).await?.map(VoluntaryExit::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn pending_voluntary_exits(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, VoluntaryExit, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_pending_voluntary_exits(&page).await?))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn processed_voluntary_exits(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, VoluntaryExit, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_processed_voluntary_exits(&page).await?))
+    }
+
+    async fn bls_to_execution_change(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+    ) -> FieldResult<Option<BlsToExecutionChange>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service
+            .get_bls_to_execution_change(validator)
+            .await?
+            .map(BlsToExecutionChange::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn pending_bls_to_execution_changes(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, BlsToExecutionChange, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_pending_bls_to_execution_changes(&page).await?))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn processed_bls_to_execution_changes(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, BlsToExecutionChange, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_processed_bls_to_execution_changes(&page).await?))
+    }
+}
+
+#[Object]
+impl BalanceFlowQuery {
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn deposits_for_validator(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Deposit, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let deposits = service.get_deposit_page_for_validator(validator, &page).await?;
+        Ok(connection(deposits))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn deposits_for_address(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Deposit, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_deposits_for_address(&address, &page).await?))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn withdrawals_for_validator(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Withdrawal, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let withdrawals = service.get_withdrawal_page_for_validator(validator, &page).await?;
+        Ok(connection(withdrawals))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn withdrawals_for_address(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Withdrawal, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        Ok(connection(service.get_withdrawals_for_address(&address, &page).await?))
+    }
+
+    async fn withdrawal_total_for_validator(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<u64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.withdrawal_total_for_validator(validator).await?)
+    }
+
+    async fn withdrawal_total_for_address(&self, ctx: &Context<'_>, address: String) -> FieldResult<u64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.withdrawal_total_for_address(&address).await?)
+    }
+}
+
+pub async fn index(
+    schema: web::Data<IndexerSchema>,
+    request: actix_web::HttpRequest,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut req = req.into_inner();
+    let span = tracing::info_span!(
+        "graphql_request",
+        operation = req.operation_name.as_deref(),
+        api_key = tracing::field::Empty
+    );
+    if let Some(key) = request.extensions().get::<ApiKey>() {
+        span.record("api_key", key.name.as_str());
+        req = req.data(key.clone());
+    }
+    schema.execute(req).instrument(span).await.into()
+}
+
+pub async fn index_ws(
+    schema: web::Data<IndexerSchema>,
+    req: actix_web::HttpRequest,
+    payload: web::Payload,
+) -> actix_web::Result<actix_web::HttpResponse> {
+    let mut data = Data::default();
+    if let Some(key) = req.extensions().get::<ApiKey>() {
+        data.insert(key.clone());
+    }
+    GraphQLSubscription::new(Schema::clone(&*schema))
+        .with_data(data)
+        .start(&req, payload)
 }
 
 pub async fn index_playground() -> actix_web::Result<actix_web::HttpResponse> {
@@ -40,3 +392,523 @@ pub async fn index_playground() -> actix_web::Result<actix_web::HttpResponse> {
             GraphQLPlaygroundConfig::new("/").subscription_endpoint("/"),
         )))
 }
+
+#[Object]
+impl ExecutionQuery {
+    async fn execution_payload(&self, ctx: &Context<'_>, slot: u64) -> FieldResult<Option<ExecutionPayload>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_execution_payload(slot).await?.map(ExecutionPayload::from))
+    }
+
+    async fn execution_payload_by_block_hash(
+        &self,
+        ctx: &Context<'_>,
+        block_hash: String,
+    ) -> FieldResult<Option<ExecutionPayload>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let payload = service.get_execution_payload_by_block_hash(&block_hash).await?;
+        Ok(payload.map(ExecutionPayload::from))
+    }
+
+    async fn execution_payload_by_block_number(
+        &self,
+        ctx: &Context<'_>,
+        block_number: u64,
+    ) -> FieldResult<Option<ExecutionPayload>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let payload = service.get_execution_payload_by_block_number(block_number).await?;
+        Ok(payload.map(ExecutionPayload::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn execution_payloads_for_proposer(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, ExecutionPayload, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let payloads = service.get_execution_payloads_for_proposer(validator, &page).await?;
+        Ok(connection(payloads))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn fee_recipients_for_proposer(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, FeeRecipient, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let recipients = service.get_fee_recipients_for_proposer(validator, &page).await?;
+        Ok(connection(recipients))
+    }
+}
+
+#[Object]
+impl RelayQuery {
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn relay_payloads_for_validator(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, RelayPayload, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let payloads = service.get_relay_payloads_for_validator(validator, &page).await?;
+        Ok(connection(payloads))
+    }
+
+    async fn mev_income_for_validator(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<MevIncome> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_mev_income_for_validator(validator).await?.into())
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn relay_usage_for_validator(
+        &self,
+        ctx: &Context<'_>,
+        validator: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, RelayUsage, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let usage = service.get_relay_usage_for_validator(validator, &page).await?;
+        Ok(connection(usage))
+    }
+}
+
+#[Object]
+impl BlobQuery {
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn blob_sidecars(
+        &self,
+        ctx: &Context<'_>,
+        slot: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, BlobSidecar, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let sidecars = service.get_blob_sidecar_page(slot, &page).await?;
+        Ok(connection(sidecars))
+    }
+
+    async fn blob_throughput_for_epoch(&self, ctx: &Context<'_>, epoch: u64) -> FieldResult<BlobThroughput> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_blob_throughput_for_epoch(epoch).await?.into())
+    }
+
+    async fn blob_throughput_for_proposer(&self, ctx: &Context<'_>, validator: u64) -> FieldResult<BlobThroughput> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_blob_throughput_for_proposer(validator).await?.into())
+    }
+}
+
+#[Object]
+impl GroupQuery {
+    async fn validator_group(&self, ctx: &Context<'_>, name: String) -> FieldResult<Option<ValidatorGroup>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_validator_group(&name).await?.map(ValidatorGroup::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn validator_groups(
+        &self,
+        ctx: &Context<'_>,
+        label: Option<String>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, ValidatorGroup, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let groups = service.get_validator_groups(label.as_deref(), &page).await?;
+        Ok(connection(groups))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn validator_group_indices(
+        &self,
+        ctx: &Context<'_>,
+        group: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, u64, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let indices = service.get_validator_group_index_page(&group, &page).await?;
+        Ok(connection(indices))
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + child_complexity")]
+    async fn participation_rate_for_group(
+        &self,
+        ctx: &Context<'_>,
+        group: String,
+        epoch: Option<u64>,
+    ) -> FieldResult<f64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_participation_rate_for_group(&group, epoch).await?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + page_cost(first, last, child_complexity)")]
+    async fn proposal_outcomes_for_group(
+        &self,
+        ctx: &Context<'_>,
+        group: String,
+        epoch: Option<u64>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, ProposalOutcome, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let outcomes = service.get_proposal_outcomes_for_group(&group, epoch, &page).await?;
+        Ok(connection(outcomes))
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + child_complexity")]
+    async fn proposal_success_rate_for_group(
+        &self,
+        ctx: &Context<'_>,
+        group: String,
+        epoch: Option<u64>,
+    ) -> FieldResult<f64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_proposal_success_rate_for_group(&group, epoch).await?)
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + page_cost(first, last, child_complexity)")]
+    async fn missed_proposals_for_group(
+        &self,
+        ctx: &Context<'_>,
+        group: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, u64, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let slots = service.missed_proposals_for_group(&group, &page).await?;
+        Ok(connection(slots.map(|slot| slot.slot)))
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + child_complexity")]
+    async fn withdrawal_total_for_group(&self, ctx: &Context<'_>, group: String) -> FieldResult<u64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.withdrawal_total_for_group(&group).await?)
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + child_complexity")]
+    async fn mev_income_for_group(&self, ctx: &Context<'_>, group: String) -> FieldResult<MevIncome> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_mev_income_for_group(&group).await?.into())
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + page_cost(first, last, child_complexity)")]
+    async fn relay_usage_for_group(
+        &self,
+        ctx: &Context<'_>,
+        group: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, RelayUsage, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let usage = service.get_relay_usage_for_group(&group, &page).await?;
+        Ok(connection(usage))
+    }
+
+    #[graphql(complexity = "GROUP_AGGREGATE_COST + child_complexity")]
+    async fn blob_throughput_for_group(&self, ctx: &Context<'_>, group: String) -> FieldResult<BlobThroughput> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_blob_throughput_for_group(&group).await?.into())
+    }
+}
+
+#[Object]
+impl GroupMutation {
+    #[graphql(guard = "ScopeGuard(ApiScope::Admin)")]
+    async fn create_validator_group(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        #[graphql(default)] labels: Vec<String>,
+    ) -> FieldResult<ValidatorGroup> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.create_validator_group(&name, &labels).await?.into())
+    }
+
+    #[graphql(guard = "ScopeGuard(ApiScope::Admin)")]
+    async fn delete_validator_group(&self, ctx: &Context<'_>, name: String) -> FieldResult<bool> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.delete_validator_group(&name).await?)
+    }
+
+    #[graphql(guard = "ScopeGuard(ApiScope::Admin)")]
+    async fn set_validator_group_labels(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        labels: Vec<String>,
+    ) -> FieldResult<ValidatorGroup> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.set_validator_group_labels(&name, &labels).await?.into())
+    }
+
+    #[graphql(guard = "ScopeGuard(ApiScope::Admin)")]
+    async fn add_validator_group_members(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        members: Vec<GroupMemberInput>,
+    ) -> FieldResult<ValidatorGroup> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let members = members
+            .into_iter()
+            .map(service::model::GroupMember::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(service.add_validator_group_members(&name, &members).await?.into())
+    }
+
+    #[graphql(guard = "ScopeGuard(ApiScope::Admin)")]
+    async fn remove_validator_group_members(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        members: Vec<GroupMemberInput>,
+    ) -> FieldResult<ValidatorGroup> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let members = members
+            .into_iter()
+            .map(service::model::GroupMember::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(service.remove_validator_group_members(&name, &members).await?.into())
+    }
+}
+
+#[Object]
+impl ValidatorQuery {
+    async fn validator(&self, ctx: &Context<'_>, index: u64) -> FieldResult<Option<Validator>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_validator(index).await?.map(Validator::from))
+    }
+
+    async fn validator_by_pubkey(&self, ctx: &Context<'_>, pubkey: String) -> FieldResult<Option<Validator>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_validator_by_pubkey(&pubkey).await?.map(Validator::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn validators_by_pubkey_prefix(
+        &self,
+        ctx: &Context<'_>,
+        prefix: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Validator, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let validators = service.get_validators_by_pubkey_prefix(&prefix, &page).await?;
+        Ok(connection(validators))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn validators_for_withdrawal_address(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Validator, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let validators = service.get_validators_for_withdrawal_address(&address, &page).await?;
+        Ok(connection(validators))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn validators(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: ValidatorFilter,
+        #[graphql(default)] sort: ValidatorSortField,
+        #[graphql(default)] direction: SortDirection,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Validator, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let validators = service
+            .get_validators_page(&filter.into(), sort.into(), direction.into(), &page)
+            .await?;
+        Ok(connection(validators))
+    }
+}
+
+#[Object]
+impl ChainQuery {
+    async fn epoch(&self, ctx: &Context<'_>, index: u64) -> FieldResult<Option<Epoch>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_epoch(index).await?.map(Epoch::from))
+    }
+
+    async fn slot(&self, ctx: &Context<'_>, slot: u64) -> FieldResult<Option<Slot>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_slot(slot).await?.map(Slot::from))
+    }
+
+    async fn committee(&self, ctx: &Context<'_>, slot: u64, index: u8) -> FieldResult<Option<Committee>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_committee(slot, index).await?.map(Committee::from))
+    }
+
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn committees_for_slot(
+        &self,
+        ctx: &Context<'_>,
+        slot: u64,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Committee, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let committees = service.get_committee_page_for_slot(slot, &page).await?;
+        Ok(connection(committees))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "page_cost(first, last, child_complexity)")]
+    async fn slots(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: SlotFilter,
+        #[graphql(default)] direction: SortDirection,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> FieldResult<Connection<String, Slot, ConnectionFields>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let page = PageRequest::new(first, after.as_deref(), last, before.as_deref())?;
+        let slots = service.get_slots_page(&filter.into(), direction.into(), &page).await?;
+        Ok(connection(slots))
+    }
+
+    async fn attestation(&self, ctx: &Context<'_>, epoch: u64, validator: u64) -> FieldResult<Option<Attestation>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_attestation(epoch, validator).await?.map(Attestation::from))
+    }
+}
+
+#[Subscription]
+impl Subscription {
+    async fn head_slots(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = Slot>> {
+        Ok(chain_events(ctx)?.filter_map(|event| async move {
+            match event {
+                ChainEvent::Head(slot) => Some(Slot::from(slot)),
+                _ => None,
+            }
+        }))
+    }
+
+    async fn finalized_checkpoints(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = Checkpoint>> {
+        Ok(chain_events(ctx)?.filter_map(|event| async move {
+            match event {
+                ChainEvent::FinalizedCheckpoint(checkpoint) => Some(Checkpoint::from(checkpoint)),
+                _ => None,
+            }
+        }))
+    }
+
+    async fn epochs(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = Epoch>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?.clone();
+        Ok(chain_events(ctx)?.filter_map(move |event| {
+            let service = service.clone();
+            async move {
+                let ChainEvent::Epoch { index } = event else {
+                    return None;
+                };
+                match service.get_epoch(index).await {
+                    Ok(epoch) => epoch.map(Epoch::from),
+                    Err(err) => {
+                        tracing::warn!("Failed to load epoch {index} for subscription: {err}");
+                        None
+                    }
+                }
+            }
+        }))
+    }
+
+    async fn duty_outcomes(
+        &self,
+        ctx: &Context<'_>,
+        validators: Vec<u64>,
+    ) -> FieldResult<impl Stream<Item = DutyOutcome>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?.clone();
+        let validators = Arc::new(validators);
+        Ok(chain_events(ctx)?
+            .then(move |event| {
+                let service = service.clone();
+                let validators = validators.clone();
+                async move {
+                    match service.get_duty_outcomes_for_event(&event, &validators).await {
+                        Ok(outcomes) => outcomes,
+                        Err(err) => {
+                            tracing::warn!("Failed to load duty outcomes for subscription: {err}");
+                            Vec::new()
+                        }
+                    }
+                }
+            })
+            .flat_map(|outcomes| stream::iter(outcomes.into_iter().map(DutyOutcome::from))))
+    }
+}
+
+fn chain_events(ctx: &Context<'_>) -> FieldResult<impl Stream<Item = ChainEvent>> {
+    let receiver = ctx.data::<broadcast::Sender<ChainEvent>>()?.subscribe();
+    Ok(stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(RecvError::Lagged(skipped)) => tracing::warn!("Subscriber skipped {skipped} chain events"),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }))
+}
@@ -0,0 +1,97 @@
+use std::{sync::Arc, time::Duration};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute, NextValidation},
+    ErrorExtensionValues, Response, ServerError, ValidationResult,
+};
+
+pub const SLOTS_PER_EPOCH: usize = 32;
+pub const MAX_COMMITTEES_PER_SLOT: usize = 64;
+// Mainnet committees hold a few hundred validators, so members are costed at the upper end of that
+pub const COMMITTEE_SIZE: usize = 512;
+pub const AGGREGATE_COST: usize = 20;
+pub const GROUP_AGGREGATE_COST: usize = 100;
+
+pub fn list_cost(limit: u64, child_complexity: usize) -> usize {
+    usize::try_from(limit)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(child_complexity)
+}
+
+pub fn page_cost(first: Option<u64>, last: Option<u64>, child_complexity: usize) -> usize {
+    list_cost(
+        first.or(last).unwrap_or(service::model::DEFAULT_PAGE_SIZE),
+        child_complexity,
+    )
+}
+
+#[derive(Clone, Copy)]
+pub struct QueryLimits {
+    pub max_depth: usize,
+    pub max_complexity: usize,
+    pub timeout: Duration,
+}
+
+impl ExtensionFactory for QueryLimits {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryLimitsExtension(*self))
+    }
+}
+
+struct QueryLimitsExtension(QueryLimits);
+
+fn limit_error(code: &str, message: String) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", code);
+    let mut error = ServerError::new(message, None);
+    error.extensions = Some(extensions);
+    error
+}
+
+#[async_trait::async_trait]
+impl Extension for QueryLimitsExtension {
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let result = next.run(ctx).await?;
+        let mut errors = Vec::new();
+        if result.depth > self.0.max_depth {
+            errors.push(limit_error(
+                "QUERY_TOO_DEEP",
+                format!("Query depth {} exceeds the limit of {}", result.depth, self.0.max_depth),
+            ));
+        }
+        if result.complexity > self.0.max_complexity {
+            errors.push(limit_error(
+                "QUERY_TOO_COMPLEX",
+                format!(
+                    "Query complexity {} exceeds the limit of {}, request fewer items or fields",
+                    result.complexity, self.0.max_complexity
+                ),
+            ));
+        }
+        if errors.is_empty() {
+            Ok(result)
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Only queries and mutations pass through execute, so subscriptions are not cut off by the timeout
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        match tokio::time::timeout(self.0.timeout, next.run(ctx, operation_name)).await {
+            Ok(response) => response,
+            Err(_) => Response::from_errors(vec![limit_error(
+                "QUERY_TIMEOUT",
+                format!("Query did not complete within {}s", self.0.timeout.as_secs()),
+            )]),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_trait::async_trait;
+use service::{
+    model::{
+        AttestationData, Committee, Deposit, Epoch, ProposalOutcome, SlashingStatus, Validator, VoluntaryExit,
+        Withdrawal,
+    },
+    Service, ServiceImpl,
+};
+
+pub struct Loaders {
+    pub validators: DataLoader<ValidatorLoader>,
+    pub committees: DataLoader<CommitteeLoader>,
+    pub epochs: DataLoader<EpochLoader>,
+    pub attestations: DataLoader<AttestationLoader>,
+    pub participation_rates: DataLoader<ParticipationRateLoader>,
+    pub proposal_duties: DataLoader<ProposalDutyLoader>,
+    pub deposits: DataLoader<DepositLoader>,
+    pub withdrawals: DataLoader<WithdrawalLoader>,
+    pub slashing_statuses: DataLoader<SlashingStatusLoader>,
+    pub voluntary_exits: DataLoader<VoluntaryExitLoader>,
+}
+
+impl Loaders {
+    pub fn new(service: Arc<ServiceImpl>) -> Self {
+        Self {
+            validators: DataLoader::new(ValidatorLoader(service.clone()), tokio::spawn),
+            committees: DataLoader::new(CommitteeLoader(service.clone()), tokio::spawn),
+            epochs: DataLoader::new(EpochLoader(service.clone()), tokio::spawn),
+            attestations: DataLoader::new(AttestationLoader(service.clone()), tokio::spawn),
+            participation_rates: DataLoader::new(ParticipationRateLoader(service.clone()), tokio::spawn),
+            proposal_duties: DataLoader::new(ProposalDutyLoader(service.clone()), tokio::spawn),
+            deposits: DataLoader::new(DepositLoader(service.clone()), tokio::spawn),
+            withdrawals: DataLoader::new(WithdrawalLoader(service.clone()), tokio::spawn),
+            slashing_statuses: DataLoader::new(SlashingStatusLoader(service.clone()), tokio::spawn),
+            voluntary_exits: DataLoader::new(VoluntaryExitLoader(service), tokio::spawn),
+        }
+    }
+}
+
+pub struct ValidatorLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<u64> for ValidatorLoader {
+    type Value = Validator;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        let validators = self.0.get_validators(keys).await?;
+        Ok(validators
+            .into_iter()
+            .map(|validator| (validator.index, validator))
+            .collect())
+    }
+}
+
+pub struct CommitteeLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<(u64, u8)> for CommitteeLoader {
+    type Value = Committee;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[(u64, u8)]) -> Result<HashMap<(u64, u8), Self::Value>, Self::Error> {
+        let committees = self.0.get_committees(keys).await?;
+        Ok(committees
+            .into_iter()
+            .map(|committee| ((committee.slot, committee.index), committee))
+            .collect())
+    }
+}
+
+pub struct EpochLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<u64> for EpochLoader {
+    type Value = Epoch;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        let epochs = self.0.get_epochs(keys).await?;
+        Ok(epochs.into_iter().map(|epoch| (epoch.index, epoch)).collect())
+    }
+}
+
+pub struct AttestationLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<(u64, u64)> for AttestationLoader {
+    type Value = AttestationData;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[(u64, u64)]) -> Result<HashMap<(u64, u64), Self::Value>, Self::Error> {
+        let attestations = self.0.get_attestation_data(keys).await?;
+        Ok(attestations
+            .into_iter()
+            .map(|attestation| ((attestation.epoch, attestation.validator), attestation))
+            .collect())
+    }
+}
+
+pub struct ParticipationRateLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<u64> for ParticipationRateLoader {
+    type Value = f64;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        Ok(self
+            .0
+            .get_participation_rates_for_validators(keys)
+            .await?
+            .into_iter()
+            .collect())
+    }
+}
+
+pub struct SlashingStatusLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<u64> for SlashingStatusLoader {
+    type Value = SlashingStatus;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        let statuses = self.0.get_slashing_statuses(keys).await?;
+        Ok(statuses
+            .into_iter()
+            .map(|status| (status.validator_index, status))
+            .collect())
+    }
+}
+
+pub struct VoluntaryExitLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<u64> for VoluntaryExitLoader {
+    type Value = VoluntaryExit;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        let exits = self.0.get_voluntary_exits(keys).await?;
+        Ok(exits.into_iter().map(|exit| (exit.validator_index, exit)).collect())
+    }
+}
+
+// History loaders are keyed by validator and limit. Fields in one query nearly always share a limit, so each
+// distinct limit is fetched with one query and validators without rows get an empty list.
+async fn load_recent<T, F, Fut>(
+    keys: &[(u64, u64)],
+    fetch: F,
+    validator: fn(&T) -> Option<u64>,
+) -> Result<HashMap<(u64, u64), Vec<T>>, Arc<anyhow::Error>>
+where
+    F: Fn(Vec<u64>, u64) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<T>>>,
+{
+    let mut limits: HashMap<u64, Vec<u64>> = HashMap::new();
+    for (index, limit) in keys {
+        limits.entry(*limit).or_default().push(*index);
+    }
+    let mut loaded = keys.iter().map(|key| (*key, Vec::new())).collect::<HashMap<_, _>>();
+    for (limit, indices) in limits {
+        for item in fetch(indices, limit).await? {
+            if let Some(list) = validator(&item).and_then(|index| loaded.get_mut(&(index, limit))) {
+                list.push(item);
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+pub struct ProposalDutyLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<(u64, u64)> for ProposalDutyLoader {
+    type Value = Vec<ProposalOutcome>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[(u64, u64)]) -> Result<HashMap<(u64, u64), Self::Value>, Self::Error> {
+        load_recent(
+            keys,
+            |validators, limit| async move {
+                self.0
+                    .get_recent_proposal_outcomes_for_validators(&validators, limit)
+                    .await
+            },
+            |outcome| Some(outcome.scheduled_proposer),
+        )
+        .await
+    }
+}
+
+pub struct DepositLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<(u64, u64)> for DepositLoader {
+    type Value = Vec<Deposit>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[(u64, u64)]) -> Result<HashMap<(u64, u64), Self::Value>, Self::Error> {
+        load_recent(
+            keys,
+            |validators, limit| async move { self.0.get_recent_deposits_for_validators(&validators, limit).await },
+            |deposit| deposit.validator_index,
+        )
+        .await
+    }
+}
+
+pub struct WithdrawalLoader(Arc<ServiceImpl>);
+
+#[async_trait]
+impl Loader<(u64, u64)> for WithdrawalLoader {
+    type Value = Vec<Withdrawal>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[(u64, u64)]) -> Result<HashMap<(u64, u64), Self::Value>, Self::Error> {
+        load_recent(
+            keys,
+            |validators, limit| async move { self.0.get_recent_withdrawals_for_validators(&validators, limit).await },
+            |withdrawal| Some(withdrawal.validator_index),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn load_recent_queries_once_per_limit() {
+        let calls = Mutex::new(Vec::new());
+        let loaded = load_recent(
+            &[(1, 10), (2, 10), (3, 5)],
+            |validators, limit| {
+                calls.lock().unwrap().push((validators.clone(), limit));
+                async move { Ok(validators.into_iter().filter(|index| *index != 2).collect::<Vec<_>>()) }
+            },
+            |index| Some(*index),
+        )
+        .await
+        .unwrap();
+        let mut calls = calls.into_inner().unwrap();
+        calls.sort();
+        assert_eq!(calls, vec![(vec![1, 2], 10), (vec![3], 5)]);
+        assert_eq!(loaded[&(1, 10)], vec![1]);
+        assert!(loaded[&(2, 10)].is_empty());
+        assert_eq!(loaded[&(3, 5)], vec![3]);
+    }
+}
@@ -1,14 +1,21 @@
-use std::{net::TcpListener, sync::Arc};
+use std::{net::TcpListener, sync::Arc, time::Duration};
 
 use actix_web::{guard, middleware, web, App, HttpServer};
 use anyhow::Result;
-use api::Query;
-use async_graphql::{EmptyMutation, EmptySubscription, Schema};
-use envconfig::Envconfig;
-use store::{
-    attestation::PostgresAttestationRepository, committee::PostgresCommitteeRepository, epoch::PostgresEpochRepository,
-    proposer::PostgresProposerRepository, validator::PostgresValidatorRepository, DbConfig,
+use api::{
+    auth::{ApiKeyAuth, AuthState},
+    export::ExportLimits,
+    limit::QueryLimits,
+    loader::Loaders,
+    telemetry::GraphQLMetrics,
+    Mutation, Query, Subscription,
 };
+use async_graphql::Schema;
+use envconfig::Envconfig;
+use logging::LogConfig;
+use service::{cache::ResponseCache, ServiceImpl};
+use store::{cache::PostgresCacheRepository, DbConfig};
+use tokio::sync::broadcast::{self, error::RecvError};
 use url::Url;
 
 #[derive(Envconfig, Clone)]
@@ -21,6 +28,36 @@ pub struct AppConfig {
 
     #[envconfig(from = "APP_PORT", default = "8080")]
     pub port: u16,
+
+    #[envconfig(from = "API_AUTH", default = "true")]
+    pub auth: bool,
+
+    #[envconfig(from = "CACHE_CAPACITY", default = "10000")]
+    pub cache_capacity: usize,
+
+    #[envconfig(from = "CACHE_HEAD_TTL_SECS", default = "12")]
+    pub cache_head_ttl_secs: u64,
+
+    #[envconfig(from = "SHARED_CACHE", default = "false")]
+    pub shared_cache: bool,
+
+    #[envconfig(from = "GRAPHQL_MAX_DEPTH", default = "16")]
+    pub graphql_max_depth: usize,
+
+    #[envconfig(from = "GRAPHQL_MAX_COMPLEXITY", default = "20000")]
+    pub graphql_max_complexity: usize,
+
+    #[envconfig(from = "GRAPHQL_TIMEOUT_SECS", default = "30")]
+    pub graphql_timeout_secs: u64,
+
+    #[envconfig(from = "EXPORT_MAX_CONCURRENT", default = "2")]
+    pub export_max_concurrent: usize,
+
+    #[envconfig(from = "EXPORT_IDLE_TIMEOUT_SECS", default = "60")]
+    pub export_idle_timeout_secs: u64,
+
+    #[envconfig(from = "SHUTDOWN_TIMEOUT_SECS", default = "30")]
+    pub shutdown_timeout_secs: u64,
 }
 
 impl AppConfig {
@@ -31,40 +68,117 @@ impl AppConfig {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    env_logger::init();
+    let _log_guard = logging::init(&LogConfig::init_from_env()?, "api")?;
 
     let db_config = DbConfig::init_from_env()?;
-    let db_pool = store::connect(db_config).await;
+    let db_pool = store::connect(db_config.clone()).await;
 
     let app_config = AppConfig::init_from_env()?;
     let listener = TcpListener::bind(app_config.connection_string())?;
 
-    let epoch_repository = Arc::new(PostgresEpochRepository::new(db_pool.clone()));
-    let validator_repository = Arc::new(PostgresValidatorRepository::new(db_pool.clone()));
-    let attestation_repository = Arc::new(PostgresAttestationRepository::new(db_pool.clone()));
-    let committee_repository = Arc::new(PostgresCommitteeRepository::new(db_pool.clone()));
-    let proposer_repository = Arc::new(PostgresProposerRepository::new(db_pool.clone()));
-    let service = Arc::new(service::ServiceImpl::new(
-        epoch_repository,
-        validator_repository,
-        attestation_repository,
-        committee_repository,
-        proposer_repository,
-    ));
-
-    let schema = Schema::build(Query::default(), EmptyMutation, EmptySubscription)
-        .data(service)
+    let mut cache = ResponseCache::new(
+        app_config.cache_capacity,
+        Duration::from_secs(app_config.cache_head_ttl_secs),
+    );
+    if app_config.shared_cache {
+        cache = cache.with_shared(Arc::new(PostgresCacheRepository::new(db_pool.clone())));
+    }
+    let health_pool = db_pool.clone();
+    let service = Arc::new(ServiceImpl::new(store::repositories(db_pool)).with_cache(cache));
+
+    let auth_enabled = app_config.auth;
+    let auth_state = Arc::new(AuthState::new(service.clone()));
+    let usage_state = auth_state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            if let Err(err) = usage_state.flush_usage().await {
+                tracing::error!("Failed to record API key usage: {err}");
+            }
+        }
+    });
+
+    let (events, _) = broadcast::channel(1024);
+    let mut cache_events = events.subscribe();
+    let cache_service = service.clone();
+    tokio::spawn(async move {
+        loop {
+            match cache_events.recv().await {
+                Ok(event) => {
+                    if let Err(err) = cache_service.apply_chain_event(&event).await {
+                        tracing::error!("Failed to invalidate cached responses: {err}");
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => tracing::warn!("Cache invalidation skipped {skipped} chain events"),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+    let listener_events = events.clone();
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = store::event::listen_events(db_config.clone(), listener_events.clone()).await {
+                tracing::error!("Chain event listener stopped: {err}");
+            }
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    });
+
+    let schema = Schema::build(Query::default(), Mutation::default(), Subscription)
+        .data(Loaders::new(service.clone()))
+        .data(service.clone())
+        .data(events)
+        .extension(GraphQLMetrics)
+        .extension(QueryLimits {
+            max_depth: app_config.graphql_max_depth,
+            max_complexity: app_config.graphql_max_complexity,
+            timeout: Duration::from_secs(app_config.graphql_timeout_secs),
+        })
         .finish();
 
+    let export_limits = ExportLimits::new(
+        app_config.export_max_concurrent,
+        Duration::from_secs(app_config.export_idle_timeout_secs),
+    );
+    let server_auth_state = auth_state.clone();
     let server = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(schema.clone()))
+            .app_data(web::Data::new(service.clone()))
+            .app_data(web::Data::new(health_pool.clone()))
+            .app_data(web::Data::new(export_limits.clone()))
+            .wrap(middleware::Condition::new(
+                auth_enabled,
+                ApiKeyAuth::new(server_auth_state.clone()),
+            ))
             .wrap(middleware::Logger::default())
+            .service(
+                web::resource("/metrics")
+                    .guard(guard::Get())
+                    .to(api::telemetry::metrics),
+            )
+            .route("/health/live", web::get().to(api::health::live))
+            .route("/health/ready", web::get().to(api::health::ready))
+            .configure(api::rest::configure)
+            .configure(api::beacon::configure)
+            .service(
+                web::resource("/")
+                    .guard(guard::Get())
+                    .guard(guard::Header("upgrade", "websocket"))
+                    .to(api::index_ws),
+            )
             .service(web::resource("/").guard(guard::Get()).to(api::index_playground))
             .service(web::resource("/").guard(guard::Post()).to(api::index))
     })
     .listen(listener)?
+    .shutdown_timeout(app_config.shutdown_timeout_secs)
     .run();
 
-    Ok(server.await?)
+    // Actix stops accepting connections on SIGINT or SIGTERM and waits for in-flight requests before returning
+    server.await?;
+    tracing::info!("Server stopped, recording remaining API key usage");
+    auth_state.flush_usage().await?;
+
+    Ok(())
 }
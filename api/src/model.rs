@@ -0,0 +1,941 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use async_graphql::{
+    connection::{Connection, Edge},
+    ComplexObject, Context, Enum, FieldResult, InputObject, OutputType, SimpleObject,
+};
+use serde::Serialize;
+use service::{Service, ServiceImpl};
+use utoipa::ToSchema;
+
+use crate::{
+    limit::{list_cost, AGGREGATE_COST, COMMITTEE_SIZE, MAX_COMMITTEES_PER_SLOT, SLOTS_PER_EPOCH},
+    loader::Loaders,
+};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotStatus {
+    Proposed,
+    Missed,
+    Orphaned,
+}
+
+impl From<service::model::SlotStatus> for SlotStatus {
+    fn from(status: service::model::SlotStatus) -> Self {
+        match status {
+            service::model::SlotStatus::Proposed => Self::Proposed,
+            service::model::SlotStatus::Missed => Self::Missed,
+            service::model::SlotStatus::Orphaned => Self::Orphaned,
+        }
+    }
+}
+
+impl From<SlotStatus> for service::model::SlotStatus {
+    fn from(status: SlotStatus) -> Self {
+        match status {
+            SlotStatus::Proposed => Self::Proposed,
+            SlotStatus::Missed => Self::Missed,
+            SlotStatus::Orphaned => Self::Orphaned,
+        }
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+#[graphql(complex)]
+pub struct ProposalOutcome {
+    pub slot: u64,
+    pub epoch: u64,
+    pub scheduled_proposer: u64,
+    pub actual_proposer: Option<u64>,
+    pub status: Option<SlotStatus>,
+}
+
+impl From<service::model::ProposalOutcome> for ProposalOutcome {
+    fn from(outcome: service::model::ProposalOutcome) -> Self {
+        Self {
+            slot: outcome.slot,
+            epoch: outcome.epoch,
+            scheduled_proposer: outcome.scheduled_proposer,
+            actual_proposer: outcome.actual_proposer,
+            status: outcome.status.map(SlotStatus::from),
+        }
+    }
+}
+
+#[ComplexObject]
+impl ProposalOutcome {
+    async fn scheduled_validator(&self, ctx: &Context<'_>) -> FieldResult<Option<Validator>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .validators
+            .load_one(self.scheduled_proposer)
+            .await?
+            .map(Validator::from))
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+#[graphql(complex)]
+pub struct Slot {
+    pub slot: u64,
+    pub epoch: u64,
+    pub proposer_index: u64,
+    pub status: SlotStatus,
+}
+
+impl From<service::model::Slot> for Slot {
+    fn from(slot: service::model::Slot) -> Self {
+        Self {
+            slot: slot.slot,
+            epoch: slot.epoch,
+            proposer_index: slot.proposer_index,
+            status: slot.status.into(),
+        }
+    }
+}
+
+#[ComplexObject]
+impl Slot {
+    async fn proposer(&self, ctx: &Context<'_>) -> FieldResult<Option<Validator>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .validators
+            .load_one(self.proposer_index)
+            .await?
+            .map(Validator::from))
+    }
+
+    async fn epoch_details(&self, ctx: &Context<'_>) -> FieldResult<Option<Epoch>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders.epochs.load_one(self.epoch).await?.map(Epoch::from))
+    }
+
+    async fn outcome(&self, ctx: &Context<'_>) -> FieldResult<Option<ProposalOutcome>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service
+            .get_proposal_outcome(self.slot)
+            .await?
+            .map(ProposalOutcome::from))
+    }
+
+    #[graphql(complexity = "MAX_COMMITTEES_PER_SLOT * child_complexity")]
+    async fn committees(&self, ctx: &Context<'_>) -> FieldResult<Vec<Committee>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let committees = service.get_committees_for_slot(self.slot).await?;
+        Ok(committees.into_iter().map(Committee::from).collect())
+    }
+
+    async fn execution_payload(&self, ctx: &Context<'_>) -> FieldResult<Option<ExecutionPayload>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service
+            .get_execution_payload(self.slot)
+            .await?
+            .map(ExecutionPayload::from))
+    }
+
+    async fn blob_sidecars(&self, ctx: &Context<'_>) -> FieldResult<Vec<BlobSidecar>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let sidecars = service.get_blob_sidecars(self.slot).await?;
+        Ok(sidecars.into_iter().map(BlobSidecar::from).collect())
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+pub struct BlockProduction {
+    pub epoch: u64,
+    pub proposed: u64,
+    pub missed: u64,
+    pub orphaned: u64,
+    pub slots: Vec<Slot>,
+}
+
+impl From<service::model::BlockProduction> for BlockProduction {
+    fn from(production: service::model::BlockProduction) -> Self {
+        Self {
+            epoch: production.epoch,
+            proposed: production.proposed,
+            missed: production.missed,
+            orphaned: production.orphaned,
+            slots: production.slots.into_iter().map(Slot::from).collect(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum SlashingKind {
+    Proposer,
+    Attester,
+}
+
+impl From<service::model::SlashingKind> for SlashingKind {
+    fn from(kind: service::model::SlashingKind) -> Self {
+        match kind {
+            service::model::SlashingKind::Proposer => Self::Proposer,
+            service::model::SlashingKind::Attester => Self::Attester,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Slashing {
+    pub slot: u64,
+    pub epoch: u64,
+    pub kind: SlashingKind,
+    pub validator_index: u64,
+    pub proposer_index: u64,
+    pub whistleblower_index: u64,
+    pub evidence: String,
+}
+
+impl From<service::model::Slashing> for Slashing {
+    fn from(slashing: service::model::Slashing) -> Self {
+        Self {
+            slot: slashing.slot,
+            epoch: slashing.epoch,
+            kind: slashing.kind.into(),
+            validator_index: slashing.validator_index,
+            proposer_index: slashing.proposer_index,
+            whistleblower_index: slashing.whistleblower_index,
+            evidence: slashing.evidence,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct SlashingStatus {
+    pub validator_index: u64,
+    pub slashed: bool,
+    pub slashings: Vec<Slashing>,
+}
+
+impl From<service::model::SlashingStatus> for SlashingStatus {
+    fn from(status: service::model::SlashingStatus) -> Self {
+        Self {
+            validator_index: status.validator_index,
+            slashed: status.slashed,
+            slashings: status.slashings.into_iter().map(Slashing::from).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct VoluntaryExit {
+    pub validator_index: u64,
+    pub epoch: u64,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl From<service::model::VoluntaryExit> for VoluntaryExit {
+    fn from(exit: service::model::VoluntaryExit) -> Self {
+        Self {
+            validator_index: exit.validator_index,
+            epoch: exit.epoch,
+            inclusion_slot: exit.inclusion_slot,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BlsToExecutionChange {
+    pub validator_index: u64,
+    pub from_bls_pubkey: String,
+    pub to_execution_address: String,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl From<service::model::BlsToExecutionChange> for BlsToExecutionChange {
+    fn from(change: service::model::BlsToExecutionChange) -> Self {
+        Self {
+            validator_index: change.validator_index,
+            from_bls_pubkey: change.from_bls_pubkey,
+            to_execution_address: change.to_execution_address,
+            inclusion_slot: change.inclusion_slot,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Deposit {
+    pub slot: u64,
+    pub position: u64,
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub amount: u64,
+    pub validator_index: Option<u64>,
+}
+
+impl From<service::model::Deposit> for Deposit {
+    fn from(deposit: service::model::Deposit) -> Self {
+        Self {
+            slot: deposit.slot,
+            position: deposit.position,
+            pubkey: deposit.pubkey,
+            withdrawal_credentials: deposit.withdrawal_credentials,
+            amount: deposit.amount,
+            validator_index: deposit.validator_index,
+        }
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+pub struct Withdrawal {
+    pub index: u64,
+    pub slot: u64,
+    pub validator_index: u64,
+    pub address: String,
+    pub amount: u64,
+}
+
+impl From<service::model::Withdrawal> for Withdrawal {
+    fn from(withdrawal: service::model::Withdrawal) -> Self {
+        Self {
+            index: withdrawal.index,
+            slot: withdrawal.slot,
+            validator_index: withdrawal.validator_index,
+            address: withdrawal.address,
+            amount: withdrawal.amount,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ExecutionPayload {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub block_number: u64,
+    pub block_hash: String,
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: u64,
+    pub timestamp: u64,
+    pub transaction_count: u64,
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl From<service::model::ExecutionPayload> for ExecutionPayload {
+    fn from(payload: service::model::ExecutionPayload) -> Self {
+        Self {
+            slot: payload.slot,
+            proposer_index: payload.proposer_index,
+            block_number: payload.block_number,
+            block_hash: payload.block_hash,
+            parent_hash: payload.parent_hash,
+            fee_recipient: payload.fee_recipient,
+            gas_used: payload.gas_used,
+            gas_limit: payload.gas_limit,
+            base_fee_per_gas: payload.base_fee_per_gas,
+            timestamp: payload.timestamp,
+            transaction_count: payload.transaction_count,
+            blob_gas_used: payload.blob_gas_used,
+            excess_blob_gas: payload.excess_blob_gas,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct FeeRecipient {
+    pub address: String,
+    pub block_count: u64,
+}
+
+impl From<service::model::FeeRecipient> for FeeRecipient {
+    fn from(recipient: service::model::FeeRecipient) -> Self {
+        Self {
+            address: recipient.address,
+            block_count: recipient.block_count,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct RelayPayload {
+    pub relay: String,
+    pub slot: u64,
+    pub block_hash: String,
+    pub builder_pubkey: String,
+    pub proposer_pubkey: String,
+    pub proposer_fee_recipient: String,
+    pub value: String,
+}
+
+impl From<service::model::RelayPayload> for RelayPayload {
+    fn from(payload: service::model::RelayPayload) -> Self {
+        Self {
+            relay: payload.relay,
+            slot: payload.slot,
+            block_hash: payload.block_hash,
+            builder_pubkey: payload.builder_pubkey,
+            proposer_pubkey: payload.proposer_pubkey,
+            proposer_fee_recipient: payload.proposer_fee_recipient,
+            value: payload.value.to_string(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct MevIncome {
+    pub block_count: u64,
+    pub total_value: String,
+}
+
+impl From<service::model::MevIncome> for MevIncome {
+    fn from(income: service::model::MevIncome) -> Self {
+        Self {
+            block_count: income.block_count,
+            total_value: income.total_value.to_string(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct RelayUsage {
+    pub relay: String,
+    pub block_count: u64,
+    pub total_value: String,
+}
+
+impl From<service::model::RelayUsage> for RelayUsage {
+    fn from(usage: service::model::RelayUsage) -> Self {
+        Self {
+            relay: usage.relay,
+            block_count: usage.block_count,
+            total_value: usage.total_value.to_string(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BlobSidecar {
+    pub slot: u64,
+    pub epoch: u64,
+    pub proposer_index: u64,
+    pub index: u64,
+    pub kzg_commitment: String,
+    pub kzg_proof: Option<String>,
+    pub size: u64,
+    pub blob: Option<String>,
+}
+
+impl From<service::model::BlobSidecar> for BlobSidecar {
+    fn from(sidecar: service::model::BlobSidecar) -> Self {
+        Self {
+            slot: sidecar.slot,
+            epoch: sidecar.epoch,
+            proposer_index: sidecar.proposer_index,
+            index: sidecar.index,
+            kzg_commitment: sidecar.kzg_commitment,
+            kzg_proof: sidecar.kzg_proof,
+            size: sidecar.size,
+            blob: sidecar.blob,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct BlobThroughput {
+    pub block_count: u64,
+    pub blob_count: u64,
+    pub total_size: u64,
+}
+
+impl From<service::model::BlobThroughput> for BlobThroughput {
+    fn from(throughput: service::model::BlobThroughput) -> Self {
+        Self {
+            block_count: throughput.block_count,
+            blob_count: throughput.blob_count,
+            total_size: throughput.total_size,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum GroupMemberKind {
+    Index,
+    Pubkey,
+    WithdrawalCredentials,
+}
+
+impl From<service::model::GroupMemberKind> for GroupMemberKind {
+    fn from(kind: service::model::GroupMemberKind) -> Self {
+        match kind {
+            service::model::GroupMemberKind::Index => Self::Index,
+            service::model::GroupMemberKind::Pubkey => Self::Pubkey,
+            service::model::GroupMemberKind::WithdrawalCredentials => Self::WithdrawalCredentials,
+        }
+    }
+}
+
+impl From<GroupMemberKind> for service::model::GroupMemberKind {
+    fn from(kind: GroupMemberKind) -> Self {
+        match kind {
+            GroupMemberKind::Index => Self::Index,
+            GroupMemberKind::Pubkey => Self::Pubkey,
+            GroupMemberKind::WithdrawalCredentials => Self::WithdrawalCredentials,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GroupMember {
+    pub kind: GroupMemberKind,
+    pub value: String,
+}
+
+impl From<service::model::GroupMember> for GroupMember {
+    fn from(member: service::model::GroupMember) -> Self {
+        Self {
+            kind: member.kind.into(),
+            value: member.value,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct GroupMemberInput {
+    pub kind: GroupMemberKind,
+    pub value: String,
+}
+
+impl TryFrom<GroupMemberInput> for service::model::GroupMember {
+    type Error = anyhow::Error;
+
+    fn try_from(input: GroupMemberInput) -> Result<Self, Self::Error> {
+        service::model::GroupMember::new(input.kind.into(), &input.value)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ValidatorGroup {
+    pub name: String,
+    pub labels: Vec<String>,
+    pub members: Vec<GroupMember>,
+}
+
+impl From<service::model::ValidatorGroup> for ValidatorGroup {
+    fn from(group: service::model::ValidatorGroup) -> Self {
+        Self {
+            name: group.name,
+            labels: group.labels,
+            members: group.members.into_iter().map(GroupMember::from).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+#[graphql(complex)]
+pub struct Validator {
+    pub index: u64,
+    pub pubkey: String,
+    pub attestations: u64,
+    pub activation_epoch: u64,
+    pub exit_epoch: u64,
+    pub slashed: bool,
+    pub withdrawal_credentials: String,
+}
+
+impl From<service::model::Validator> for Validator {
+    fn from(validator: service::model::Validator) -> Self {
+        Self {
+            index: validator.index,
+            pubkey: validator.pubkey,
+            attestations: validator.attestations,
+            activation_epoch: validator.activation_epoch,
+            exit_epoch: validator.exit_epoch,
+            slashed: validator.slashed,
+            withdrawal_credentials: validator.withdrawal_credentials,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Validator {
+    #[graphql(complexity = "AGGREGATE_COST")]
+    async fn participation_rate(&self, ctx: &Context<'_>) -> FieldResult<f64> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .participation_rates
+            .load_one(self.index)
+            .await?
+            .ok_or(anyhow!("Validator not found"))?)
+    }
+
+    #[graphql(complexity = "list_cost(limit, child_complexity)")]
+    async fn proposal_duties(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: u64,
+    ) -> FieldResult<Vec<ProposalOutcome>> {
+        let loaders = ctx.data::<Loaders>()?;
+        let outcomes = loaders.proposal_duties.load_one((self.index, limit)).await?;
+        Ok(outcomes
+            .unwrap_or_default()
+            .into_iter()
+            .map(ProposalOutcome::from)
+            .collect())
+    }
+
+    async fn attestation(&self, ctx: &Context<'_>, epoch: u64) -> FieldResult<Option<Attestation>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .attestations
+            .load_one((epoch, self.index))
+            .await?
+            .map(Attestation::from))
+    }
+
+    #[graphql(complexity = "list_cost(limit, child_complexity)")]
+    async fn attestation_history(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: u64,
+    ) -> FieldResult<Vec<Attestation>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let attestations = service.get_attestations_for_validator(self.index, limit).await?;
+        Ok(attestations.into_iter().map(Attestation::from).collect())
+    }
+
+    async fn slashing_status(&self, ctx: &Context<'_>) -> FieldResult<Option<SlashingStatus>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .slashing_statuses
+            .load_one(self.index)
+            .await?
+            .map(SlashingStatus::from))
+    }
+
+    async fn voluntary_exit(&self, ctx: &Context<'_>) -> FieldResult<Option<VoluntaryExit>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .voluntary_exits
+            .load_one(self.index)
+            .await?
+            .map(VoluntaryExit::from))
+    }
+
+    #[graphql(complexity = "list_cost(limit, child_complexity)")]
+    async fn deposits(&self, ctx: &Context<'_>, #[graphql(default = 100)] limit: u64) -> FieldResult<Vec<Deposit>> {
+        let loaders = ctx.data::<Loaders>()?;
+        let deposits = loaders.deposits.load_one((self.index, limit)).await?;
+        Ok(deposits.unwrap_or_default().into_iter().map(Deposit::from).collect())
+    }
+
+    #[graphql(complexity = "list_cost(limit, child_complexity)")]
+    async fn withdrawals(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 100)] limit: u64,
+    ) -> FieldResult<Vec<Withdrawal>> {
+        let loaders = ctx.data::<Loaders>()?;
+        let withdrawals = loaders.withdrawals.load_one((self.index, limit)).await?;
+        Ok(withdrawals
+            .unwrap_or_default()
+            .into_iter()
+            .map(Withdrawal::from)
+            .collect())
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+#[graphql(complex)]
+pub struct Epoch {
+    pub index: u64,
+    pub active_validators: u64,
+    pub total_validators: u64,
+    pub attestations: u64,
+}
+
+impl From<service::model::Epoch> for Epoch {
+    fn from(epoch: service::model::Epoch) -> Self {
+        Self {
+            index: epoch.index,
+            active_validators: epoch.active_validators,
+            total_validators: epoch.total_validators,
+            attestations: epoch.attestations,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Epoch {
+    #[graphql(complexity = "AGGREGATE_COST")]
+    async fn participation_rate(&self, ctx: &Context<'_>) -> FieldResult<f64> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_participation_rate_for_epoch(self.index).await?)
+    }
+
+    #[graphql(complexity = "SLOTS_PER_EPOCH * child_complexity")]
+    async fn slots(&self, ctx: &Context<'_>) -> FieldResult<Vec<Slot>> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        let slots = service.get_slots_for_epoch(self.index).await?;
+        Ok(slots.into_iter().map(Slot::from).collect())
+    }
+
+    #[graphql(complexity = "AGGREGATE_COST + child_complexity")]
+    async fn block_production(&self, ctx: &Context<'_>) -> FieldResult<BlockProduction> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_block_production_for_epoch(self.index).await?.into())
+    }
+
+    async fn blob_throughput(&self, ctx: &Context<'_>) -> FieldResult<BlobThroughput> {
+        let service = ctx.data::<Arc<ServiceImpl>>()?;
+        Ok(service.get_blob_throughput_for_epoch(self.index).await?.into())
+    }
+}
+
+#[derive(SimpleObject, Serialize, ToSchema)]
+#[graphql(complex)]
+pub struct Committee {
+    pub slot: u64,
+    pub index: u8,
+    pub validators: Vec<u64>,
+}
+
+impl From<service::model::Committee> for Committee {
+    fn from(committee: service::model::Committee) -> Self {
+        Self {
+            slot: committee.slot,
+            index: committee.index,
+            validators: committee.validators,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Committee {
+    #[graphql(complexity = "COMMITTEE_SIZE * child_complexity")]
+    async fn members(&self, ctx: &Context<'_>) -> FieldResult<Vec<Validator>> {
+        let loaders = ctx.data::<Loaders>()?;
+        let mut validators = loaders.validators.load_many(self.validators.iter().copied()).await?;
+        Ok(self
+            .validators
+            .iter()
+            .filter_map(|index| validators.remove(index))
+            .map(Validator::from)
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Attestation {
+    pub epoch: u64,
+    pub slot: u64,
+    pub validator_index: u64,
+    pub committee_index: u8,
+    pub attested: bool,
+}
+
+impl From<service::model::AttestationData> for Attestation {
+    fn from(attestation: service::model::AttestationData) -> Self {
+        Self {
+            epoch: attestation.epoch,
+            slot: attestation.slot,
+            validator_index: attestation.validator,
+            committee_index: attestation.committee_index,
+            attested: attestation.attested,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Attestation {
+    async fn validator(&self, ctx: &Context<'_>) -> FieldResult<Option<Validator>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .validators
+            .load_one(self.validator_index)
+            .await?
+            .map(Validator::from))
+    }
+
+    async fn committee(&self, ctx: &Context<'_>) -> FieldResult<Option<Committee>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders
+            .committees
+            .load_one((self.slot, self.committee_index))
+            .await?
+            .map(Committee::from))
+    }
+
+    async fn epoch_details(&self, ctx: &Context<'_>) -> FieldResult<Option<Epoch>> {
+        let loaders = ctx.data::<Loaders>()?;
+        Ok(loaders.epochs.load_one(self.epoch).await?.map(Epoch::from))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ConnectionFields {
+    pub total_count: u64,
+}
+
+pub fn connection<S, T: From<S> + OutputType>(
+    page: service::model::Page<S>,
+) -> Connection<String, T, ConnectionFields> {
+    let mut connection = Connection::with_additional_fields(
+        page.has_previous_page,
+        page.has_next_page,
+        ConnectionFields {
+            total_count: page.total_count,
+        },
+    );
+    connection.edges.extend(
+        page.items
+            .into_iter()
+            .map(|(cursor, item)| Edge::new(cursor.to_string(), T::from(item))),
+    );
+    connection
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<SortDirection> for service::model::SortDirection {
+    fn from(direction: SortDirection) -> Self {
+        match direction {
+            SortDirection::Asc => Self::Asc,
+            SortDirection::Desc => Self::Desc,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ValidatorStatus {
+    Pending,
+    Active,
+    Exited,
+    Slashed,
+}
+
+impl From<ValidatorStatus> for service::model::ValidatorStatus {
+    fn from(status: ValidatorStatus) -> Self {
+        match status {
+            ValidatorStatus::Pending => Self::Pending,
+            ValidatorStatus::Active => Self::Active,
+            ValidatorStatus::Exited => Self::Exited,
+            ValidatorStatus::Slashed => Self::Slashed,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ValidatorSortField {
+    #[default]
+    Index,
+    ActivationEpoch,
+    ExitEpoch,
+    Attestations,
+}
+
+impl From<ValidatorSortField> for service::model::ValidatorSortField {
+    fn from(sort: ValidatorSortField) -> Self {
+        match sort {
+            ValidatorSortField::Index => Self::Index,
+            ValidatorSortField::ActivationEpoch => Self::ActivationEpoch,
+            ValidatorSortField::ExitEpoch => Self::ExitEpoch,
+            ValidatorSortField::Attestations => Self::Attestations,
+        }
+    }
+}
+
+#[derive(InputObject, Default)]
+pub struct ValidatorFilter {
+    pub status: Option<ValidatorStatus>,
+    pub active_from: Option<u64>,
+    pub active_to: Option<u64>,
+    pub group: Option<String>,
+    pub min_participation_rate: Option<f64>,
+}
+
+impl From<ValidatorFilter> for service::model::ValidatorFilter {
+    fn from(filter: ValidatorFilter) -> Self {
+        Self {
+            status: filter.status.map(Into::into),
+            active_from: filter.active_from,
+            active_to: filter.active_to,
+            group: filter.group,
+            min_participation_rate: filter.min_participation_rate,
+        }
+    }
+}
+
+#[derive(InputObject, Default)]
+pub struct SlotFilter {
+    pub status: Option<SlotStatus>,
+    pub epoch_from: Option<u64>,
+    pub epoch_to: Option<u64>,
+    pub proposer: Option<u64>,
+    pub group: Option<String>,
+}
+
+impl From<SlotFilter> for service::model::SlotFilter {
+    fn from(filter: SlotFilter) -> Self {
+        Self {
+            status: filter.status.map(Into::into),
+            epoch_from: filter.epoch_from,
+            epoch_to: filter.epoch_to,
+            proposer: filter.proposer,
+            group: filter.group,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Checkpoint {
+    pub epoch: u64,
+    pub root: String,
+}
+
+impl From<service::model::Checkpoint> for Checkpoint {
+    fn from(checkpoint: service::model::Checkpoint) -> Self {
+        Self {
+            epoch: checkpoint.epoch,
+            root: checkpoint.root,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum DutyKind {
+    Proposal,
+    Attestation,
+}
+
+impl From<service::model::DutyKind> for DutyKind {
+    fn from(kind: service::model::DutyKind) -> Self {
+        match kind {
+            service::model::DutyKind::Proposal => Self::Proposal,
+            service::model::DutyKind::Attestation => Self::Attestation,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct DutyOutcome {
+    pub validator: u64,
+    pub epoch: u64,
+    pub slot: Option<u64>,
+    pub kind: DutyKind,
+    pub success: bool,
+}
+
+impl From<service::model::DutyOutcome> for DutyOutcome {
+    fn from(outcome: service::model::DutyOutcome) -> Self {
+        Self {
+            validator: outcome.validator,
+            epoch: outcome.epoch,
+            slot: outcome.slot,
+            kind: outcome.kind.into(),
+            success: outcome.success,
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use std::{fmt::Display, sync::Arc};
+
+use actix_web::{
+    error,
+    http::{header, StatusCode},
+    web, HttpRequest, HttpResponse, ResponseError,
+};
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use service::{Service, ServiceImpl};
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    export::{export, ExportDataset, ExportFormat, ExportLimits, EXPORT_RETRY_AFTER_SECS},
+    model::{BlockProduction, Committee, Epoch, ProposalOutcome, Slot, SlotStatus, Validator, Withdrawal},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        epoch,
+        epoch_participation,
+        epoch_slots,
+        epoch_block_production,
+        slot,
+        slot_committees,
+        validator,
+        validator_participation,
+        validator_proposals,
+        validator_withdrawals,
+        export_dataset,
+    ),
+    components(schemas(
+        ErrorBody,
+        Participation,
+        Epoch,
+        Slot,
+        SlotStatus,
+        BlockProduction,
+        Committee,
+        Validator,
+        ProposalOutcome,
+        Withdrawal,
+    ))
+)]
+pub struct ApiDoc;
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub code: u16,
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Participation {
+    pub participation_rate: f64,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    from_epoch: u64,
+    to_epoch: u64,
+    format: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum RestError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    TooManyRequests(String, u64),
+    Internal(anyhow::Error),
+}
+
+impl Display for RestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestError::BadRequest(message)
+            | RestError::Unauthorized(message)
+            | RestError::Forbidden(message)
+            | RestError::NotFound(message) => write!(f, "{message}"),
+            RestError::TooManyRequests(message, retry_after) => write!(f, "{message}, retry in {retry_after}s"),
+            RestError::Internal(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl ResponseError for RestError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            RestError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            RestError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            RestError::Forbidden(_) => StatusCode::FORBIDDEN,
+            RestError::NotFound(_) => StatusCode::NOT_FOUND,
+            RestError::TooManyRequests(..) => StatusCode::TOO_MANY_REQUESTS,
+            RestError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        if let RestError::Internal(err) = self {
+            tracing::error!("REST request failed: {err:?}");
+        }
+        let mut response = HttpResponse::build(self.status_code());
+        if let RestError::TooManyRequests(_, retry_after) = self {
+            response.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+        response.json(ErrorBody {
+            code: self.status_code().as_u16(),
+            message: self.to_string(),
+        })
+    }
+}
+
+impl From<anyhow::Error> for RestError {
+    fn from(err: anyhow::Error) -> Self {
+        RestError::Internal(err)
+    }
+}
+
+pub(crate) type RestResult<T> = Result<web::Json<T>, RestError>;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/v1")
+            .app_data(path_config())
+            .app_data(query_config())
+            .route("/openapi.json", web::get().to(openapi))
+            .route("/epochs/{epoch}", web::get().to(epoch))
+            .route("/epochs/{epoch}/participation", web::get().to(epoch_participation))
+            .route("/epochs/{epoch}/slots", web::get().to(epoch_slots))
+            .route(
+                "/epochs/{epoch}/block-production",
+                web::get().to(epoch_block_production),
+            )
+            .route("/slots/{slot}", web::get().to(slot))
+            .route("/slots/{slot}/committees", web::get().to(slot_committees))
+            .route("/validators/{id}", web::get().to(validator))
+            .route("/validators/{id}/participation", web::get().to(validator_participation))
+            .route("/validators/{id}/proposals", web::get().to(validator_proposals))
+            .route("/validators/{id}/withdrawals", web::get().to(validator_withdrawals))
+            .route("/export/{dataset}", web::get().to(export_dataset))
+            .default_service(web::to(not_found)),
+    );
+}
+
+pub(crate) fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|err, _| {
+        error::InternalError::from_response("", RestError::BadRequest(err.to_string()).error_response()).into()
+    })
+}
+
+pub(crate) fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|err, _| {
+        error::InternalError::from_response("", RestError::BadRequest(err.to_string()).error_response()).into()
+    })
+}
+
+async fn openapi() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+pub(crate) async fn not_found(req: HttpRequest) -> HttpResponse {
+    RestError::NotFound(format!("No route for {}", req.path())).error_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/epochs/{epoch}",
+    params(("epoch" = u64, Path, description = "Epoch index")),
+    responses(
+        (status = 200, body = Epoch),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn epoch(service: web::Data<Arc<ServiceImpl>>, path: web::Path<u64>) -> RestResult<Epoch> {
+    let epoch = path.into_inner();
+    let epoch = service
+        .get_epoch(epoch)
+        .await?
+        .ok_or(RestError::NotFound(format!("Epoch {epoch} not found")))?;
+    Ok(web::Json(epoch.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/epochs/{epoch}/participation",
+    params(("epoch" = u64, Path, description = "Epoch index")),
+    responses(
+        (status = 200, body = Participation),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn epoch_participation(service: web::Data<Arc<ServiceImpl>>, path: web::Path<u64>) -> RestResult<Participation> {
+    let epoch = path.into_inner();
+    if service.get_epoch(epoch).await?.is_none() {
+        return Err(RestError::NotFound(format!("Epoch {epoch} not found")));
+    }
+    let participation_rate = service.get_participation_rate_for_epoch(epoch).await?;
+    Ok(web::Json(Participation { participation_rate }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/epochs/{epoch}/slots",
+    params(("epoch" = u64, Path, description = "Epoch index")),
+    responses((status = 200, body = [Slot]))
+)]
+async fn epoch_slots(service: web::Data<Arc<ServiceImpl>>, path: web::Path<u64>) -> RestResult<Vec<Slot>> {
+    let slots = service.get_slots_for_epoch(path.into_inner()).await?;
+    Ok(web::Json(slots.into_iter().map(Slot::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/epochs/{epoch}/block-production",
+    params(("epoch" = u64, Path, description = "Epoch index")),
+    responses((status = 200, body = BlockProduction))
+)]
+async fn epoch_block_production(
+    service: web::Data<Arc<ServiceImpl>>,
+    path: web::Path<u64>,
+) -> RestResult<BlockProduction> {
+    let production = service.get_block_production_for_epoch(path.into_inner()).await?;
+    Ok(web::Json(production.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/slots/{slot}",
+    params(("slot" = u64, Path, description = "Slot number")),
+    responses(
+        (status = 200, body = Slot),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn slot(service: web::Data<Arc<ServiceImpl>>, path: web::Path<u64>) -> RestResult<Slot> {
+    let slot = path.into_inner();
+    let slot = service
+        .get_slot(slot)
+        .await?
+        .ok_or(RestError::NotFound(format!("Slot {slot} not found")))?;
+    Ok(web::Json(slot.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/slots/{slot}/committees",
+    params(("slot" = u64, Path, description = "Slot number")),
+    responses((status = 200, body = [Committee]))
+)]
+async fn slot_committees(service: web::Data<Arc<ServiceImpl>>, path: web::Path<u64>) -> RestResult<Vec<Committee>> {
+    let committees = service.get_committees_for_slot(path.into_inner()).await?;
+    Ok(web::Json(committees.into_iter().map(Committee::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/validators/{id}",
+    params(("id" = String, Path, description = "Validator index or 0x-prefixed pubkey")),
+    responses(
+        (status = 200, body = Validator),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn validator(service: web::Data<Arc<ServiceImpl>>, path: web::Path<String>) -> RestResult<Validator> {
+    let validator = find_validator(&service, &path).await?;
+    Ok(web::Json(validator.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/validators/{id}/participation",
+    params(("id" = String, Path, description = "Validator index or 0x-prefixed pubkey")),
+    responses(
+        (status = 200, body = Participation),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn validator_participation(
+    service: web::Data<Arc<ServiceImpl>>,
+    path: web::Path<String>,
+) -> RestResult<Participation> {
+    let validator = find_validator(&service, &path).await?;
+    let participation_rate = service.get_participation_rate_for_validator(validator.index).await?;
+    Ok(web::Json(Participation { participation_rate }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/validators/{id}/proposals",
+    params(("id" = String, Path, description = "Validator index or 0x-prefixed pubkey")),
+    responses(
+        (status = 200, body = [ProposalOutcome]),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn validator_proposals(
+    service: web::Data<Arc<ServiceImpl>>,
+    path: web::Path<String>,
+) -> RestResult<Vec<ProposalOutcome>> {
+    let validator = find_validator(&service, &path).await?;
+    let outcomes = service.get_proposal_outcomes_for_validator(validator.index).await?;
+    Ok(web::Json(outcomes.into_iter().map(ProposalOutcome::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/validators/{id}/withdrawals",
+    params(("id" = String, Path, description = "Validator index or 0x-prefixed pubkey")),
+    responses(
+        (status = 200, body = [Withdrawal]),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn validator_withdrawals(
+    service: web::Data<Arc<ServiceImpl>>,
+    path: web::Path<String>,
+) -> RestResult<Vec<Withdrawal>> {
+    let validator = find_validator(&service, &path).await?;
+    let withdrawals = service.get_withdrawals_for_validator(validator.index).await?;
+    Ok(web::Json(withdrawals.into_iter().map(Withdrawal::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/export/{dataset}",
+    params(
+        ("dataset" = String, Path, description = "One of attestations, epochs, proposers or validator_history"),
+        ("from_epoch" = u64, Query, description = "First epoch of the range"),
+        ("to_epoch" = u64, Query, description = "Last epoch of the range, inclusive"),
+        ("format" = Option<String>, Query, description = "csv (default) or parquet"),
+    ),
+    responses(
+        (status = 200, description = "Streamed CSV or Parquet file", content_type = "text/csv"),
+        (status = 400, body = ErrorBody),
+        (status = 429, description = "Too many exports running", body = ErrorBody),
+    )
+)]
+async fn export_dataset(
+    service: web::Data<Arc<ServiceImpl>>,
+    limits: web::Data<ExportLimits>,
+    path: web::Path<String>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, RestError> {
+    let dataset = path
+        .parse::<ExportDataset>()
+        .map_err(|err| RestError::BadRequest(err.to_string()))?;
+    let format = query
+        .format
+        .as_deref()
+        .map(str::parse::<ExportFormat>)
+        .transpose()
+        .map_err(|err| RestError::BadRequest(err.to_string()))?
+        .unwrap_or_default();
+    let (from_epoch, to_epoch) = (query.from_epoch, query.to_epoch);
+    if from_epoch > to_epoch {
+        return Err(RestError::BadRequest(format!(
+            "Invalid epoch range: {from_epoch} > {to_epoch}"
+        )));
+    }
+    let permit = limits.try_acquire().ok_or(RestError::TooManyRequests(
+        "Too many exports running".to_string(),
+        EXPORT_RETRY_AFTER_SECS,
+    ))?;
+    let chunks = limits.guard(permit, export(&service, dataset, format, from_epoch, to_epoch).await?);
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{dataset}-{from_epoch}-{to_epoch}.{format}\""),
+        ))
+        .streaming(chunks.map_ok(web::Bytes::from).map_err(move |err| {
+            tracing::error!("Export of {dataset} failed: {err:?}");
+            err
+        })))
+}
+
+async fn find_validator(service: &ServiceImpl, id: &str) -> Result<service::model::Validator, RestError> {
+    let validator = if id.starts_with("0x") {
+        service.get_validator_by_pubkey(id).await?
+    } else {
+        let index = id
+            .parse()
+            .map_err(|_| RestError::BadRequest(format!("Invalid validator id: {id}")))?;
+        service.get_validator(index).await?
+    };
+    validator.ok_or(RestError::NotFound(format!("Validator {id} not found")))
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextRequest},
+    Response,
+};
+
+pub struct GraphQLMetrics;
+
+impl ExtensionFactory for GraphQLMetrics {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(GraphQLMetricsExtension)
+    }
+}
+
+struct GraphQLMetricsExtension;
+
+#[async_trait::async_trait]
+impl Extension for GraphQLMetricsExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let response = metrics::GRAPHQL_REQUEST_DURATION.time(&[], next.run(ctx)).await;
+        if response.is_err() {
+            metrics::GRAPHQL_REQUEST_ERRORS.inc(&[]);
+        }
+        response
+    }
+}
+
+pub async fn metrics() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(metrics::CONTENT_TYPE)
+        .body(metrics::render())
+}
@@ -6,13 +6,17 @@ use futures_core::Stream;
 use futures_util::StreamExt;
 use model::{
     attestation::Attestation,
-    block::{BlockHeader, BlockHeaderResponse, BlockId},
+    blob::{BlobSidecar, BlobSidecarResponse},
+    block::{BlockHeaderData, BlockHeaderResponse, BlockHeadersResponse, BlockId, BlockResponse, SignedBlock},
     checkpoint::{FinalityCheckpointResponse, FinalityCheckpoints},
     committee::Committee,
+    genesis::{Genesis, GenesisResponse},
+    node::{SyncStatus, SyncStatusResponse},
     proposer::Proposer,
     state::{StateId, StateRootResponse},
     validator::{ValidatorData, ValidatorId, ValidatorResponse, ValidatorStatus},
 };
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
 use subscription::Subscribable;
 use url::Url;
@@ -20,12 +24,16 @@ use url::Url;
 use crate::model::{attestation::AttestationResponse, committee::CommitteeResponse, proposer::ProposerResponse};
 
 pub mod model;
+pub mod relay;
 pub mod subscription;
 pub mod util;
 
 #[async_trait]
 pub trait JsonRpcClient: Sync + Send {
-    async fn get_header_for_block(&self, block_id: BlockId) -> Result<Option<BlockHeader>>;
+    async fn get_header_for_block(&self, block_id: BlockId) -> Result<Option<BlockHeaderData>>;
+    async fn get_headers_for_slot(&self, slot: u64) -> Result<Vec<BlockHeaderData>>;
+    async fn get_block(&self, block_id: BlockId) -> Result<Option<SignedBlock>>;
+    async fn get_blob_sidecars(&self, block_id: BlockId) -> Result<Vec<BlobSidecar>>;
     async fn get_root_for_block(&self, block_id: BlockId) -> Result<String>;
     async fn get_attestations_for_block(&self, block_id: BlockId) -> Result<Option<Vec<Attestation>>>;
     async fn get_root_for_state(&self, state_id: StateId) -> Result<String>;
@@ -48,6 +56,10 @@ pub trait JsonRpcClient: Sync + Send {
     async fn get_finality_checkpoints(&self, state_id: StateId) -> Result<FinalityCheckpoints>;
 
     async fn get_proposers_for_epoch(&self, epoch: u64) -> Result<Vec<Proposer>>;
+
+    async fn get_sync_status(&self) -> Result<SyncStatus>;
+
+    async fn get_genesis(&self) -> Result<Genesis>;
 }
 
 pub struct HttpClient {
@@ -65,7 +77,7 @@ impl HttpClient {
         let event = T::subscribe_event();
         let mut url = self.http_rpc_url.join("eth/v1/events")?;
         url.query_pairs_mut().append_pair("topics", &event.to_string());
-        log::debug!("GET {url}");
+        tracing::debug!(%url, "Subscribing to events");
         // Ok(self.client.get(url).send().await?.bytes_stream())
         let stream = self.client.get(url).send().await?.bytes_stream();
         let stream = stream.map(|bytes| {
@@ -77,31 +89,88 @@ impl HttpClient {
 
         Ok(stream)
     }
+
+    // The endpoint is the path template, so spans can be grouped without the slot or state in the url
+    #[tracing::instrument(name = "beacon_request", skip(self, url), fields(%url, status))]
+    async fn send(&self, endpoint: &str, url: Url) -> Result<reqwest::Response> {
+        metrics::BEACON_REQUESTS.inc(&[endpoint]);
+        let response = metrics::BEACON_REQUEST_DURATION
+            .time(&[endpoint], self.client.get(url).send())
+            .await;
+        if let Ok(response) = &response {
+            tracing::Span::current().record("status", response.status().as_u16());
+        }
+        // A 404 is how the beacon node reports missing blocks and states, which callers expect
+        match &response {
+            Ok(response) if response.status().is_success() || response.status() == StatusCode::NOT_FOUND => {}
+            _ => metrics::BEACON_REQUEST_ERRORS.inc(&[endpoint]),
+        }
+        Ok(response?)
+    }
 }
 
 #[async_trait]
 impl JsonRpcClient for HttpClient {
-    async fn get_header_for_block(&self, block_id: BlockId) -> Result<Option<BlockHeader>> {
+    async fn get_header_for_block(&self, block_id: BlockId) -> Result<Option<BlockHeaderData>> {
         let url = self.http_rpc_url.join(&format!("eth/v1/beacon/headers/{block_id}"))?;
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/headers/{block_id}", url).await?;
         match response.error_for_status_ref() {
             Ok(_) => {
                 let data = response.json::<BlockHeaderResponse>().await?.data;
-                let header = data.header;
-                Ok(Some(header))
+                Ok(Some(data))
+            }
+            Err(err) if err.status().map(|s| s.as_u16()) == Some(404) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn get_headers_for_slot(&self, slot: u64) -> Result<Vec<BlockHeaderData>> {
+        let mut url = self.http_rpc_url.join("eth/v1/beacon/headers")?;
+        url.query_pairs_mut().append_pair("slot", &slot.to_string());
+        let response = self.send("eth/v1/beacon/headers", url).await?;
+        match response.error_for_status_ref() {
+            Ok(_) => {
+                let headers = response.json::<BlockHeadersResponse>().await?.data;
+                Ok(headers)
+            }
+            Err(err) if err.status().map(|s| s.as_u16()) == Some(404) => Ok(Vec::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn get_block(&self, block_id: BlockId) -> Result<Option<SignedBlock>> {
+        let url = self.http_rpc_url.join(&format!("eth/v2/beacon/blocks/{block_id}"))?;
+        let response = self.send("eth/v2/beacon/blocks/{block_id}", url).await?;
+        match response.error_for_status_ref() {
+            Ok(_) => {
+                let block = response.json::<BlockResponse>().await?.data;
+                Ok(Some(block))
             }
             Err(err) if err.status().map(|s| s.as_u16()) == Some(404) => Ok(None),
             Err(err) => Err(err.into()),
         }
     }
 
+    async fn get_blob_sidecars(&self, block_id: BlockId) -> Result<Vec<BlobSidecar>> {
+        let url = self
+            .http_rpc_url
+            .join(&format!("eth/v1/beacon/blob_sidecars/{block_id}"))?;
+        let response = self.send("eth/v1/beacon/blob_sidecars/{block_id}", url).await?;
+        match response.error_for_status_ref() {
+            Ok(_) => {
+                let sidecars = response.json::<BlobSidecarResponse>().await?.data;
+                Ok(sidecars)
+            }
+            Err(err) if err.status().map(|s| s.as_u16()) == Some(404) => Ok(Vec::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     async fn get_root_for_block(&self, block_id: BlockId) -> Result<String> {
         let url = self
             .http_rpc_url
             .join(&format!("eth/v1/beacon/blocks/{block_id}/root"))?;
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/blocks/{block_id}/root", url).await?;
         response.error_for_status_ref()?;
         let data = response.json::<StateRootResponse>().await?.data;
         let root = data.root;
@@ -112,8 +181,7 @@ impl JsonRpcClient for HttpClient {
         let url = self
             .http_rpc_url
             .join(&format!("eth/v1/beacon/blocks/{block_id}/attestations"))?;
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/blocks/{block_id}/attestations", url).await?;
         match response.error_for_status_ref() {
             Ok(_) => {
                 let attestations = response.json::<AttestationResponse>().await?.data;
@@ -128,8 +196,7 @@ impl JsonRpcClient for HttpClient {
         let url = self
             .http_rpc_url
             .join(&format!("eth/v1/beacon/states/{state_id}/root"))?;
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/states/{state_id}/root", url).await?;
         response.error_for_status_ref()?;
         let data = response.json::<StateRootResponse>().await?.data;
         let root = data.root;
@@ -155,8 +222,7 @@ impl JsonRpcClient for HttpClient {
         if let Some(slot) = slot {
             url.query_pairs_mut().append_pair("slot", &slot.to_string());
         }
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/states/{state_id}/committees", url).await?;
         response.error_for_status_ref()?;
         let committees = response.json::<CommitteeResponse>().await?.data;
 
@@ -183,8 +249,7 @@ impl JsonRpcClient for HttpClient {
         if let Some(id) = id {
             url.query_pairs_mut().append_pair("id", &id);
         }
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/states/{state_id}/validators", url).await?;
         response.error_for_status_ref()?;
         let body = response.json::<ValidatorResponse>().await?;
         let validators = body.data;
@@ -198,8 +263,7 @@ impl JsonRpcClient for HttpClient {
         if let Some(status) = validator_status {
             url.query_pairs_mut().append_pair("status", &status.to_string());
         }
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/beacon/states/{state_id}/validators", url).await?;
         response.error_for_status_ref()?;
         let body = response.json::<ValidatorResponse>().await?;
         let active_validator_count = body.data.len();
@@ -210,8 +274,9 @@ impl JsonRpcClient for HttpClient {
         let url = self
             .http_rpc_url
             .join(&format!("eth/v1/beacon/states/{state_id}/finality_checkpoints"))?;
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self
+            .send("eth/v1/beacon/states/{state_id}/finality_checkpoints", url)
+            .await?;
         response.error_for_status_ref()?;
         let body = response.json::<FinalityCheckpointResponse>().await?;
         let finality_checkpoints = body.data;
@@ -222,11 +287,26 @@ impl JsonRpcClient for HttpClient {
         let url = self
             .http_rpc_url
             .join(&format!("eth/v1/validator/duties/proposer/{epoch}"))?;
-        log::debug!("GET {url}");
-        let response = self.client.get(url).send().await?;
+        let response = self.send("eth/v1/validator/duties/proposer/{epoch}", url).await?;
         response.error_for_status_ref()?;
         let body = response.json::<ProposerResponse>().await?;
         let proposers = body.data;
         Ok(proposers)
     }
+
+    async fn get_sync_status(&self) -> Result<SyncStatus> {
+        let url = self.http_rpc_url.join("eth/v1/node/syncing")?;
+        let response = self.send("eth/v1/node/syncing", url).await?;
+        response.error_for_status_ref()?;
+        let body = response.json::<SyncStatusResponse>().await?;
+        Ok(body.data)
+    }
+
+    async fn get_genesis(&self) -> Result<Genesis> {
+        let url = self.http_rpc_url.join("eth/v1/beacon/genesis")?;
+        let response = self.send("eth/v1/beacon/genesis", url).await?;
+        response.error_for_status_ref()?;
+        let body = response.json::<GenesisResponse>().await?;
+        Ok(body.data)
+    }
 }
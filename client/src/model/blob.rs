@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::deserialize_num;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlobSidecarResponse {
+    pub data: Vec<BlobSidecar>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlobSidecar {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub index: u64,
+    pub blob: String,
+    pub kzg_commitment: String,
+    pub kzg_proof: String,
+}
+
+impl BlobSidecar {
+    pub fn size(&self) -> u64 {
+        (self.blob.trim_start_matches("0x").len() / 2) as u64
+    }
+}
@@ -1,8 +1,16 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
-use crate::util::deserialize_num;
+use crate::util::{deserialize_num, serialize_num};
+
+use super::{
+    bls_to_execution_change::SignedBlsToExecutionChange,
+    deposit::Deposit,
+    execution::ExecutionPayload,
+    slashing::{AttesterSlashing, ProposerSlashing},
+    voluntary_exit::SignedVoluntaryExit,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -27,6 +35,24 @@ pub enum BlockId {
     BlockRoot(String),
 }
 
+impl FromStr for BlockId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(Self::Head),
+            "genesis" => Ok(Self::Genesis),
+            "finalized" => Ok(Self::Finalized),
+            "justified" => Ok(Self::Justified),
+            root if root.starts_with("0x") => Ok(Self::BlockRoot(root.to_lowercase())),
+            slot => slot
+                .parse()
+                .map(Self::Slot)
+                .map_err(|_| anyhow::anyhow!("Invalid block id: {}", s)),
+        }
+    }
+}
+
 impl Display for BlockId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -56,6 +82,22 @@ mod tests {
             "0x1234567890abcdef"
         );
     }
+
+    #[test]
+    fn block_id_from_str() {
+        assert!(matches!("head".parse::<BlockId>().unwrap(), BlockId::Head));
+        assert!(matches!("123".parse::<BlockId>().unwrap(), BlockId::Slot(123)));
+        assert!(matches!("0xABCD".parse::<BlockId>().unwrap(), BlockId::BlockRoot(root) if root == "0xabcd"));
+        assert!("latest".parse::<BlockId>().is_err());
+    }
+
+    #[test]
+    fn block_header_message_round_trip() {
+        let json = r#"{"slot":"64","proposer_index":"7","parent_root":"0x01","state_root":"0x02","body_root":"0x03"}"#;
+        let message = serde_json::from_str::<BlockHeaderMessage>(json).unwrap();
+        assert_eq!(message.slot, 64);
+        assert_eq!(serde_json::to_string(&message).unwrap(), json);
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,9 +118,9 @@ pub struct BlockHeader {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct BlockHeaderMessage {
-    #[serde(deserialize_with = "deserialize_num")]
+    #[serde(deserialize_with = "deserialize_num", serialize_with = "serialize_num")]
     pub slot: u64,
-    #[serde(deserialize_with = "deserialize_num")]
+    #[serde(deserialize_with = "deserialize_num", serialize_with = "serialize_num")]
     pub proposer_index: u64,
     pub parent_root: String,
     pub state_root: String,
@@ -92,3 +134,58 @@ pub struct BlockHeaderResponse {
     pub execution_optimistic: bool,
     pub finalized: bool,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlockHeadersResponse {
+    pub data: Vec<BlockHeaderData>,
+    pub execution_optimistic: bool,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlockResponse {
+    pub version: String,
+    pub data: SignedBlock,
+    pub execution_optimistic: bool,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedBlock {
+    pub message: BlockMessage,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlockMessage {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub slot: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub proposer_index: u64,
+    pub parent_root: String,
+    pub state_root: String,
+    pub body: BlockBody,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlockBody {
+    #[serde(default)]
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    #[serde(default)]
+    pub attester_slashings: Vec<AttesterSlashing>,
+    #[serde(default)]
+    pub deposits: Vec<Deposit>,
+    #[serde(default)]
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    #[serde(default)]
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+    #[serde(default)]
+    pub execution_payload: Option<ExecutionPayload>,
+    #[serde(default)]
+    pub blob_kzg_commitments: Vec<String>,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    subscription::{Subscribable, SubscribeEvent},
+    util::deserialize_num,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedBlsToExecutionChange {
+    pub message: BlsToExecutionChange,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BlsToExecutionChange {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub validator_index: u64,
+    pub from_bls_pubkey: String,
+    pub to_execution_address: String,
+}
+
+impl Subscribable for SignedBlsToExecutionChange {
+    fn subscribe_event() -> SubscribeEvent {
+        SubscribeEvent::BlsToExecutionChange
+    }
+}
@@ -1,15 +1,15 @@
 use serde::{Deserialize, Serialize};
 
-use crate::util::{deserialize_num, deserialize_vec_num};
+use crate::util::{deserialize_num, deserialize_vec_num, serialize_num, serialize_vec_num};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Committee {
-    #[serde(deserialize_with = "deserialize_num")]
+    #[serde(deserialize_with = "deserialize_num", serialize_with = "serialize_num")]
     pub index: u8,
-    #[serde(deserialize_with = "deserialize_num")]
+    #[serde(deserialize_with = "deserialize_num", serialize_with = "serialize_num")]
     pub slot: u64,
-    #[serde(deserialize_with = "deserialize_vec_num")]
+    #[serde(deserialize_with = "deserialize_vec_num", serialize_with = "serialize_vec_num")]
     pub validators: Vec<u64>,
 }
 
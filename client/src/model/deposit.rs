@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::deserialize_num;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Deposit {
+    pub proof: Vec<String>,
+    pub data: DepositData,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DepositData {
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub amount: u64,
+    pub signature: String,
+}
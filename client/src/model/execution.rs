@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::{deserialize_num, deserialize_option_num};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExecutionPayload {
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub block_number: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub gas_limit: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub gas_used: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub timestamp: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub base_fee_per_gas: u64,
+    pub block_hash: String,
+    pub transactions: Vec<String>,
+    #[serde(default)]
+    pub withdrawals: Vec<Withdrawal>,
+    #[serde(default, deserialize_with = "deserialize_option_num")]
+    pub blob_gas_used: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_option_num")]
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl ExecutionPayload {
+    pub fn is_empty(&self) -> bool {
+        self.block_hash.trim_start_matches("0x").chars().all(|c| c == '0')
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Withdrawal {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub index: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub validator_index: u64,
+    pub address: String,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub amount: u64,
+}
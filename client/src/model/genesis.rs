@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::deserialize_num;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Genesis {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: String,
+    pub genesis_fork_version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GenesisResponse {
+    pub data: Genesis,
+}
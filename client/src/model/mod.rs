@@ -1,7 +1,16 @@
 pub mod attestation;
+pub mod blob;
 pub mod block;
+pub mod bls_to_execution_change;
 pub mod checkpoint;
 pub mod committee;
+pub mod deposit;
+pub mod execution;
+pub mod genesis;
+pub mod node;
 pub mod proposer;
+pub mod relay;
+pub mod slashing;
 pub mod state;
 pub mod validator;
+pub mod voluntary_exit;
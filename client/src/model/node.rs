@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::deserialize_num;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SyncStatus {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub head_slot: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub sync_distance: u64,
+    pub is_syncing: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SyncStatusResponse {
+    pub data: SyncStatus,
+}
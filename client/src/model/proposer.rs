@@ -1,14 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-use crate::util::deserialize_num;
+use crate::util::{deserialize_num, serialize_num};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Proposer {
     pub pubkey: String,
-    #[serde(deserialize_with = "deserialize_num")]
+    #[serde(deserialize_with = "deserialize_num", serialize_with = "serialize_num")]
     pub validator_index: u64,
-    #[serde(deserialize_with = "deserialize_num")]
+    #[serde(deserialize_with = "deserialize_num", serialize_with = "serialize_num")]
     pub slot: u64,
 }
 
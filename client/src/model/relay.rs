@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::deserialize_num;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BidTrace {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub slot: u64,
+    pub parent_hash: String,
+    pub block_hash: String,
+    pub builder_pubkey: String,
+    pub proposer_pubkey: String,
+    pub proposer_fee_recipient: String,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub gas_limit: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub gas_used: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub value: u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bid_trace_value_exceeds_u64() {
+        let trace: BidTrace = serde_json::from_str(
+            r#"{
+                "slot": "6000000",
+                "parent_hash": "0x00",
+                "block_hash": "0x01",
+                "builder_pubkey": "0x02",
+                "proposer_pubkey": "0x03",
+                "proposer_fee_recipient": "0x04",
+                "gas_limit": "30000000",
+                "gas_used": "12000000",
+                "value": "25000000000000000000",
+                "num_tx": "150"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(trace.slot, 6_000_000);
+        assert_eq!(trace.value, 25_000_000_000_000_000_000);
+    }
+}
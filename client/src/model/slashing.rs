@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::deserialize_vec_num;
+
+use super::{attestation::AggregationData, block::BlockHeader};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposerSlashing {
+    pub signed_header_1: BlockHeader,
+    pub signed_header_2: BlockHeader,
+}
+
+impl ProposerSlashing {
+    pub fn slashed_index(&self) -> u64 {
+        self.signed_header_1.message.proposer_index
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct IndexedAttestation {
+    #[serde(deserialize_with = "deserialize_vec_num")]
+    pub attesting_indices: Vec<u64>,
+    pub data: AggregationData,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AttesterSlashing {
+    pub attestation_1: IndexedAttestation,
+    pub attestation_2: IndexedAttestation,
+}
+
+impl AttesterSlashing {
+    pub fn slashed_indices(&self) -> Vec<u64> {
+        let mut indices = self
+            .attestation_1
+            .attesting_indices
+            .iter()
+            .filter(|index| self.attestation_2.attesting_indices.contains(index))
+            .copied()
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attester_slashing_slashes_intersection() {
+        let data = r#"{
+            "attesting_indices": ["%INDICES%"],
+            "data": {
+                "slot": "1",
+                "index": "0",
+                "beacon_block_root": "0x00",
+                "source": { "epoch": "0", "root": "0x00" },
+                "target": { "epoch": "0", "root": "0x00" }
+            },
+            "signature": "0x00"
+        }"#;
+        let attestation_1 = data.replace(r#""%INDICES%""#, r#""5", "1", "3""#);
+        let attestation_2 = data.replace(r#""%INDICES%""#, r#""3", "4", "5""#);
+        let slashing: AttesterSlashing = serde_json::from_str(&format!(
+            r#"{{ "attestation_1": {attestation_1}, "attestation_2": {attestation_2} }}"#
+        ))
+        .unwrap();
+        assert_eq!(slashing.slashed_indices(), vec![3, 5]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +11,24 @@ pub enum StateId {
     StateRoot(String),
 }
 
+impl FromStr for StateId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(Self::Head),
+            "genesis" => Ok(Self::Genesis),
+            "finalized" => Ok(Self::Finalized),
+            "justified" => Ok(Self::Justified),
+            root if root.starts_with("0x") => Ok(Self::StateRoot(root.to_lowercase())),
+            slot => slot
+                .parse()
+                .map(Self::Slot)
+                .map_err(|_| anyhow::anyhow!("Invalid state id: {}", s)),
+        }
+    }
+}
+
 impl Display for StateId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
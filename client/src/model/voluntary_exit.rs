@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    subscription::{Subscribable, SubscribeEvent},
+    util::deserialize_num,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedVoluntaryExit {
+    pub message: VoluntaryExit,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct VoluntaryExit {
+    #[serde(deserialize_with = "deserialize_num")]
+    pub epoch: u64,
+    #[serde(deserialize_with = "deserialize_num")]
+    pub validator_index: u64,
+}
+
+impl Subscribable for SignedVoluntaryExit {
+    fn subscribe_event() -> SubscribeEvent {
+        SubscribeEvent::VoluntaryExit
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use url::Url;
+
+use crate::model::relay::BidTrace;
+
+#[async_trait]
+pub trait RelayClient: Sync + Send {
+    fn name(&self) -> &str;
+    async fn get_delivered_payloads_for_slot(&self, slot: u64) -> Result<Vec<BidTrace>>;
+    // Payloads delivered at or before `cursor`, newest first
+    async fn get_delivered_payloads(&self, cursor: u64, limit: u64) -> Result<Vec<BidTrace>>;
+}
+
+const RELAY_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const RELAY_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub struct HttpRelayClient {
+    name: String,
+    relay_url: Url,
+    client: Arc<reqwest::Client>,
+}
+
+impl HttpRelayClient {
+    pub fn new(relay_url: Url) -> Result<Self> {
+        let name = relay_url
+            .host_str()
+            .ok_or(anyhow!("Relay url {relay_url} has no host"))?
+            .to_string();
+        let client = Arc::new(
+            reqwest::Client::builder()
+                .connect_timeout(RELAY_CONNECT_TIMEOUT)
+                .timeout(RELAY_REQUEST_TIMEOUT)
+                .build()?,
+        );
+        Ok(Self {
+            name,
+            relay_url,
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl RelayClient for HttpRelayClient {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[tracing::instrument(name = "relay_request", skip(self), fields(relay = %self.name))]
+    async fn get_delivered_payloads_for_slot(&self, slot: u64) -> Result<Vec<BidTrace>> {
+        self.get_bid_traces(&[("slot", slot)]).await
+    }
+
+    #[tracing::instrument(name = "relay_request", skip(self), fields(relay = %self.name))]
+    async fn get_delivered_payloads(&self, cursor: u64, limit: u64) -> Result<Vec<BidTrace>> {
+        self.get_bid_traces(&[("cursor", cursor), ("limit", limit)]).await
+    }
+}
+
+impl HttpRelayClient {
+    async fn get_bid_traces(&self, query: &[(&str, u64)]) -> Result<Vec<BidTrace>> {
+        let mut url = self
+            .relay_url
+            .join("relay/v1/data/bidtraces/proposer_payload_delivered")?;
+        for (key, value) in query {
+            url.query_pairs_mut().append_pair(key, &value.to_string());
+        }
+        let response = self.client.get(url).send().await?;
+        response.error_for_status_ref()?;
+        let traces = response.json::<Vec<BidTrace>>().await?;
+        Ok(traces)
+    }
+}
+
+pub struct FileRelayClient {
+    name: String,
+    traces: BTreeMap<u64, Vec<BidTrace>>,
+}
+
+impl FileRelayClient {
+    pub fn new(path: &Path) -> Result<Self> {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or(anyhow!("Invalid relay dump path {}", path.display()))?
+            .to_string();
+        let contents = std::fs::read_to_string(path)?;
+        let mut traces: BTreeMap<u64, Vec<BidTrace>> = BTreeMap::new();
+        for trace in serde_json::from_str::<Vec<BidTrace>>(&contents)? {
+            traces.entry(trace.slot).or_default().push(trace);
+        }
+        Ok(Self { name, traces })
+    }
+}
+
+#[async_trait]
+impl RelayClient for FileRelayClient {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn get_delivered_payloads_for_slot(&self, slot: u64) -> Result<Vec<BidTrace>> {
+        Ok(self.traces.get(&slot).cloned().unwrap_or_default())
+    }
+
+    async fn get_delivered_payloads(&self, cursor: u64, limit: u64) -> Result<Vec<BidTrace>> {
+        Ok(self
+            .traces
+            .range(..=cursor)
+            .rev()
+            .flat_map(|(_, traces)| traces.iter().cloned())
+            .take(limit as usize)
+            .collect())
+    }
+}
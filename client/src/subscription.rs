@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 pub trait Subscribable {
     fn subscribe_event() -> SubscribeEvent;
 }
@@ -10,18 +12,20 @@ pub enum SubscribeEvent {
     FinalizedCheckpoint,
     ChainReorg,
     ContributionAndProof,
+    BlsToExecutionChange,
 }
 
-impl ToString for SubscribeEvent {
-    fn to_string(&self) -> String {
+impl Display for SubscribeEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SubscribeEvent::Head => "head".to_string(),
-            SubscribeEvent::Block => "block".to_string(),
-            SubscribeEvent::Attestation => "attestation".to_string(),
-            SubscribeEvent::VoluntaryExit => "voluntary_exit".to_string(),
-            SubscribeEvent::FinalizedCheckpoint => "finalized_checkpoint".to_string(),
-            SubscribeEvent::ChainReorg => "chain_reorg".to_string(),
-            SubscribeEvent::ContributionAndProof => "contribution_and_proof".to_string(),
+            SubscribeEvent::Head => write!(f, "head"),
+            SubscribeEvent::Block => write!(f, "block"),
+            SubscribeEvent::Attestation => write!(f, "attestation"),
+            SubscribeEvent::VoluntaryExit => write!(f, "voluntary_exit"),
+            SubscribeEvent::FinalizedCheckpoint => write!(f, "finalized_checkpoint"),
+            SubscribeEvent::ChainReorg => write!(f, "chain_reorg"),
+            SubscribeEvent::ContributionAndProof => write!(f, "contribution_and_proof"),
+            SubscribeEvent::BlsToExecutionChange => write!(f, "bls_to_execution_change"),
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::{fmt::Display, str::FromStr};
 
-use serde::Deserialize;
+use serde::{ser::SerializeSeq, Deserialize, Serializer};
 
 pub fn deserialize_num<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -12,6 +12,16 @@ where
     s.parse().map_err(serde::de::Error::custom)
 }
 
+pub fn deserialize_option_num<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    <T as FromStr>::Err: Display,
+{
+    let s = <Option<String>>::deserialize(deserializer)?;
+    s.map(|s| s.parse().map_err(serde::de::Error::custom)).transpose()
+}
+
 pub fn deserialize_vec_num<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -23,3 +33,23 @@ where
         .map(|s| s.parse().map_err(serde::de::Error::custom))
         .collect()
 }
+
+pub fn serialize_num<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.collect_str(value)
+}
+
+pub fn serialize_vec_num<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    let mut seq = serializer.serialize_seq(Some(values.len()))?;
+    for value in values {
+        seq.serialize_element(&value.to_string())?;
+    }
+    seq.end()
+}
@@ -1,3 +1,5 @@
 pub mod polling;
 pub mod pubsub;
+pub mod server;
+pub mod shutdown;
 pub mod util;
@@ -1,14 +1,25 @@
-use std::sync::Arc;
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Result;
-use client::HttpClient;
+use client::{
+    model::{bls_to_execution_change::SignedBlsToExecutionChange, voluntary_exit::SignedVoluntaryExit},
+    relay::{FileRelayClient, HttpRelayClient, RelayClient},
+    HttpClient,
+};
 use envconfig::Envconfig;
-use indexer::polling::PollingIndexer;
-use service::ServiceImpl;
-use store::{
-    attestation::PostgresAttestationRepository, committee::PostgresCommitteeRepository, epoch::PostgresEpochRepository,
-    proposer::PostgresProposerRepository, validator::PostgresValidatorRepository, DbConfig,
+use futures_util::StreamExt;
+use indexer::{
+    polling::PollingIndexer,
+    server::{HealthState, Progress},
 };
+use logging::LogConfig;
+use service::ServiceImpl;
+use store::DbConfig;
 use tokio::task::JoinSet;
 use url::Url;
 
@@ -18,38 +29,82 @@ struct IndexerConfig {
     http_rpc_url: Url,
     #[envconfig(from = "MAX_EPOCH")]
     max_epoch: Option<u64>,
+    #[envconfig(from = "RELAY_SOURCES")]
+    relay_sources: Option<String>,
+    #[envconfig(from = "STORE_BLOBS", default = "false")]
+    store_blobs: bool,
+    #[envconfig(from = "SUBSCRIBE_OPERATIONS", default = "true")]
+    subscribe_operations: bool,
+    #[envconfig(from = "METRICS_HOST", default = "127.0.0.1")]
+    metrics_host: IpAddr,
+    #[envconfig(from = "METRICS_PORT", default = "9100")]
+    metrics_port: u16,
+    #[envconfig(from = "HEALTH_STALL_SECS", default = "300")]
+    health_stall_secs: u64,
+}
+
+fn relays_from_sources(sources: &str) -> Result<Vec<Arc<dyn RelayClient>>> {
+    let mut relays: Vec<Arc<dyn RelayClient>> = Vec::new();
+    for source in sources.split(',').map(str::trim).filter(|source| !source.is_empty()) {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            relays.push(Arc::new(HttpRelayClient::new(Url::parse(source)?)?));
+        } else {
+            relays.push(Arc::new(FileRelayClient::new(Path::new(source))?));
+        }
+    }
+    Ok(relays)
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv::dotenv()?;
-    env_logger::init();
+    let _log_guard = logging::init(&LogConfig::init_from_env()?, "indexer")?;
 
     let indexer_config = IndexerConfig::init_from_env()?;
+    let shutdown = indexer::shutdown::listen()?;
     let client = HttpClient::new(indexer_config.http_rpc_url.clone());
 
     let db_config = DbConfig::init_from_env()?;
     let db_pool = store::connect(db_config).await;
 
     let client = Arc::new(client);
-    let epoch_repository = Arc::new(PostgresEpochRepository::new(db_pool.clone()));
-    let validator_repository = Arc::new(PostgresValidatorRepository::new(db_pool.clone()));
-    let attestation_repository = Arc::new(PostgresAttestationRepository::new(db_pool.clone()));
-    let committee_repository = Arc::new(PostgresCommitteeRepository::new(db_pool.clone()));
-    let proposer_repository = Arc::new(PostgresProposerRepository::new(db_pool.clone()));
-    let service = Arc::new(ServiceImpl::new(
-        epoch_repository,
-        validator_repository,
-        attestation_repository,
-        committee_repository,
-        proposer_repository,
-    ));
+    let service = Arc::new(ServiceImpl::new(store::repositories(db_pool.clone())));
+    let progress = Arc::new(Progress::default());
 
     let mut handle_set = JoinSet::new();
 
-    let polling_indexer = PollingIndexer::new(client.clone(), service.clone(), indexer_config.max_epoch);
+    let health = Arc::new(HealthState {
+        client: client.clone(),
+        pool: db_pool,
+        progress: progress.clone(),
+        stall_timeout: Duration::from_secs(indexer_config.health_stall_secs),
+    });
+    handle_set.spawn(indexer::server::serve(
+        SocketAddr::new(indexer_config.metrics_host, indexer_config.metrics_port),
+        health,
+    ));
+
+    let relays = match &indexer_config.relay_sources {
+        Some(sources) => relays_from_sources(sources)?,
+        None => Vec::new(),
+    };
+
+    let polling_indexer = PollingIndexer::new(client.clone(), service.clone(), indexer_config.max_epoch)
+        .with_relays(relays)
+        .with_blob_storage(indexer_config.store_blobs)
+        .with_progress(progress.clone())
+        .with_shutdown(shutdown);
 
-    handle_set.spawn(polling_indexer.run());
+    let mut polling = tokio::spawn(polling_indexer.run());
+
+    // Exits and BLS changes are capped at 16 per block and rarely gossiped, unlike attestations below, so these two
+    // streams are cheap enough to follow by default
+    if indexer_config.max_epoch.is_none() && indexer_config.subscribe_operations {
+        let stream = client.subscribe::<SignedVoluntaryExit>().await?.boxed();
+        handle_set.spawn(indexer::pubsub::index_voluntary_exits(service.clone(), stream));
+        let stream = client.subscribe::<SignedBlsToExecutionChange>().await?.boxed();
+        handle_set.spawn(indexer::pubsub::index_bls_to_execution_changes(service.clone(), stream));
+    }
 
     // Disable pubsub for now because of rate limiting
     // if indexer_config.max_epoch.is_none() {
@@ -60,9 +115,19 @@ async fn main() -> Result<()> {
     //         stream,
     //     ));
     // }
-    while let Some(result) = handle_set.join_next().await {
-        result??;
+
+    // The server and event streams would otherwise keep running, so the indexer exits once polling has stopped
+    loop {
+        tokio::select! {
+            result = &mut polling => {
+                result??;
+                break;
+            }
+            Some(result) = handle_set.join_next() => result??,
+        }
     }
+    handle_set.shutdown().await;
+    tracing::info!(last_processed_slot = progress.last_slot(), "Indexer stopped");
 
     Ok(())
 }
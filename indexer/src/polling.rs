@@ -1,21 +1,57 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::{anyhow, Result};
 use client::{
-    model::{block::BlockId, state::StateId},
+    model::{
+        block::{BlockHeaderData, BlockId},
+        state::StateId,
+        validator::{ValidatorData, ValidatorId},
+    },
+    relay::RelayClient,
     JsonRpcClient,
 };
 use service::{
-    model::{AttestationData, ValidatorDataInput},
+    model::{
+        AttestationData, BlobSidecar, BlsToExecutionChange, ChainEvent, Checkpoint, Deposit, ExecutionPayload,
+        PageRequest, RelayPayload, Slot, SlotStatus, ValidatorDataInput, VoluntaryExit, Withdrawal, MAX_PAGE_SIZE,
+    },
     Service,
 };
+use tokio::sync::watch;
+
+use crate::{
+    server::Progress,
+    util::{get_committee_for_slot_and_index, slashings_from_block},
+};
 
-use crate::util::get_committee_for_slot_and_index;
+// A relay delivers at most one payload per slot, so a page this size covers a whole epoch unless it is full
+const RELAY_PAGE_LIMIT: u64 = 100;
+const RELAY_RETRY_LIMIT: u64 = 32;
+const RELAY_MAX_ATTEMPTS: u32 = 5;
+const SECONDS_PER_SLOT: u64 = 12;
+// How far back a reorg is followed; anything deeper is left as indexed
+const MAX_REORG_DEPTH: u64 = 64;
+
+struct EpochDuties {
+    epoch: u64,
+    scheduled_proposers: HashMap<u64, u64>,
+    relays_covered: HashSet<String>,
+}
 
 pub struct PollingIndexer {
     pub client: Arc<dyn JsonRpcClient>,
     pub service: Arc<dyn Service>,
     pub max_epoch: Option<u64>,
+    pub relays: Vec<Arc<dyn RelayClient>>,
+    pub store_blobs: bool,
+    finalized_epoch: Mutex<Option<u64>>,
+    head_slot: Mutex<Option<u64>>,
+    progress: Arc<Progress>,
+    shutdown: watch::Receiver<bool>,
 }
 
 impl PollingIndexer {
@@ -24,61 +60,162 @@ impl PollingIndexer {
             client,
             service,
             max_epoch,
+            relays: Vec::new(),
+            store_blobs: false,
+            finalized_epoch: Mutex::new(None),
+            head_slot: Mutex::new(None),
+            progress: Arc::new(Progress::default()),
+            shutdown: watch::channel(false).1,
         }
     }
 
+    pub fn with_relays(mut self, relays: Vec<Arc<dyn RelayClient>>) -> Self {
+        self.relays = relays;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: Arc<Progress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub fn with_blob_storage(mut self, store_blobs: bool) -> Self {
+        self.store_blobs = store_blobs;
+        self
+    }
+
     pub async fn run(self) -> Result<()> {
         let finality_checkpoints = self.client.get_finality_checkpoints(StateId::Head).await?;
         self.index_current_validators().await?;
-        let current_epoch = finality_checkpoints.current_justified.epoch;
-        let max_epoch = match self.max_epoch {
-            Some(max) if max > current_epoch => max,
-            _ => current_epoch,
-        };
-        for epoch in 0..=max_epoch {
-            self.run_for_epoch(epoch).await?;
+        self.expire_voluntary_exits().await?;
+        let genesis_time = self.client.get_genesis().await?.genesis_time;
+        let last_epoch = self
+            .max_epoch
+            .map(|max_epoch| max_epoch.max(finality_checkpoints.current_justified.epoch));
+        // The slot row is the last write for a slot, so the slot after the newest one is where indexing stopped.
+        // Anything written for that slot before an interruption is overwritten when it is indexed again.
+        let mut slot = self.service.get_last_slot().await?.map_or(0, |last_slot| last_slot + 1);
+        if slot > 0 {
+            tracing::info!(slot, "Resuming after the last indexed slot");
+            self.progress.record(slot - 1);
         }
-        if self.max_epoch.is_none() {
-            loop {
-                self.run_for_epoch(max_epoch + 1).await?;
+        if let Some(epoch) = unfinished_epoch(slot) {
+            self.finish_epoch(epoch).await?;
+        }
+        let mut duties: Option<EpochDuties> = None;
+        while !self.is_shutting_down() {
+            let epoch = slot / 32;
+            if last_epoch.is_some_and(|last_epoch| epoch > last_epoch) {
+                break;
+            }
+            // A slot after the head has not been produced yet, so it is left until the head moves past it rather
+            // than being recorded as missed
+            if !self.head_reached(slot) {
+                if let Err(err) = self.update_head_slot().await {
+                    tracing::warn!("Failed to get head slot: {err}");
+                }
+                if !self.head_reached(slot) {
+                    self.wait_for_next_slot(genesis_time, slot).await;
+                    continue;
+                }
             }
+            let header = self
+                .client
+                .get_header_for_block(BlockId::Slot(slot))
+                .await?
+                .filter(|header| header.header.message.slot == slot);
+            if let Some(fork_slot) = self.find_fork_slot(slot, header.as_ref()).await? {
+                tracing::warn!(slot, fork_slot, "Chain was reorganised, indexing again from the fork");
+                slot = fork_slot;
+                continue;
+            }
+            let current = match duties.take() {
+                Some(duties) if duties.epoch == epoch => duties,
+                _ => self.start_epoch(epoch).await?,
+            };
+            self.index_slot(
+                epoch,
+                slot,
+                header,
+                &current.scheduled_proposers,
+                &current.relays_covered,
+            )
+            .await?;
+            if slot % 32 == 31 {
+                self.finish_epoch(epoch).await?;
+            }
+            duties = Some(current);
+            slot += 1;
+        }
+        if self.is_shutting_down() {
+            tracing::info!(last_slot = slot.checked_sub(1), "Stopped after the last complete slot");
         }
         Ok(())
     }
 
+    fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
     pub async fn index_current_validators(&self) -> Result<()> {
         let validators = self.client.validators_for_state(StateId::Head, &[], None).await?;
         let total_validator_count = validators.len() as u64;
-        log::info!("Adding validators");
+        tracing::info!(total = total_validator_count, "Adding validators");
         let mut added = 0;
         for chunk in validators.chunks(1000) {
-            let mut validator_data = Vec::new();
-            for data in chunk {
-                let pubkey = data.validator.pubkey.clone();
-                let activation_epoch = data.validator.activation_epoch;
-                let exit_epoch = data.validator.exit_epoch;
-                validator_data.push(ValidatorDataInput {
-                    index: data.index,
-                    pubkey,
-                    activation_epoch,
-                    exit_epoch,
-                });
-            }
+            let validator_data = chunk.iter().map(validator_data_input).collect::<Vec<_>>();
+            metrics::INDEXER_BATCH_SIZE.observe(&["validators"], validator_data.len() as f64);
             self.service.create_or_update_validator_batch(&validator_data).await?;
             added += chunk.len();
-            log::info!("Added {added}/{total_validator_count} validators");
+            tracing::info!(added, total = total_validator_count, "Added validators");
         }
 
         Ok(())
     }
 
+    // Gossiped exits stay pending until a block includes them, which never happens once the validator has exited
+    // some other way or was slashed, so those are dropped after refreshing the validators they belong to
+    pub async fn expire_voluntary_exits(&self) -> Result<()> {
+        let mut request = PageRequest {
+            limit: MAX_PAGE_SIZE,
+            ..Default::default()
+        };
+        loop {
+            let pending = self.service.get_pending_voluntary_exits(&request).await?;
+            if !pending.items.is_empty() {
+                let ids = pending
+                    .items
+                    .iter()
+                    .map(|(_, exit)| ValidatorId::Index(exit.validator_index))
+                    .collect::<Vec<_>>();
+                let validators = self.client.validators_for_state(StateId::Head, &ids, None).await?;
+                let validator_data = validators.iter().map(validator_data_input).collect::<Vec<_>>();
+                self.service.create_or_update_validator_batch(&validator_data).await?;
+            }
+            match pending.items.last() {
+                Some((cursor, _)) if pending.has_next_page => request.cursor = Some(*cursor),
+                _ => break,
+            }
+        }
+        let expired = self.service.expire_voluntary_exits().await?;
+        if expired > 0 {
+            tracing::info!(expired, "Expired pending voluntary exits");
+        }
+        Ok(())
+    }
+
     pub async fn index_committees_for_epoch(&self, epoch: u64) -> Result<()> {
         let slot = epoch * 32;
         let committees = self
             .client
             .get_committees_for_state(StateId::Slot(slot), Some(epoch), None, None)
             .await?;
-        log::info!("Adding committees for epoch {epoch}");
+        tracing::info!(count = committees.len(), "Adding committees");
         let total_committee_count = committees.len();
         let mut added = 0;
         for chunk in committees.chunks(1000) {
@@ -95,9 +232,10 @@ impl PollingIndexer {
                 });
             }
             let service = self.service.clone();
+            metrics::INDEXER_BATCH_SIZE.observe(&["committees"], committee_data.len() as f64);
             service.create_or_update_committee_batch(&committee_data).await?;
             added += chunk.len();
-            log::info!("Added {added}/{total_committee_count} committees");
+            tracing::info!(added, total = total_committee_count, "Added committees");
         }
         Ok(())
     }
@@ -105,76 +243,586 @@ impl PollingIndexer {
     pub async fn create_epoch(&self, epoch: u64) -> Result<()> {
         let active_validator_count = self.service.active_validator_count(epoch).await?;
         let total_validator_count = self.service.total_validator_count(epoch).await?;
-        log::info!("Creating epoch {epoch} with {active_validator_count} active validators and {total_validator_count} total validators");
+        tracing::info!(
+            epoch,
+            active_validators = active_validator_count,
+            total_validators = total_validator_count,
+            "Creating epoch"
+        );
         self.service
             .create_epoch(epoch, active_validator_count, total_validator_count)
             .await?;
         Ok(())
     }
 
-    pub async fn run_for_epoch(&self, epoch: u64) -> Result<()> {
-        log::info!("Processing epoch {epoch}");
+    pub async fn index_proposer_duties_for_epoch(&self, epoch: u64) -> Result<HashMap<u64, u64>> {
+        // No validator is asked to propose the genesis block, so the duty returned for slot 0 is dropped
+        let duties = self
+            .client
+            .get_proposers_for_epoch(epoch)
+            .await?
+            .into_iter()
+            .filter(|duty| duty.slot != 0)
+            .map(service::model::Proposer::from)
+            .collect::<Vec<_>>();
+        tracing::info!(count = duties.len(), "Adding proposer duties");
+        metrics::INDEXER_BATCH_SIZE.observe(&["proposer_duties"], duties.len() as f64);
+        self.service.create_proposer_duties(&duties).await?;
+        Ok(duties
+            .into_iter()
+            .map(|duty| (duty.slot, duty.validator_index))
+            .collect())
+    }
+
+    pub async fn index_block(&self, slot: u64, relays_covered: &HashSet<String>) -> Result<()> {
+        let block = match self.client.get_block(BlockId::Slot(slot)).await? {
+            Some(block) => block,
+            None => return Ok(()),
+        };
+        let slashings = slashings_from_block(&block)?;
+        if !slashings.is_empty() {
+            tracing::info!(count = slashings.len(), "Adding slashings");
+            metrics::INDEXER_BATCH_SIZE.observe(&["slashings"], slashings.len() as f64);
+            self.service.create_slashings(&slashings).await?;
+        }
+        let body = block.message.body;
+        let deposits = body
+            .deposits
+            .into_iter()
+            .enumerate()
+            .map(|(position, deposit)| Deposit {
+                slot,
+                position: position as u64,
+                pubkey: deposit.data.pubkey.to_lowercase(),
+                withdrawal_credentials: deposit.data.withdrawal_credentials.to_lowercase(),
+                amount: deposit.data.amount,
+                validator_index: None,
+            })
+            .collect::<Vec<_>>();
+        if !deposits.is_empty() {
+            tracing::info!(count = deposits.len(), "Adding deposits");
+            metrics::INDEXER_BATCH_SIZE.observe(&["deposits"], deposits.len() as f64);
+            self.service.create_deposits(&deposits).await?;
+        }
+        let execution_payload = body.execution_payload.filter(|payload| !payload.is_empty());
+        if let Some(payload) = &execution_payload {
+            let payload = ExecutionPayload::new(slot, block.message.proposer_index, payload);
+            tracing::info!(block_number = payload.block_number, "Adding execution payload");
+            self.service.create_execution_payload(&payload).await?;
+            self.index_relay_payloads(slot, relays_covered).await?;
+        }
+        let withdrawals = execution_payload
+            .map(|payload| payload.withdrawals)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|withdrawal| Withdrawal::new(slot, withdrawal))
+            .collect::<Vec<_>>();
+        if !withdrawals.is_empty() {
+            tracing::info!(count = withdrawals.len(), "Adding withdrawals");
+            metrics::INDEXER_BATCH_SIZE.observe(&["withdrawals"], withdrawals.len() as f64);
+            self.service.create_withdrawals(&withdrawals).await?;
+        }
+        let exits = body
+            .voluntary_exits
+            .into_iter()
+            .map(|exit| VoluntaryExit {
+                inclusion_slot: Some(slot),
+                ..exit.into()
+            })
+            .collect::<Vec<_>>();
+        if !exits.is_empty() {
+            tracing::info!(count = exits.len(), "Adding voluntary exits");
+            metrics::INDEXER_BATCH_SIZE.observe(&["voluntary_exits"], exits.len() as f64);
+            self.service.create_voluntary_exits(&exits).await?;
+        }
+        let changes = body
+            .bls_to_execution_changes
+            .into_iter()
+            .map(|change| BlsToExecutionChange {
+                inclusion_slot: Some(slot),
+                ..change.into()
+            })
+            .collect::<Vec<_>>();
+        if !changes.is_empty() {
+            tracing::info!(count = changes.len(), "Adding BLS to execution changes");
+            metrics::INDEXER_BATCH_SIZE.observe(&["bls_to_execution_changes"], changes.len() as f64);
+            self.service.create_bls_to_execution_changes(&changes).await?;
+        }
+        if !body.blob_kzg_commitments.is_empty() {
+            self.index_blob_sidecars(slot, block.message.proposer_index, &body.blob_kzg_commitments)
+                .await?;
+        }
+        Ok(())
+    }
+
+    // The block already carries the commitments, so the full sidecars are only downloaded when blobs are stored
+    pub async fn index_blob_sidecars(&self, slot: u64, proposer_index: u64, commitments: &[String]) -> Result<()> {
+        let sidecars = if self.store_blobs {
+            self.client
+                .get_blob_sidecars(BlockId::Slot(slot))
+                .await?
+                .into_iter()
+                .map(|sidecar| BlobSidecar::new(slot, proposer_index, sidecar))
+                .collect::<Vec<_>>()
+        } else {
+            commitments
+                .iter()
+                .enumerate()
+                .map(|(index, commitment)| BlobSidecar::from_commitment(slot, proposer_index, index as u64, commitment))
+                .collect::<Vec<_>>()
+        };
+        if !sidecars.is_empty() {
+            tracing::info!(count = sidecars.len(), "Adding blob sidecars");
+            metrics::INDEXER_BATCH_SIZE.observe(&["blob_sidecars"], sidecars.len() as f64);
+            self.service.create_blob_sidecars(&sidecars).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn index_relay_payloads(&self, slot: u64, relays_covered: &HashSet<String>) -> Result<()> {
+        let mut payloads = Vec::new();
+        for relay in &self.relays {
+            if relays_covered.contains(relay.name()) {
+                continue;
+            }
+            match relay.get_delivered_payloads_for_slot(slot).await {
+                Ok(traces) => payloads.extend(traces.into_iter().map(|trace| RelayPayload::new(relay.name(), trace))),
+                Err(err) => {
+                    tracing::warn!(relay = relay.name(), "Failed to get delivered payloads: {err}");
+                    self.service.record_relay_failures(relay.name(), &[slot]).await?;
+                }
+            }
+        }
+        self.create_relay_payloads(&payloads).await
+    }
+
+    // Backfilled epochs are fetched with one paged request per relay instead of one per slot. Returns the relays
+    // whose page covered the whole epoch; the others fall back to per slot requests.
+    pub async fn index_relay_payloads_for_epoch(&self, epoch: u64) -> Result<HashSet<String>> {
+        let start_slot = epoch * 32;
+        let end_slot = start_slot + 31;
+        let mut covered = HashSet::new();
+        let mut payloads = Vec::new();
+        for relay in &self.relays {
+            match relay.get_delivered_payloads(end_slot, RELAY_PAGE_LIMIT).await {
+                Ok(traces) => {
+                    let complete =
+                        (traces.len() as u64) < RELAY_PAGE_LIMIT || traces.iter().any(|trace| trace.slot < start_slot);
+                    payloads.extend(
+                        traces
+                            .into_iter()
+                            .filter(|trace| (start_slot..=end_slot).contains(&trace.slot))
+                            .map(|trace| RelayPayload::new(relay.name(), trace)),
+                    );
+                    if complete {
+                        covered.insert(relay.name().to_string());
+                    }
+                }
+                Err(err) => tracing::warn!(relay = relay.name(), "Failed to get delivered payloads: {err}"),
+            }
+        }
+        self.create_relay_payloads(&payloads).await?;
+        Ok(covered)
+    }
+
+    pub async fn retry_relay_failures(&self) -> Result<()> {
+        let failures = self
+            .service
+            .get_relay_failures(RELAY_MAX_ATTEMPTS, RELAY_RETRY_LIMIT)
+            .await?;
+        for failure in failures {
+            let Some(relay) = self.relays.iter().find(|relay| relay.name() == failure.relay) else {
+                continue;
+            };
+            match relay.get_delivered_payloads_for_slot(failure.slot).await {
+                Ok(traces) => {
+                    let payloads = traces
+                        .into_iter()
+                        .map(|trace| RelayPayload::new(relay.name(), trace))
+                        .collect::<Vec<_>>();
+                    self.create_relay_payloads(&payloads).await?;
+                    self.service.delete_relay_failure(relay.name(), failure.slot).await?;
+                }
+                Err(err) => {
+                    tracing::warn!(relay = relay.name(), slot = failure.slot, "Retry failed: {err}");
+                    self.service
+                        .record_relay_failures(relay.name(), &[failure.slot])
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_relay_payloads(&self, payloads: &[RelayPayload]) -> Result<()> {
+        if !payloads.is_empty() {
+            tracing::info!(count = payloads.len(), "Adding relay payloads");
+            metrics::INDEXER_BATCH_SIZE.observe(&["relay_payloads"], payloads.len() as f64);
+            self.service.create_relay_payloads(payloads).await?;
+        }
+        Ok(())
+    }
+
+    async fn status_for_slot_without_block(&self, slot: u64) -> Result<SlotStatus> {
+        let headers = self.client.get_headers_for_slot(slot).await?;
+        Ok(status_without_block(slot, &headers))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn start_epoch(&self, epoch: u64) -> Result<EpochDuties> {
+        tracing::info!("Processing epoch");
+        if let Err(err) = self.update_head_slot().await {
+            tracing::warn!("Failed to get head slot: {err}");
+        }
         let start_slot = epoch * 32;
         self.create_epoch(epoch).await?;
         if epoch != 0 {
             self.create_epoch(epoch - 1).await?;
         }
         self.index_committees_for_epoch(epoch).await?;
-        for slot in start_slot..start_slot + 32 {
-            log::info!("Processing slot {slot}");
-            // Can't use get_proposers_for_epoch because it doesn't return the correct proposer for the genesis slot
-            let header = self.client.get_header_for_block(BlockId::Slot(slot)).await?;
-            if let Some(header) = header {
-                let proposer = header.message.proposer_index;
-                self.service.create_proposer(slot, proposer).await?;
+        let scheduled_proposers = self.index_proposer_duties_for_epoch(epoch).await?;
+        let relays_covered = if self.head_reached(start_slot + 31) {
+            self.index_relay_payloads_for_epoch(epoch).await?
+        } else {
+            HashSet::new()
+        };
+        Ok(EpochDuties {
+            epoch,
+            scheduled_proposers,
+            relays_covered,
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn finish_epoch(&self, epoch: u64) -> Result<()> {
+        if let Err(err) = self.retry_relay_failures().await {
+            tracing::warn!("Failed to retry relay payloads: {err}");
+        }
+        // Attestations for an epoch keep landing during the next one, so the previous epoch is now complete
+        if epoch != 0 {
+            self.publish_event(ChainEvent::Epoch { index: epoch - 1 }).await;
+        }
+        self.publish_finalized_checkpoint().await?;
+        // Exits are only checked once the indexer has caught up, instead of after every backfilled epoch
+        if !self.head_reached((epoch + 2) * 32) {
+            if let Err(err) = self.expire_voluntary_exits().await {
+                tracing::warn!("Failed to expire voluntary exits: {err}");
             }
-            let attestations = match self.client.get_attestations_for_block(BlockId::Slot(slot)).await? {
-                Some(attestations) => attestations,
-                None => continue,
-            };
-            log::debug!("attestations.len() = {}", attestations.len());
-
-            let mut batch = Vec::new();
-            for attestation in attestations {
-                let index = attestation.data.index;
-                log::debug!(
-                    "Processing attestation with slot = {}, index = {}, target epoch = {}",
-                    attestation.data.slot,
-                    index,
-                    attestation.data.target.epoch
-                );
-                let committee = get_committee_for_slot_and_index(
-                    self.client.clone(),
-                    self.service.clone(),
-                    attestation.data.slot,
-                    attestation.data.index,
-                )
+        }
+        Ok(())
+    }
+
+    fn head_reached(&self, slot: u64) -> bool {
+        self.head_slot
+            .lock()
+            .unwrap()
+            .is_some_and(|head_slot| slot <= head_slot)
+    }
+
+    // Sleeps until the next slot starts on the wall clock, or until shutdown
+    async fn wait_for_next_slot(&self, genesis_time: u64, slot: u64) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let current_slot = now.as_secs().saturating_sub(genesis_time) / SECONDS_PER_SLOT;
+        let next_slot = slot.max(current_slot + 1);
+        let wait = Duration::from_secs(genesis_time + next_slot * SECONDS_PER_SLOT).saturating_sub(now);
+        let mut shutdown = self.shutdown.clone();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shutdown.changed() => {}
+        }
+    }
+
+    // A block whose parent isn't the newest indexed block means the chain was reorganised after those slots were
+    // indexed. Indexed and canonical roots are compared going back from the slot to find where they diverge.
+    async fn find_fork_slot(&self, slot: u64, header: Option<&BlockHeaderData>) -> Result<Option<u64>> {
+        let Some(header) = header else {
+            return Ok(None);
+        };
+        let Some(previous) = self.service.get_block_header_before(slot).await? else {
+            return Ok(None);
+        };
+        if previous.root == header.header.message.parent_root {
+            return Ok(None);
+        }
+        let finalized_slot = self.finalized_epoch.lock().unwrap().unwrap_or(0) * 32;
+        let floor = slot.saturating_sub(MAX_REORG_DEPTH).max(finalized_slot);
+        let mut roots = Vec::new();
+        for previous_slot in (floor..slot).rev() {
+            let indexed = self
+                .service
+                .get_block_header(previous_slot)
+                .await?
+                .map(|header| header.root);
+            let canonical = self
+                .client
+                .get_header_for_block(BlockId::Slot(previous_slot))
                 .await?
-                .ok_or(anyhow!("Committee not found"))?;
+                .filter(|header| header.header.message.slot == previous_slot)
+                .map(|header| header.root);
+            let common = indexed.is_some() && indexed == canonical;
+            roots.push((previous_slot, indexed, canonical));
+            if common {
+                break;
+            }
+        }
+        Ok(fork_slot(&roots))
+    }
+
+    #[tracing::instrument(skip(self, header, scheduled_proposers, relays_covered))]
+    async fn index_slot(
+        &self,
+        epoch: u64,
+        slot: u64,
+        header: Option<BlockHeaderData>,
+        scheduled_proposers: &HashMap<u64, u64>,
+        relays_covered: &HashSet<String>,
+    ) -> Result<()> {
+        tracing::info!("Processing slot");
+        let scheduled_proposer = match &header {
+            // There is no proposer duty for the genesis slot, so credit the genesis block's proposer
+            Some(header) if slot == 0 => header.header.message.proposer_index,
+            _ => *scheduled_proposers
+                .get(&slot)
+                .ok_or(anyhow!("No proposer scheduled for slot {slot}"))?,
+        };
+        let previous_root = self.service.get_block_header(slot).await?.map(|previous| previous.root);
+        let reorged = previous_root.is_some() && previous_root != header.as_ref().map(|header| header.root.clone());
+        // Rows from the replaced block are keyed by slot and mostly never overwritten, so clear them before
+        // indexing whatever is at the slot now
+        if reorged {
+            self.service.delete_block(slot).await?;
+        }
+        let status = match header {
+            Some(header) => {
+                let proposer = header.header.message.proposer_index;
+                self.service.create_proposer(slot, proposer).await?;
+                self.service.create_block_header(&header.into()).await?;
+                self.index_block(slot, relays_covered).await?;
+                SlotStatus::Proposed
+            }
+            None => self.status_for_slot_without_block(slot).await?,
+        };
+        let attestations = self
+            .client
+            .get_attestations_for_block(BlockId::Slot(slot))
+            .await?
+            .unwrap_or_default();
+        tracing::debug!(count = attestations.len(), "Processing attestations");
 
-                let aggregation_bits = attestation.aggregation_bits.clone();
+        let mut batch = Vec::new();
+        for attestation in attestations {
+            let index = attestation.data.index;
+            tracing::debug!(
+                attestation_slot = attestation.data.slot,
+                index,
+                target_epoch = attestation.data.target.epoch,
+                "Processing attestation"
+            );
+            let committee = get_committee_for_slot_and_index(
+                self.client.clone(),
+                self.service.clone(),
+                attestation.data.slot,
+                attestation.data.index,
+            )
+            .await?
+            .ok_or(anyhow!("Committee not found"))?;
 
-                log::debug!("Aggregation bits: {aggregation_bits}");
+            let aggregation_bits = attestation.aggregation_bits.clone();
 
-                let aggregation_bits = aggregation_bits.trim_start_matches("0x");
-                let aggregation_bits = hex::decode(aggregation_bits)?;
-                let bit_vec = bit_vec::BitVec::from_bytes(&aggregation_bits);
+            tracing::debug!(%aggregation_bits, "Decoding aggregation bits");
 
-                for (i, validator) in committee.validators.iter().enumerate() {
-                    let attested = bit_vec[i];
-                    batch.push(AttestationData {
-                        epoch: attestation.data.target.epoch,
-                        validator: *validator,
-                        slot: attestation.data.slot,
-                        committee_index: attestation.data.index,
-                        attested,
-                    });
-                }
+            let aggregation_bits = aggregation_bits.trim_start_matches("0x");
+            let aggregation_bits = hex::decode(aggregation_bits)?;
+            let bit_vec = bit_vec::BitVec::from_bytes(&aggregation_bits);
+
+            for (i, validator) in committee.validators.iter().enumerate() {
+                let attested = bit_vec[i];
+                batch.push(AttestationData {
+                    epoch: attestation.data.target.epoch,
+                    validator: *validator,
+                    slot: attestation.data.slot,
+                    committee_index: attestation.data.index,
+                    attested,
+                    inclusion_slot: Some(slot),
+                });
             }
-            log::info!("Adding attestations for slot {slot}");
+        }
+        if !batch.is_empty() {
+            tracing::info!(count = batch.len(), "Adding attestations");
+            metrics::INDEXER_BATCH_SIZE.observe(&["attestations"], batch.len() as f64);
             self.service.create_or_update_attestation_batch(&batch).await?;
         }
 
+        // The slot row and its head event come last, so anything reacting to them sees the whole slot
+        tracing::info!(%status, proposer = scheduled_proposer, "Slot indexed");
+        let indexed_slot = Slot {
+            slot,
+            epoch,
+            proposer_index: scheduled_proposer,
+            status,
+        };
+        self.service.create_slot(&indexed_slot).await?;
+        self.record_indexed_slot(slot);
+        if reorged {
+            tracing::info!("Block was replaced by a reorg");
+            self.publish_event(ChainEvent::Reorg { slot }).await;
+        }
+        self.publish_event(ChainEvent::Head(indexed_slot)).await;
+        Ok(())
+    }
+
+    async fn update_head_slot(&self) -> Result<()> {
+        if let Some(head) = self.client.get_header_for_block(BlockId::Head).await? {
+            let slot = head.header.message.slot;
+            *self.head_slot.lock().unwrap() = Some(slot);
+            metrics::INDEXER_HEAD_SLOT.set(&[], slot as f64);
+        }
+        Ok(())
+    }
+
+    fn record_indexed_slot(&self, slot: u64) {
+        self.progress.record(slot);
+        metrics::INDEXER_INDEXED_SLOT.set(&[], slot as f64);
+        if let Some(head_slot) = *self.head_slot.lock().unwrap() {
+            metrics::INDEXER_LAG_SLOTS.set(&[], head_slot.saturating_sub(slot) as f64);
+        }
+    }
+
+    async fn publish_finalized_checkpoint(&self) -> Result<()> {
+        let finalized = self.client.get_finality_checkpoints(StateId::Head).await?.finalized;
+        let is_new = {
+            let mut finalized_epoch = self.finalized_epoch.lock().unwrap();
+            let is_new = *finalized_epoch != Some(finalized.epoch);
+            *finalized_epoch = Some(finalized.epoch);
+            is_new
+        };
+        if is_new {
+            tracing::info!(epoch = finalized.epoch, "Finalized checkpoint");
+            self.publish_event(ChainEvent::FinalizedCheckpoint(Checkpoint {
+                epoch: finalized.epoch,
+                root: finalized.root,
+            }))
+            .await;
+        }
         Ok(())
     }
+
+    async fn publish_event(&self, event: ChainEvent) {
+        if let Err(err) = self.service.publish_event(&event).await {
+            tracing::warn!("Failed to publish chain event: {err}");
+        }
+    }
+}
+
+fn validator_data_input(data: &ValidatorData) -> ValidatorDataInput {
+    ValidatorDataInput {
+        index: data.index,
+        pubkey: data.validator.pubkey.to_lowercase(),
+        activation_eligibility_epoch: data.validator.activation_eligibility_epoch,
+        activation_epoch: data.validator.activation_epoch,
+        exit_epoch: data.validator.exit_epoch,
+        withdrawable_epoch: data.validator.withdrawable_epoch,
+        slashed: data.validator.slashed,
+        withdrawal_credentials: data.validator.withdrawal_credentials.to_lowercase(),
+    }
+}
+
+// A slot with no canonical block was orphaned if the node still knows a block for it that lost out
+fn status_without_block(slot: u64, headers: &[BlockHeaderData]) -> SlotStatus {
+    let orphaned = headers
+        .iter()
+        .any(|data| !data.canonical && data.header.message.slot == slot);
+    if orphaned {
+        SlotStatus::Orphaned
+    } else {
+        SlotStatus::Missed
+    }
+}
+
+// Takes the indexed and canonical block roots of each slot, newest first, and returns the oldest slot that differs
+// after the newest block both agree on
+fn fork_slot(roots: &[(u64, Option<String>, Option<String>)]) -> Option<u64> {
+    roots
+        .iter()
+        .take_while(|(_, indexed, canonical)| indexed.is_none() || indexed != canonical)
+        .filter(|(_, indexed, canonical)| indexed != canonical)
+        .map(|(slot, _, _)| *slot)
+        .min()
+}
+
+// The last slot of an epoch is written before the epoch is finished, so an interruption between the two leaves an
+// epoch that was never finished. Finishing it again only republishes its events.
+fn unfinished_epoch(resume_slot: u64) -> Option<u64> {
+    (resume_slot > 0 && resume_slot.is_multiple_of(32)).then(|| resume_slot / 32 - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(value: &str) -> Option<String> {
+        Some(value.to_string())
+    }
+
+    fn header(slot: u64, root: &str, parent_root: &str, canonical: bool) -> BlockHeaderData {
+        serde_json::from_value(serde_json::json!({
+            "root": root,
+            "canonical": canonical,
+            "header": {
+                "message": {
+                    "slot": slot.to_string(),
+                    "proposer_index": "7",
+                    "parent_root": parent_root,
+                    "state_root": "0x00",
+                    "body_root": "0x00",
+                },
+                "signature": "0x00",
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reorged_out_block_is_orphaned() {
+        // Slot 11 was indexed as proposed, then the block at 12 was built on 10 instead
+        let roots = [(11, root("0x11"), None), (10, root("0x10"), root("0x10"))];
+        let fork = fork_slot(&roots).unwrap();
+        assert_eq!(fork, 11);
+        let headers = [header(11, "0x11", "0x10", false)];
+        assert_eq!(status_without_block(fork, &headers), SlotStatus::Orphaned);
+        assert_eq!(status_without_block(fork, &[]), SlotStatus::Missed);
+    }
+
+    #[test]
+    fn resume_finishes_the_previous_epoch_at_a_boundary() {
+        assert_eq!(unfinished_epoch(0), None);
+        assert_eq!(unfinished_epoch(31), None);
+        assert_eq!(unfinished_epoch(32), Some(0));
+        assert_eq!(unfinished_epoch(33), None);
+        assert_eq!(unfinished_epoch(96), Some(2));
+    }
+
+    #[test]
+    fn fork_slot_after_common_block() {
+        // Slot 12 was replaced, slot 11 lost its block and slot 10 is where both chains meet
+        let roots = [
+            (12, root("0xa12"), root("0xb12")),
+            (11, root("0xa11"), None),
+            (10, root("0x10"), root("0x10")),
+            (9, root("0xa9"), root("0xb9")),
+        ];
+        assert_eq!(fork_slot(&roots), Some(11));
+    }
+
+    #[test]
+    fn fork_slot_with_late_block() {
+        // Slot 11 was indexed as missed before its block arrived
+        let roots = [
+            (12, None, None),
+            (11, None, root("0x11")),
+            (10, root("0x10"), root("0x10")),
+        ];
+        assert_eq!(fork_slot(&roots), Some(11));
+        assert_eq!(fork_slot(&[(10, root("0x10"), root("0x10"))]), None);
+    }
 }
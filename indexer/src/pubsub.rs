@@ -1,9 +1,18 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use client::{model::attestation::Attestation, JsonRpcClient};
+use client::{
+    model::{
+        attestation::Attestation, bls_to_execution_change::SignedBlsToExecutionChange,
+        voluntary_exit::SignedVoluntaryExit,
+    },
+    JsonRpcClient,
+};
 use futures_util::{stream::BoxStream, StreamExt};
-use service::Service;
+use service::{
+    model::{BlsToExecutionChange, VoluntaryExit},
+    Service,
+};
 
 use crate::util::process_attestation;
 
@@ -16,7 +25,7 @@ pub async fn index_attestations(
         let attestation = match attestation {
             Ok(attestation) => attestation,
             Err(e) => {
-                log::error!("Error receiving attestation: {:?}", e);
+                tracing::error!("Error receiving attestation: {:?}", e);
                 continue;
             }
         };
@@ -26,10 +35,53 @@ pub async fn index_attestations(
             match process_attestation(client, service, attestation).await {
                 Ok(_) => {}
                 Err(e) => {
-                    log::error!("Error processing attestation: {:?}", e);
+                    tracing::error!("Error processing attestation: {:?}", e);
                 }
             }
         });
     }
     Ok(())
 }
+
+pub async fn index_voluntary_exits(
+    service: Arc<dyn Service>,
+    mut stream: BoxStream<'_, Result<SignedVoluntaryExit>>,
+) -> Result<()> {
+    while let Some(exit) = stream.next().await {
+        let exit = match exit {
+            Ok(exit) => VoluntaryExit::from(exit),
+            Err(e) => {
+                tracing::error!("Error receiving voluntary exit: {:?}", e);
+                continue;
+            }
+        };
+        tracing::info!(validator = exit.validator_index, "Adding pending voluntary exit");
+        if let Err(e) = service.create_voluntary_exits(&[exit]).await {
+            tracing::error!("Error processing voluntary exit: {:?}", e);
+        }
+    }
+    Ok(())
+}
+
+pub async fn index_bls_to_execution_changes(
+    service: Arc<dyn Service>,
+    mut stream: BoxStream<'_, Result<SignedBlsToExecutionChange>>,
+) -> Result<()> {
+    while let Some(change) = stream.next().await {
+        let change = match change {
+            Ok(change) => BlsToExecutionChange::from(change),
+            Err(e) => {
+                tracing::error!("Error receiving BLS to execution change: {:?}", e);
+                continue;
+            }
+        };
+        tracing::info!(
+            validator = change.validator_index,
+            "Adding pending BLS to execution change"
+        );
+        if let Err(e) = service.create_bls_to_execution_changes(&[change]).await {
+            tracing::error!("Error processing BLS to execution change: {:?}", e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use client::JsonRpcClient;
+use hyper::{
+    header::{HeaderValue, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde::Serialize;
+use store::{DbHealth, Pool};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct Progress {
+    started_at: Instant,
+    last: Mutex<Option<(u64, Instant)>>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            last: Mutex::new(None),
+        }
+    }
+}
+
+impl Progress {
+    pub fn record(&self, slot: u64) {
+        *self.last.lock().unwrap() = Some((slot, Instant::now()));
+    }
+
+    pub fn last_slot(&self) -> Option<u64> {
+        self.last.lock().unwrap().map(|(slot, _)| slot)
+    }
+
+    // Before the first slot is written, progress is measured from startup so a slow start isn't reported as stuck
+    fn snapshot(&self) -> (Option<u64>, Duration) {
+        match *self.last.lock().unwrap() {
+            Some((slot, at)) => (Some(slot), at.elapsed()),
+            None => (None, self.started_at.elapsed()),
+        }
+    }
+}
+
+pub struct HealthState {
+    pub client: Arc<dyn JsonRpcClient>,
+    pub pool: Pool,
+    pub progress: Arc<Progress>,
+    pub stall_timeout: Duration,
+}
+
+#[derive(Serialize)]
+struct Liveness {
+    status: &'static str,
+    last_processed_slot: Option<u64>,
+    seconds_since_progress: u64,
+}
+
+#[derive(Serialize)]
+struct BeaconHealth {
+    reachable: bool,
+    error: Option<String>,
+    head_slot: Option<u64>,
+    sync_distance: Option<u64>,
+    is_syncing: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct Readiness {
+    status: &'static str,
+    database: DbHealth,
+    beacon_node: BeaconHealth,
+    last_processed_slot: Option<u64>,
+    seconds_since_progress: u64,
+}
+
+pub async fn serve(addr: SocketAddr, state: Arc<HealthState>) -> Result<()> {
+    let make_service = make_service_fn(move |_| {
+        let state = state.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+    });
+    tracing::info!(%addr, "Serving metrics and health checks");
+    Server::try_bind(&addr)?.serve(make_service).await?;
+    Ok(())
+}
+
+async fn handle(state: Arc<HealthState>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let response = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => text_response(metrics::render()),
+        (&Method::GET, "/health/live") => live(&state),
+        (&Method::GET, "/health/ready") => ready(&state).await,
+        _ => {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_FOUND;
+            response
+        }
+    };
+    Ok(response)
+}
+
+fn live(state: &HealthState) -> Response<Body> {
+    let (last_processed_slot, since_progress) = state.progress.snapshot();
+    let stalled = since_progress > state.stall_timeout;
+    json_response(
+        !stalled,
+        &Liveness {
+            status: if stalled { "stalled" } else { "ok" },
+            last_processed_slot,
+            seconds_since_progress: since_progress.as_secs(),
+        },
+    )
+}
+
+async fn ready(state: &HealthState) -> Response<Body> {
+    let (database, beacon_node) = tokio::join!(
+        store::check_health(&state.pool, CHECK_TIMEOUT),
+        check_beacon_node(state.client.as_ref())
+    );
+    let (last_processed_slot, since_progress) = state.progress.snapshot();
+    // A syncing beacon node still serves data, so it is reported without failing readiness
+    let is_ready = database.connected && beacon_node.reachable;
+    json_response(
+        is_ready,
+        &Readiness {
+            status: if is_ready { "ok" } else { "unavailable" },
+            database,
+            beacon_node,
+            last_processed_slot,
+            seconds_since_progress: since_progress.as_secs(),
+        },
+    )
+}
+
+async fn check_beacon_node(client: &dyn JsonRpcClient) -> BeaconHealth {
+    let result = match tokio::time::timeout(CHECK_TIMEOUT, client.get_sync_status()).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!("Timed out waiting for the beacon node")),
+    };
+    match result {
+        Ok(status) => BeaconHealth {
+            reachable: true,
+            error: None,
+            head_slot: Some(status.head_slot),
+            sync_distance: Some(status.sync_distance),
+            is_syncing: Some(status.is_syncing),
+        },
+        Err(err) => BeaconHealth {
+            reachable: false,
+            error: Some(err.to_string()),
+            head_slot: None,
+            sync_distance: None,
+            is_syncing: None,
+        },
+    }
+}
+
+fn text_response(body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(metrics::CONTENT_TYPE));
+    response
+}
+
+fn json_response<T: Serialize>(healthy: bool, body: &T) -> Response<Body> {
+    let body = serde_json::to_string(body).unwrap_or_default();
+    let mut response = Response::new(Body::from(body));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    if !healthy {
+        *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+    }
+    response
+}
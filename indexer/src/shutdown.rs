@@ -0,0 +1,60 @@
+use anyhow::Result;
+use tokio::sync::watch;
+
+// The receiver flips to true on the first signal, so work in progress can finish before exiting. A second signal
+// exits straight away, since that work may be stuck on a request that never returns.
+pub fn listen() -> Result<watch::Receiver<bool>> {
+    let mut signals = Signals::new()?;
+    let (sender, receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        let name = signals.recv().await;
+        tracing::info!("Received {name}, shutting down after the current slot");
+        let _ = sender.send(true);
+        let name = signals.recv().await;
+        tracing::warn!("Received {name} again, exiting without waiting for the current slot");
+        std::process::exit(130);
+    });
+    Ok(receiver)
+}
+
+#[cfg(unix)]
+struct Signals {
+    interrupt: tokio::signal::unix::Signal,
+    terminate: tokio::signal::unix::Signal,
+}
+
+#[cfg(unix)]
+impl Signals {
+    fn new() -> Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+        Ok(Self {
+            interrupt: signal(SignalKind::interrupt())?,
+            terminate: signal(SignalKind::terminate())?,
+        })
+    }
+
+    async fn recv(&mut self) -> &'static str {
+        tokio::select! {
+            _ = self.interrupt.recv() => "SIGINT",
+            _ = self.terminate.recv() => "SIGTERM",
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct Signals;
+
+#[cfg(not(unix))]
+impl Signals {
+    fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    async fn recv(&mut self) -> &'static str {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl-C: {err}");
+            std::future::pending::<()>().await;
+        }
+        "Ctrl-C"
+    }
+}
@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use client::{
-    model::{attestation::Attestation, state::StateId},
+    model::{attestation::Attestation, block::SignedBlock, state::StateId},
     JsonRpcClient,
 };
-use service::{model::AttestationData, Service};
+use service::{
+    model::{AttestationData, Slashing, SlashingKind},
+    Service,
+};
 
 pub async fn get_committee_for_slot_and_index(
     client: Arc<dyn JsonRpcClient>,
@@ -39,12 +42,12 @@ pub async fn process_attestation(
     let epoch = attestation.data.target.epoch;
     let slot = attestation.data.slot;
     let index = attestation.data.index;
-    log::info!("Processing attestation at slot {slot} and index {index}");
+    tracing::info!(slot, index, "Processing attestation");
     let committee = get_committee_for_slot_and_index(client.clone(), service.clone(), slot, index)
         .await?
         .ok_or(anyhow!("Committee not found for slot {slot} and index {index}"))?;
     let aggregation_bits = attestation.aggregation_bits.clone();
-    log::debug!("Aggregation bits: {aggregation_bits}");
+    tracing::debug!(%aggregation_bits, "Decoding aggregation bits");
     let aggregation_bits = aggregation_bits.trim_start_matches("0x");
     let aggregation_bits = hex::decode(aggregation_bits)?;
     let bit_vec = bit_vec::BitVec::from_bytes(&aggregation_bits);
@@ -58,9 +61,43 @@ pub async fn process_attestation(
             slot,
             committee_index: index,
             attested,
+            inclusion_slot: None,
         });
     }
-    log::info!("Adding attestations for slot {slot} and index {index}");
+    tracing::info!(slot, index, count = batch.len(), "Adding attestations");
     service.create_or_update_attestation_batch(&batch).await?;
     Ok(())
 }
+
+pub fn slashings_from_block(block: &SignedBlock) -> Result<Vec<Slashing>> {
+    let slot = block.message.slot;
+    // The block proposer is credited as whistleblower since blocks carry no separate whistleblower index
+    let proposer_index = block.message.proposer_index;
+    let mut slashings = Vec::new();
+    for slashing in &block.message.body.proposer_slashings {
+        slashings.push(Slashing {
+            slot,
+            epoch: slot / 32,
+            kind: SlashingKind::Proposer,
+            validator_index: slashing.slashed_index(),
+            proposer_index,
+            whistleblower_index: proposer_index,
+            evidence: serde_json::to_string(slashing)?,
+        });
+    }
+    for slashing in &block.message.body.attester_slashings {
+        let evidence = serde_json::to_string(slashing)?;
+        for validator_index in slashing.slashed_indices() {
+            slashings.push(Slashing {
+                slot,
+                epoch: slot / 32,
+                kind: SlashingKind::Attester,
+                validator_index,
+                proposer_index,
+                whistleblower_index: proposer_index,
+                evidence: evidence.clone(),
+            });
+        }
+    }
+    Ok(slashings)
+}
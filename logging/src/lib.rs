@@ -0,0 +1,112 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Result};
+use envconfig::Envconfig;
+use opentelemetry::{
+    global,
+    sdk::{trace, Resource},
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(anyhow!("Invalid log format {s}, expected text or json")),
+        }
+    }
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Envconfig)]
+pub struct LogConfig {
+    #[envconfig(from = "LOG_FORMAT", default = "text")]
+    pub format: LogFormat,
+    #[envconfig(from = "OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+}
+
+pub struct LogGuard {
+    otlp: bool,
+}
+
+// Spans still buffered by the batch exporter are lost unless the provider is shut down before exit
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        if self.otlp {
+            global::shutdown_tracer_provider();
+        }
+    }
+}
+
+// RUST_LOG keeps working as before, and records from crates still using the log macros are forwarded as events
+pub fn init(config: &LogConfig, service_name: &'static str) -> Result<LogGuard> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let fmt = match config.format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .boxed(),
+    };
+    let otlp = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            // Export failures would otherwise be printed to stderr outside of the configured log format
+            global::set_error_handler(|err| tracing::warn!("Failed to export traces: {err}"))?;
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+                .with_trace_config(
+                    trace::config().with_resource(Resource::new(vec![KeyValue::new("service.name", service_name)])),
+                )
+                .install_batch(opentelemetry::runtime::Tokio)?;
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+    let enabled = otlp.is_some();
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt)
+        .with(otlp)
+        .try_init()?;
+    Ok(LogGuard { otlp: enabled })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!(
+            LogFormat::Text.to_string().parse::<LogFormat>().unwrap(),
+            LogFormat::Text
+        );
+        assert!("pretty".parse::<LogFormat>().is_err());
+    }
+}
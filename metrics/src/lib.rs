@@ -0,0 +1,330 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+pub const DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+pub const SIZE_BUCKETS: &[f64] = &[1.0, 10.0, 100.0, 1000.0, 10000.0, 100000.0];
+
+pub static INDEXER_HEAD_SLOT: Gauge = Gauge::new("indexer_head_slot", "Slot of the beacon node's head block", &[]);
+pub static INDEXER_INDEXED_SLOT: Gauge = Gauge::new("indexer_indexed_slot", "Last slot written by the indexer", &[]);
+pub static INDEXER_LAG_SLOTS: Gauge = Gauge::new(
+    "indexer_lag_slots",
+    "Slots between the beacon node's head and the last indexed slot",
+    &[],
+);
+pub static INDEXER_BATCH_SIZE: Histogram = Histogram::new(
+    "indexer_batch_size",
+    "Rows written per indexer batch",
+    &["kind"],
+    SIZE_BUCKETS,
+);
+pub static BEACON_REQUESTS: Counter = Counter::new(
+    "beacon_requests_total",
+    "Requests sent to the beacon node",
+    &["endpoint"],
+);
+pub static BEACON_REQUEST_ERRORS: Counter = Counter::new(
+    "beacon_request_errors_total",
+    "Beacon node requests that failed or returned an error status",
+    &["endpoint"],
+);
+pub static BEACON_REQUEST_DURATION: Histogram = Histogram::new(
+    "beacon_request_duration_seconds",
+    "Time until the beacon node responded",
+    &["endpoint"],
+    DURATION_BUCKETS,
+);
+pub static DB_QUERY_DURATION: Histogram = Histogram::new(
+    "db_query_duration_seconds",
+    "Time spent in each repository method",
+    &["repository", "method"],
+    DURATION_BUCKETS,
+);
+pub static GRAPHQL_REQUEST_DURATION: Histogram = Histogram::new(
+    "graphql_request_duration_seconds",
+    "Time spent handling GraphQL requests",
+    &[],
+    DURATION_BUCKETS,
+);
+pub static GRAPHQL_REQUEST_ERRORS: Counter = Counter::new(
+    "graphql_request_errors_total",
+    "GraphQL requests that returned errors",
+    &[],
+);
+
+static METRICS: &[&(dyn Metric + Sync)] = &[
+    &INDEXER_HEAD_SLOT,
+    &INDEXER_INDEXED_SLOT,
+    &INDEXER_LAG_SLOTS,
+    &INDEXER_BATCH_SIZE,
+    &BEACON_REQUESTS,
+    &BEACON_REQUEST_ERRORS,
+    &BEACON_REQUEST_DURATION,
+    &DB_QUERY_DURATION,
+    &GRAPHQL_REQUEST_DURATION,
+    &GRAPHQL_REQUEST_ERRORS,
+];
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+// Metrics that were never recorded are left out, so each binary only exposes what it actually does
+pub fn render() -> String {
+    let mut output = String::new();
+    for metric in METRICS {
+        metric.render(&mut output);
+    }
+    output
+}
+
+trait Metric {
+    fn render(&self, output: &mut String);
+}
+
+type Series<T> = Mutex<BTreeMap<Vec<String>, T>>;
+
+fn label_values(labels: &[&str], values: &[&str]) -> Vec<String> {
+    assert_eq!(labels.len(), values.len(), "Wrong number of label values");
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn format_labels(labels: &[&str], values: &[String], extra: Option<(&str, String)>) -> String {
+    let mut pairs = labels
+        .iter()
+        .zip(values)
+        .map(|(label, value)| (label.to_string(), value.clone()))
+        .collect::<Vec<_>>();
+    if let Some((label, value)) = extra {
+        pairs.push((label.to_string(), value));
+    }
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let pairs = pairs
+        .into_iter()
+        .map(|(label, value)| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("{label}=\"{value}\"")
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", pairs.join(","))
+}
+
+fn render_header(output: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+pub struct Counter {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Series<u64>,
+}
+
+impl Counter {
+    pub const fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn inc(&self, values: &[&str]) {
+        let key = label_values(self.labels, values);
+        *self.values.lock().unwrap().entry(key).or_default() += 1;
+    }
+}
+
+impl Metric for Counter {
+    fn render(&self, output: &mut String) {
+        let values = self.values.lock().unwrap();
+        if values.is_empty() {
+            return;
+        }
+        render_header(output, self.name, self.help, "counter");
+        for (key, value) in values.iter() {
+            let _ = writeln!(output, "{}{} {value}", self.name, format_labels(self.labels, key, None));
+        }
+    }
+}
+
+pub struct Gauge {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Series<f64>,
+}
+
+impl Gauge {
+    pub const fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn set(&self, values: &[&str], value: f64) {
+        let key = label_values(self.labels, values);
+        self.values.lock().unwrap().insert(key, value);
+    }
+}
+
+impl Metric for Gauge {
+    fn render(&self, output: &mut String) {
+        let values = self.values.lock().unwrap();
+        if values.is_empty() {
+            return;
+        }
+        render_header(output, self.name, self.help, "gauge");
+        for (key, value) in values.iter() {
+            let _ = writeln!(output, "{}{} {value}", self.name, format_labels(self.labels, key, None));
+        }
+    }
+}
+
+#[derive(Default)]
+struct HistogramValue {
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+pub struct Histogram {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    buckets: &'static [f64],
+    values: Series<HistogramValue>,
+}
+
+impl Histogram {
+    pub const fn new(
+        name: &'static str,
+        help: &'static str,
+        labels: &'static [&'static str],
+        buckets: &'static [f64],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            buckets,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn observe(&self, values: &[&str], value: f64) {
+        let key = label_values(self.labels, values);
+        let mut series = self.values.lock().unwrap();
+        let entry = series.entry(key).or_insert_with(|| HistogramValue {
+            buckets: vec![0; self.buckets.len()],
+            ..Default::default()
+        });
+        if let Some(bucket) = self.buckets.iter().position(|bound| value <= *bound) {
+            entry.buckets[bucket] += 1;
+        }
+        entry.sum += value;
+        entry.count += 1;
+    }
+
+    pub fn observe_duration(&self, values: &[&str], duration: Duration) {
+        self.observe(values, duration.as_secs_f64());
+    }
+
+    pub fn start_timer(&'static self, values: &[&str]) -> HistogramTimer {
+        HistogramTimer {
+            histogram: self,
+            values: label_values(self.labels, values),
+            start: Instant::now(),
+        }
+    }
+
+    pub async fn time<F: Future>(&self, values: &[&str], future: F) -> F::Output {
+        let start = Instant::now();
+        let output = future.await;
+        self.observe_duration(values, start.elapsed());
+        output
+    }
+}
+
+pub struct HistogramTimer {
+    histogram: &'static Histogram,
+    values: Vec<String>,
+    start: Instant,
+}
+
+// Observing on drop also records calls that return early through `?`
+impl Drop for HistogramTimer {
+    fn drop(&mut self) {
+        let values = self.values.iter().map(String::as_str).collect::<Vec<_>>();
+        self.histogram.observe_duration(&values, self.start.elapsed());
+    }
+}
+
+impl Metric for Histogram {
+    fn render(&self, output: &mut String) {
+        let values = self.values.lock().unwrap();
+        if values.is_empty() {
+            return;
+        }
+        render_header(output, self.name, self.help, "histogram");
+        for (key, value) in values.iter() {
+            let mut cumulative = 0;
+            for (bound, count) in self.buckets.iter().zip(&value.buckets) {
+                cumulative += count;
+                let labels = format_labels(self.labels, key, Some(("le", bound.to_string())));
+                let _ = writeln!(output, "{}_bucket{labels} {cumulative}", self.name);
+            }
+            let labels = format_labels(self.labels, key, Some(("le", "+Inf".to_string())));
+            let _ = writeln!(output, "{}_bucket{labels} {}", self.name, value.count);
+            let labels = format_labels(self.labels, key, None);
+            let _ = writeln!(output, "{}_sum{labels} {}", self.name, value.sum);
+            let _ = writeln!(output, "{}_count{labels} {}", self.name, value.count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_renders_cumulative_buckets() {
+        let histogram = Histogram::new("test_duration_seconds", "Test", &["method"], &[0.1, 1.0]);
+        histogram.observe(&["get"], 0.05);
+        histogram.observe(&["get"], 0.5);
+        histogram.observe(&["get"], 5.0);
+        let mut output = String::new();
+        histogram.render(&mut output);
+        assert_eq!(
+            output,
+            "# HELP test_duration_seconds Test
+# TYPE test_duration_seconds histogram
+test_duration_seconds_bucket{method=\"get\",le=\"0.1\"} 1
+test_duration_seconds_bucket{method=\"get\",le=\"1\"} 2
+test_duration_seconds_bucket{method=\"get\",le=\"+Inf\"} 3
+test_duration_seconds_sum{method=\"get\"} 5.55
+test_duration_seconds_count{method=\"get\"} 3
+"
+        );
+    }
+
+    #[test]
+    fn counter_escapes_label_values() {
+        let counter = Counter::new("test_total", "Test", &["endpoint"]);
+        let mut output = String::new();
+        counter.render(&mut output);
+        assert!(output.is_empty());
+        counter.inc(&["a\"b"]);
+        counter.render(&mut output);
+        assert!(output.ends_with("test_total{endpoint=\"a\\\"b\"} 1\n"));
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use lru::LruCache;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{model::ChainEvent, CacheRepository};
+
+pub const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+pub const DEFAULT_HEAD_TTL: Duration = Duration::from_secs(12);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Epoch,
+    ParticipationRate,
+    BlockProduction,
+    BlobThroughput,
+}
+
+impl Display for CacheKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheKind::Epoch => write!(f, "epoch"),
+            CacheKind::ParticipationRate => write!(f, "participation_rate"),
+            CacheKind::BlockProduction => write!(f, "block_production"),
+            CacheKind::BlobThroughput => write!(f, "blob_throughput"),
+        }
+    }
+}
+
+struct CacheEntry {
+    epoch: u64,
+    value: String,
+    // Head-dependent entries expire, finalized ones stay until evicted or invalidated by a reorg
+    expires_at: Option<Instant>,
+}
+
+struct CacheState {
+    entries: LruCache<String, CacheEntry>,
+    finalized_epoch: Option<u64>,
+    completed_epoch: Option<u64>,
+}
+
+impl CacheState {
+    // The indexer backfills from genesis, so an epoch is only final once it is both finalized and fully indexed
+    fn is_final(&self, epoch: u64) -> bool {
+        self.finalized_epoch
+            .zip(self.completed_epoch)
+            .is_some_and(|(finalized, completed)| epoch <= finalized.min(completed))
+    }
+
+    fn invalidate(&mut self, invalidate: impl Fn(&CacheEntry) -> bool) {
+        let keys = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| invalidate(entry))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.entries.pop(&key);
+        }
+    }
+}
+
+pub struct ResponseCache {
+    state: Mutex<CacheState>,
+    shared: Option<Arc<dyn CacheRepository>>,
+    head_ttl: Duration,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY, DEFAULT_HEAD_TTL)
+    }
+}
+
+impl ResponseCache {
+    pub fn new(capacity: usize, head_ttl: Duration) -> Self {
+        Self {
+            state: Mutex::new(CacheState {
+                entries: LruCache::new(capacity),
+                finalized_epoch: None,
+                completed_epoch: None,
+            }),
+            shared: None,
+            head_ttl,
+        }
+    }
+
+    // Only finalized results are written to the shared backend, so anything found there is safe to reuse
+    pub fn with_shared(mut self, shared: Arc<dyn CacheRepository>) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+
+    #[tracing::instrument(skip(self, fetch), fields(source))]
+    pub async fn get_or_fetch<T, F>(&self, kind: CacheKind, epoch: u64, fetch: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T>>,
+    {
+        let key = format!("{kind}:{epoch}");
+        let span = tracing::Span::current();
+        if let Some(value) = self.get_local(&key) {
+            span.record("source", "local");
+            return Ok(serde_json::from_str(&value)?);
+        }
+        if let Some(shared) = &self.shared {
+            match shared.get_cache_entry(&key).await {
+                Ok(Some(value)) => {
+                    span.record("source", "shared");
+                    let result = serde_json::from_str(&value)?;
+                    self.insert_local(key, epoch, value, None);
+                    return Ok(result);
+                }
+                Ok(None) => {}
+                Err(err) => tracing::warn!("Failed to read shared cache entry {key}: {err}"),
+            }
+        }
+
+        span.record("source", "fetch");
+        let result = fetch.await?;
+        let value = serde_json::to_string(&result)?;
+        let is_final = self.state.lock().unwrap().is_final(epoch);
+        if is_final {
+            if let Some(shared) = &self.shared {
+                if let Err(err) = shared.set_cache_entry(&key, epoch, &value).await {
+                    tracing::warn!("Failed to write shared cache entry {key}: {err}");
+                }
+            }
+            self.insert_local(key, epoch, value, None);
+        } else {
+            self.insert_local(key, epoch, value, Some(Instant::now() + self.head_ttl));
+        }
+        Ok(result)
+    }
+
+    pub async fn apply_chain_event(&self, event: &ChainEvent) -> Result<()> {
+        match event {
+            // Attestations for an epoch keep landing during the next one, so a new head can change both
+            ChainEvent::Head(slot) => {
+                let epoch = slot.epoch.saturating_sub(1);
+                self.state
+                    .lock()
+                    .unwrap()
+                    .invalidate(|entry| entry.expires_at.is_some() && entry.epoch >= epoch);
+            }
+            ChainEvent::Epoch { index } => {
+                let mut state = self.state.lock().unwrap();
+                state.completed_epoch = state.completed_epoch.max(Some(*index));
+                state.invalidate(|entry| entry.expires_at.is_some() && entry.epoch <= *index);
+            }
+            ChainEvent::FinalizedCheckpoint(checkpoint) => {
+                let mut state = self.state.lock().unwrap();
+                state.finalized_epoch = Some(checkpoint.epoch);
+                state.invalidate(|entry| entry.expires_at.is_some() && entry.epoch <= checkpoint.epoch);
+            }
+            ChainEvent::Reorg { slot } => {
+                let epoch = slot / 32;
+                self.state.lock().unwrap().invalidate(|entry| entry.epoch >= epoch);
+                if let Some(shared) = &self.shared {
+                    shared.delete_cache_entries_from_epoch(epoch).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_local(&self, key: &str) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state.entries.get(key)?;
+        let value = entry.value.clone();
+        if entry.expires_at.is_some_and(|expires_at| expires_at <= Instant::now()) {
+            state.entries.pop(key);
+            return None;
+        }
+        Some(value)
+    }
+
+    fn insert_local(&self, key: String, epoch: u64, value: String, expires_at: Option<Instant>) {
+        self.state.lock().unwrap().entries.put(
+            key,
+            CacheEntry {
+                epoch,
+                value,
+                expires_at,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Checkpoint;
+
+    #[test]
+    fn reorg_evicts_non_final_epochs() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            let cache = ResponseCache::new(16, Duration::from_secs(3600));
+            cache.apply_chain_event(&ChainEvent::Epoch { index: 2 }).await.unwrap();
+            cache
+                .apply_chain_event(&ChainEvent::FinalizedCheckpoint(Checkpoint {
+                    epoch: 2,
+                    root: "0x00".to_string(),
+                }))
+                .await
+                .unwrap();
+            for epoch in [1, 4] {
+                let value: u64 = cache
+                    .get_or_fetch(CacheKind::Epoch, epoch, async { Ok(epoch) })
+                    .await
+                    .unwrap();
+                assert_eq!(value, epoch);
+            }
+
+            cache
+                .apply_chain_event(&ChainEvent::Reorg { slot: 4 * 32 + 5 })
+                .await
+                .unwrap();
+            let final_value: u64 = cache.get_or_fetch(CacheKind::Epoch, 1, async { Ok(10) }).await.unwrap();
+            assert_eq!(final_value, 1);
+            let reorged_value: u64 = cache.get_or_fetch(CacheKind::Epoch, 4, async { Ok(40) }).await.unwrap();
+            assert_eq!(reorged_value, 40);
+        });
+    }
+}
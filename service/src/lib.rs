@@ -1,26 +1,47 @@
+pub mod cache;
 pub mod model;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use model::{AttestationData, Committee, Epoch, Proposer, Validator, ValidatorDataInput};
+use cache::{CacheKind, ResponseCache};
+use futures_util::stream::BoxStream;
+use model::{
+    execution_withdrawal_credentials, ApiKey, AttestationData, BlobSidecar, BlobThroughput, BlockHeader,
+    BlockProduction, BlsToExecutionChange, ChainEvent, Committee, Deposit, DutyKind, DutyOutcome, Epoch,
+    ExecutionPayload, FeeRecipient, GroupMember, MevIncome, Page, PageRequest, ProposalOutcome, Proposer, RelayFailure,
+    RelayPayload, RelayUsage, Slashing, SlashingStatus, Slot, SlotFilter, SlotQuery, SlotStatus, SortDirection,
+    Validator, ValidatorDataInput, ValidatorFilter, ValidatorGroup, ValidatorHistory, ValidatorQuery,
+    ValidatorSortField, VoluntaryExit, Withdrawal,
+};
 
 #[async_trait]
 pub trait EpochRepository: Sync + Send {
     async fn get_epoch(&self, index: u64) -> Result<Option<Epoch>>;
+    async fn get_epochs(&self, indices: &[u64]) -> Result<Vec<Epoch>>;
     async fn create_epoch(&self, epoch_index: u64, active_validators: u64, total_validators: u64) -> Result<()>;
     async fn current_epoch(&self) -> Result<u64>;
+    async fn stream_epochs(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Epoch>>>;
 }
 
 #[async_trait]
 pub trait ValidatorRepository: Sync + Send {
     async fn get_validator(&self, index: u64) -> Result<Option<Validator>>;
+    async fn get_validators(&self, indices: &[u64]) -> Result<Vec<Validator>>;
     async fn get_active_validators(&self, epoch: u64) -> Result<Vec<Validator>>;
     async fn active_validator_count(&self, epoch: u64) -> Result<u64>;
     async fn total_validator_count(&self, epoch: u64) -> Result<u64>;
     async fn create_or_update_validator(&self, validator: &ValidatorDataInput) -> Result<()>;
     async fn create_or_update_validator_batch(&self, validators: &[ValidatorDataInput]) -> Result<()>;
+    async fn get_validator_by_pubkey(&self, pubkey: &str) -> Result<Option<Validator>>;
+    async fn get_validator_page_by_pubkey_prefix(&self, prefix: &str, page: &PageRequest) -> Result<Page<Validator>>;
+    async fn get_validator_page_by_withdrawal_credentials(
+        &self,
+        withdrawal_credentials: &[String],
+        page: &PageRequest,
+    ) -> Result<Page<Validator>>;
+    async fn get_validator_page(&self, query: &ValidatorQuery, page: &PageRequest) -> Result<Page<Validator>>;
 }
 
 #[async_trait]
@@ -31,6 +52,19 @@ pub trait AttestationRepository: Sync + Send {
     async fn get_attestation_for_slot_and_validator(&self, slot: u64, validator: u64) -> Result<Option<bool>>;
     async fn get_attestations(&self, epoch_validators: &[(u64, u64)]) -> Result<Vec<Option<bool>>>;
     async fn attestation_count_for_slot(&self, slot: u64) -> Result<u64>;
+    async fn get_attestation(&self, epoch: u64, validator: u64) -> Result<Option<AttestationData>>;
+    async fn get_attestations_for_validator(&self, validator: u64, limit: u64) -> Result<Vec<AttestationData>>;
+    async fn get_attestation_data(&self, epoch_validators: &[(u64, u64)]) -> Result<Vec<AttestationData>>;
+    async fn stream_attestations(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<AttestationData>>>;
+    async fn stream_validator_history(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<ValidatorHistory>>>;
 }
 
 #[async_trait]
@@ -40,6 +74,8 @@ pub trait CommitteeRepository: Sync + Send {
     async fn get_committee(&self, slot: u64, index: u8) -> Result<Option<Committee>>;
     async fn get_committees(&self, inputs: &[(u64, u8)]) -> Result<Vec<Committee>>;
     async fn get_committees_for_slot(&self, slot: u64) -> Result<Vec<Committee>>;
+    async fn get_committee_page_for_slot(&self, slot: u64, page: &PageRequest) -> Result<Page<Committee>>;
+    async fn get_committees_for_epoch(&self, epoch: u64) -> Result<Vec<Committee>>;
 }
 
 #[async_trait]
@@ -50,34 +86,391 @@ pub trait ProposerRepository: Sync + Send {
     async fn get_proposers_for_epoch(&self, epoch: u64) -> Result<Vec<u64>>;
 }
 
+#[async_trait]
+pub trait ProposerDutyRepository: Sync + Send {
+    async fn create_proposer_duties(&self, duties: &[Proposer]) -> Result<()>;
+    async fn get_proposer_duties_for_epoch(&self, epoch: u64) -> Result<Vec<Proposer>>;
+    async fn get_proposal_outcome(&self, slot: u64) -> Result<Option<ProposalOutcome>>;
+    async fn get_proposal_outcomes_for_validators(&self, validators: &[u64]) -> Result<Vec<ProposalOutcome>>;
+    async fn get_recent_proposal_outcomes_for_validators(
+        &self,
+        validators: &[u64],
+        limit: u64,
+    ) -> Result<Vec<ProposalOutcome>>;
+    async fn get_proposal_outcome_page(
+        &self,
+        validators: &[u64],
+        epoch: Option<u64>,
+        page: &PageRequest,
+    ) -> Result<Page<ProposalOutcome>>;
+}
+
+#[async_trait]
+pub trait SlotRepository: Sync + Send {
+    async fn create_slot(&self, slot: &Slot) -> Result<()>;
+    async fn get_slot(&self, slot: u64) -> Result<Option<Slot>>;
+    async fn get_last_slot(&self) -> Result<Option<u64>>;
+    async fn get_slots_for_epoch(&self, epoch: u64) -> Result<Vec<Slot>>;
+    async fn get_slot_page(&self, query: &SlotQuery, page: &PageRequest) -> Result<Page<Slot>>;
+    async fn stream_slots(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Slot>>>;
+}
+
+#[async_trait]
+pub trait SlashingRepository: Sync + Send {
+    async fn create_slashings(&self, slashings: &[Slashing]) -> Result<()>;
+    async fn get_slashing_page(&self, page: &PageRequest) -> Result<Page<Slashing>>;
+    async fn get_slashings_for_validators(&self, validators: &[u64]) -> Result<Vec<Slashing>>;
+}
+
+#[async_trait]
+pub trait VoluntaryExitRepository: Sync + Send {
+    async fn create_voluntary_exits(&self, exits: &[VoluntaryExit]) -> Result<()>;
+    async fn get_voluntary_exit(&self, validator: u64) -> Result<Option<VoluntaryExit>>;
+    async fn get_voluntary_exits(&self, validators: &[u64]) -> Result<Vec<VoluntaryExit>>;
+    async fn get_voluntary_exit_page(&self, included: bool, page: &PageRequest) -> Result<Page<VoluntaryExit>>;
+    async fn expire_voluntary_exits(&self) -> Result<u64>;
+}
+
+#[async_trait]
+pub trait BlsToExecutionChangeRepository: Sync + Send {
+    async fn create_bls_to_execution_changes(&self, changes: &[BlsToExecutionChange]) -> Result<()>;
+    async fn get_bls_to_execution_change(&self, validator: u64) -> Result<Option<BlsToExecutionChange>>;
+    async fn get_bls_to_execution_change_page(
+        &self,
+        included: bool,
+        page: &PageRequest,
+    ) -> Result<Page<BlsToExecutionChange>>;
+}
+
+#[async_trait]
+pub trait DepositRepository: Sync + Send {
+    async fn create_deposits(&self, deposits: &[Deposit]) -> Result<()>;
+    async fn get_recent_deposits_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Deposit>>;
+    async fn get_deposit_page_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Deposit>>;
+    async fn get_deposit_page_for_withdrawal_credentials(
+        &self,
+        withdrawal_credentials: &[String],
+        page: &PageRequest,
+    ) -> Result<Page<Deposit>>;
+}
+
+#[async_trait]
+pub trait WithdrawalRepository: Sync + Send {
+    async fn create_withdrawals(&self, withdrawals: &[Withdrawal]) -> Result<()>;
+    async fn get_withdrawals_for_validator(&self, validator: u64) -> Result<Vec<Withdrawal>>;
+    async fn get_recent_withdrawals_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Withdrawal>>;
+    async fn get_withdrawal_page(
+        &self,
+        validator: Option<u64>,
+        address: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<Page<Withdrawal>>;
+    async fn withdrawal_total_for_validators(&self, validators: &[u64]) -> Result<u64>;
+    async fn withdrawal_total_for_address(&self, address: &str) -> Result<u64>;
+}
+
+#[async_trait]
+pub trait ExecutionPayloadRepository: Sync + Send {
+    async fn create_execution_payload(&self, payload: &ExecutionPayload) -> Result<()>;
+    async fn get_execution_payload(&self, slot: u64) -> Result<Option<ExecutionPayload>>;
+    async fn get_execution_payload_by_block_hash(&self, block_hash: &str) -> Result<Option<ExecutionPayload>>;
+    async fn get_execution_payload_by_block_number(&self, block_number: u64) -> Result<Option<ExecutionPayload>>;
+    async fn get_execution_payload_page_for_proposer(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<ExecutionPayload>>;
+    async fn get_fee_recipient_page_for_proposer(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<FeeRecipient>>;
+}
+
+#[async_trait]
+pub trait RelayPayloadRepository: Sync + Send {
+    async fn create_relay_payloads(&self, payloads: &[RelayPayload]) -> Result<()>;
+    async fn get_relay_payload_page_for_validator(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<RelayPayload>>;
+    async fn get_mev_income_for_validators(&self, validators: &[u64]) -> Result<MevIncome>;
+    async fn get_relay_usage_page_for_validators(
+        &self,
+        validators: &[u64],
+        page: &PageRequest,
+    ) -> Result<Page<RelayUsage>>;
+    async fn record_relay_failures(&self, relay: &str, slots: &[u64]) -> Result<()>;
+    async fn get_relay_failures(&self, max_attempts: u32, limit: u64) -> Result<Vec<RelayFailure>>;
+    async fn delete_relay_failure(&self, relay: &str, slot: u64) -> Result<()>;
+}
+
+#[async_trait]
+pub trait BlobSidecarRepository: Sync + Send {
+    async fn create_blob_sidecars(&self, sidecars: &[BlobSidecar]) -> Result<()>;
+    async fn get_blob_sidecars(&self, slot: u64) -> Result<Vec<BlobSidecar>>;
+    async fn get_blob_sidecar_page(&self, slot: u64, page: &PageRequest) -> Result<Page<BlobSidecar>>;
+    async fn get_blob_throughput_for_epoch(&self, epoch: u64) -> Result<BlobThroughput>;
+    async fn get_blob_throughput_for_proposers(&self, validators: &[u64]) -> Result<BlobThroughput>;
+}
+
+#[async_trait]
+pub trait ValidatorGroupRepository: Sync + Send {
+    async fn create_group(&self, name: &str, labels: &[String]) -> Result<bool>;
+    async fn delete_group(&self, name: &str) -> Result<bool>;
+    async fn set_group_labels(&self, name: &str, labels: &[String]) -> Result<bool>;
+    async fn add_group_members(&self, name: &str, members: &[GroupMember]) -> Result<()>;
+    async fn remove_group_members(&self, name: &str, members: &[GroupMember]) -> Result<()>;
+    async fn get_group(&self, name: &str) -> Result<Option<ValidatorGroup>>;
+    async fn get_group_page(&self, label: Option<&str>, page: &PageRequest) -> Result<Page<ValidatorGroup>>;
+    async fn get_group_validator_indices(&self, name: &str) -> Result<Vec<u64>>;
+    async fn get_group_validator_index_page(&self, name: &str, page: &PageRequest) -> Result<Page<u64>>;
+}
+
+#[async_trait]
+pub trait EventRepository: Sync + Send {
+    async fn publish_event(&self, event: &ChainEvent) -> Result<()>;
+}
+
+#[async_trait]
+pub trait BlockHeaderRepository: Sync + Send {
+    async fn create_block_header(&self, header: &BlockHeader) -> Result<()>;
+    async fn get_block_header(&self, slot: u64) -> Result<Option<BlockHeader>>;
+    async fn get_block_header_by_root(&self, root: &str) -> Result<Option<BlockHeader>>;
+    async fn get_block_header_by_state_root(&self, state_root: &str) -> Result<Option<BlockHeader>>;
+    async fn get_latest_block_header(&self) -> Result<Option<BlockHeader>>;
+    async fn get_block_header_before(&self, slot: u64) -> Result<Option<BlockHeader>>;
+    async fn delete_block(&self, slot: u64) -> Result<()>;
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: Sync + Send {
+    async fn create_api_key(&self, key: &ApiKey, key_hash: &str) -> Result<bool>;
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>>;
+    async fn get_api_keys(&self) -> Result<Vec<ApiKey>>;
+    async fn revoke_api_key(&self, name: &str) -> Result<bool>;
+    async fn record_api_key_usage(&self, usage: &[(String, u64)]) -> Result<()>;
+}
+
+#[async_trait]
+pub trait CacheRepository: Sync + Send {
+    async fn get_cache_entry(&self, key: &str) -> Result<Option<String>>;
+    async fn set_cache_entry(&self, key: &str, epoch: u64, value: &str) -> Result<()>;
+    async fn delete_cache_entries_from_epoch(&self, epoch: u64) -> Result<()>;
+}
+
 #[async_trait]
 pub trait Service: Sync + Send {
     async fn get_participation_rate_for_epoch(&self, epoch: u64) -> Result<f64>;
     async fn get_participation_rate_for_validator(&self, validator: u64) -> Result<f64>;
+    async fn get_participation_rates_for_validators(&self, validators: &[u64]) -> Result<Vec<(u64, f64)>>;
 
     async fn get_validator(&self, index: u64) -> Result<Option<Validator>>;
+    async fn get_validators(&self, indices: &[u64]) -> Result<Vec<Validator>>;
     async fn get_active_validators(&self, epoch: u64) -> Result<Vec<Validator>>;
     async fn active_validator_count(&self, epoch: u64) -> Result<u64>;
     async fn total_validator_count(&self, epoch: u64) -> Result<u64>;
     async fn create_or_update_validator(&self, validator: &ValidatorDataInput) -> Result<()>;
     async fn create_or_update_validator_batch(&self, validators: &[ValidatorDataInput]) -> Result<()>;
+    async fn get_validator_by_pubkey(&self, pubkey: &str) -> Result<Option<Validator>>;
+    async fn get_validators_by_pubkey_prefix(&self, prefix: &str, page: &PageRequest) -> Result<Page<Validator>>;
+    async fn get_validators_for_withdrawal_address(&self, address: &str, page: &PageRequest)
+        -> Result<Page<Validator>>;
 
     async fn get_epoch(&self, index: u64) -> Result<Option<Epoch>>;
     async fn create_epoch(&self, epoch_index: u64, active_validators: u64, total_validators: u64) -> Result<()>;
 
     async fn create_or_update_attestation(&self, attestation_data: AttestationData) -> Result<()>;
     async fn create_or_update_attestation_batch(&self, attestation_data: &[AttestationData]) -> Result<()>;
+    async fn get_attestation(&self, epoch: u64, validator: u64) -> Result<Option<AttestationData>>;
+    async fn get_attestations_for_validator(&self, validator: u64, limit: u64) -> Result<Vec<AttestationData>>;
 
     async fn create_or_update_committee(&self, committee: &Committee) -> Result<()>;
     async fn create_or_update_committee_batch(&self, committees: &[Committee]) -> Result<()>;
     async fn get_committee(&self, slot: u64, index: u8) -> Result<Option<Committee>>;
     async fn get_committees(&self, inputs: &[(u64, u8)]) -> Result<Vec<Committee>>;
+    async fn get_committees_for_slot(&self, slot: u64) -> Result<Vec<Committee>>;
+    async fn get_committee_page_for_slot(&self, slot: u64, page: &PageRequest) -> Result<Page<Committee>>;
 
     async fn create_proposer(&self, slot: u64, validator: u64) -> Result<()>;
     async fn create_proposers(&self, proposers: &[Proposer]) -> Result<()>;
     async fn get_proposer(&self, slot: u64) -> Result<Option<u64>>;
     async fn block_created(&self, slot: u64) -> Result<bool>;
-    async fn block_count_for_epoch(&self, epoch: u64) -> Result<u8>;
+    async fn block_count_for_epoch(&self, epoch: u64) -> Result<u64>;
+    async fn get_block_production_for_epoch(&self, epoch: u64) -> Result<BlockProduction>;
+
+    async fn create_proposer_duties(&self, duties: &[Proposer]) -> Result<()>;
+    async fn get_proposer_duties_for_epoch(&self, epoch: u64) -> Result<Vec<Proposer>>;
+    async fn get_proposal_outcome(&self, slot: u64) -> Result<Option<ProposalOutcome>>;
+    async fn get_proposal_outcomes_for_validator(&self, validator: u64) -> Result<Vec<ProposalOutcome>>;
+    async fn get_recent_proposal_outcomes_for_validators(
+        &self,
+        validators: &[u64],
+        limit: u64,
+    ) -> Result<Vec<ProposalOutcome>>;
+    async fn get_proposal_outcome_page_for_validator(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<ProposalOutcome>>;
+    async fn get_proposal_success_rate_for_validator(&self, validator: u64) -> Result<f64>;
+
+    async fn create_slot(&self, slot: &Slot) -> Result<()>;
+    async fn get_slot(&self, slot: u64) -> Result<Option<Slot>>;
+    async fn get_last_slot(&self) -> Result<Option<u64>>;
+    async fn get_slots_for_epoch(&self, epoch: u64) -> Result<Vec<Slot>>;
+    async fn missed_proposals_for_epoch(&self, epoch: u64, page: &PageRequest) -> Result<Page<Slot>>;
+    async fn missed_proposals_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Slot>>;
+
+    async fn create_slashings(&self, slashings: &[Slashing]) -> Result<()>;
+    async fn get_slashings(&self, page: &PageRequest) -> Result<Page<Slashing>>;
+    async fn get_slashing_status(&self, validator: u64) -> Result<Option<SlashingStatus>>;
+    async fn get_slashing_statuses(&self, validators: &[u64]) -> Result<Vec<SlashingStatus>>;
+
+    async fn create_voluntary_exits(&self, exits: &[VoluntaryExit]) -> Result<()>;
+    async fn get_voluntary_exit(&self, validator: u64) -> Result<Option<VoluntaryExit>>;
+    async fn get_voluntary_exits(&self, validators: &[u64]) -> Result<Vec<VoluntaryExit>>;
+    async fn get_pending_voluntary_exits(&self, page: &PageRequest) -> Result<Page<VoluntaryExit>>;
+    async fn get_processed_voluntary_exits(&self, page: &PageRequest) -> Result<Page<VoluntaryExit>>;
+    async fn expire_voluntary_exits(&self) -> Result<u64>;
+
+    async fn create_bls_to_execution_changes(&self, changes: &[BlsToExecutionChange]) -> Result<()>;
+    async fn get_bls_to_execution_change(&self, validator: u64) -> Result<Option<BlsToExecutionChange>>;
+    async fn get_pending_bls_to_execution_changes(&self, page: &PageRequest) -> Result<Page<BlsToExecutionChange>>;
+    async fn get_processed_bls_to_execution_changes(&self, page: &PageRequest) -> Result<Page<BlsToExecutionChange>>;
+
+    async fn create_deposits(&self, deposits: &[Deposit]) -> Result<()>;
+    async fn get_recent_deposits_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Deposit>>;
+    async fn get_deposit_page_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Deposit>>;
+    async fn get_deposits_for_address(&self, address: &str, page: &PageRequest) -> Result<Page<Deposit>>;
+
+    async fn create_withdrawals(&self, withdrawals: &[Withdrawal]) -> Result<()>;
+    async fn get_withdrawals_for_validator(&self, validator: u64) -> Result<Vec<Withdrawal>>;
+    async fn get_recent_withdrawals_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Withdrawal>>;
+    async fn get_withdrawal_page_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Withdrawal>>;
+    async fn get_withdrawals_for_address(&self, address: &str, page: &PageRequest) -> Result<Page<Withdrawal>>;
+    async fn withdrawal_total_for_validator(&self, validator: u64) -> Result<u64>;
+    async fn withdrawal_total_for_address(&self, address: &str) -> Result<u64>;
+
+    async fn create_execution_payload(&self, payload: &ExecutionPayload) -> Result<()>;
+    async fn get_execution_payload(&self, slot: u64) -> Result<Option<ExecutionPayload>>;
+    async fn get_execution_payload_by_block_hash(&self, block_hash: &str) -> Result<Option<ExecutionPayload>>;
+    async fn get_execution_payload_by_block_number(&self, block_number: u64) -> Result<Option<ExecutionPayload>>;
+    async fn get_execution_payloads_for_proposer(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<ExecutionPayload>>;
+    async fn get_fee_recipients_for_proposer(&self, validator: u64, page: &PageRequest) -> Result<Page<FeeRecipient>>;
+
+    async fn create_relay_payloads(&self, payloads: &[RelayPayload]) -> Result<()>;
+    async fn get_relay_payloads_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<RelayPayload>>;
+    async fn get_mev_income_for_validator(&self, validator: u64) -> Result<MevIncome>;
+    async fn get_relay_usage_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<RelayUsage>>;
+    async fn record_relay_failures(&self, relay: &str, slots: &[u64]) -> Result<()>;
+    async fn get_relay_failures(&self, max_attempts: u32, limit: u64) -> Result<Vec<RelayFailure>>;
+    async fn delete_relay_failure(&self, relay: &str, slot: u64) -> Result<()>;
+
+    async fn create_blob_sidecars(&self, sidecars: &[BlobSidecar]) -> Result<()>;
+    async fn get_blob_sidecars(&self, slot: u64) -> Result<Vec<BlobSidecar>>;
+    async fn get_blob_sidecar_page(&self, slot: u64, page: &PageRequest) -> Result<Page<BlobSidecar>>;
+    async fn get_blob_throughput_for_epoch(&self, epoch: u64) -> Result<BlobThroughput>;
+    async fn get_blob_throughput_for_proposer(&self, validator: u64) -> Result<BlobThroughput>;
+
+    async fn create_validator_group(&self, name: &str, labels: &[String]) -> Result<ValidatorGroup>;
+    async fn delete_validator_group(&self, name: &str) -> Result<bool>;
+    async fn set_validator_group_labels(&self, name: &str, labels: &[String]) -> Result<ValidatorGroup>;
+    async fn add_validator_group_members(&self, name: &str, members: &[GroupMember]) -> Result<ValidatorGroup>;
+    async fn remove_validator_group_members(&self, name: &str, members: &[GroupMember]) -> Result<ValidatorGroup>;
+    async fn get_validator_group(&self, name: &str) -> Result<Option<ValidatorGroup>>;
+    async fn get_validator_groups(&self, label: Option<&str>, page: &PageRequest) -> Result<Page<ValidatorGroup>>;
+    async fn get_validator_group_indices(&self, name: &str) -> Result<Vec<u64>>;
+    async fn get_validator_group_index_page(&self, name: &str, page: &PageRequest) -> Result<Page<u64>>;
+    async fn get_participation_rate_for_group(&self, name: &str, epoch: Option<u64>) -> Result<f64>;
+    async fn get_proposal_outcomes_for_group(
+        &self,
+        name: &str,
+        epoch: Option<u64>,
+        page: &PageRequest,
+    ) -> Result<Page<ProposalOutcome>>;
+    async fn get_proposal_success_rate_for_group(&self, name: &str, epoch: Option<u64>) -> Result<f64>;
+    async fn missed_proposals_for_group(&self, name: &str, page: &PageRequest) -> Result<Page<Slot>>;
+    async fn withdrawal_total_for_group(&self, name: &str) -> Result<u64>;
+    async fn get_mev_income_for_group(&self, name: &str) -> Result<MevIncome>;
+    async fn get_relay_usage_for_group(&self, name: &str, page: &PageRequest) -> Result<Page<RelayUsage>>;
+    async fn get_blob_throughput_for_group(&self, name: &str) -> Result<BlobThroughput>;
+
+    async fn get_validators_page(
+        &self,
+        filter: &ValidatorFilter,
+        sort: ValidatorSortField,
+        direction: SortDirection,
+        page: &PageRequest,
+    ) -> Result<Page<Validator>>;
+    async fn get_slots_page(
+        &self,
+        filter: &SlotFilter,
+        direction: SortDirection,
+        page: &PageRequest,
+    ) -> Result<Page<Slot>>;
+
+    async fn publish_event(&self, event: &ChainEvent) -> Result<()>;
+    async fn get_duty_outcomes_for_event(&self, event: &ChainEvent, validators: &[u64]) -> Result<Vec<DutyOutcome>>;
+
+    async fn get_epochs(&self, indices: &[u64]) -> Result<Vec<Epoch>>;
+    async fn get_attestation_data(&self, epoch_validators: &[(u64, u64)]) -> Result<Vec<AttestationData>>;
+    async fn get_committees_for_epoch(&self, epoch: u64) -> Result<Vec<Committee>>;
+
+    async fn create_block_header(&self, header: &BlockHeader) -> Result<()>;
+    async fn get_block_header(&self, slot: u64) -> Result<Option<BlockHeader>>;
+    async fn get_block_header_by_root(&self, root: &str) -> Result<Option<BlockHeader>>;
+    async fn get_block_header_by_state_root(&self, state_root: &str) -> Result<Option<BlockHeader>>;
+    async fn get_latest_block_header(&self) -> Result<Option<BlockHeader>>;
+    async fn get_block_header_before(&self, slot: u64) -> Result<Option<BlockHeader>>;
+    async fn delete_block(&self, slot: u64) -> Result<()>;
+
+    async fn stream_attestations(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<AttestationData>>>;
+    async fn stream_epochs(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Epoch>>>;
+    async fn stream_slots(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Slot>>>;
+    async fn stream_validator_history(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<ValidatorHistory>>>;
+
+    async fn create_api_key(&self, key: &ApiKey, key_hash: &str) -> Result<bool>;
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>>;
+    async fn get_api_keys(&self) -> Result<Vec<ApiKey>>;
+    async fn revoke_api_key(&self, name: &str) -> Result<bool>;
+    async fn record_api_key_usage(&self, usage: &[(String, u64)]) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct Repositories {
+    pub epoch_repository: Arc<dyn EpochRepository>,
+    pub validator_repository: Arc<dyn ValidatorRepository>,
+    pub attestation_repository: Arc<dyn AttestationRepository>,
+    pub committee_repository: Arc<dyn CommitteeRepository>,
+    pub proposer_repository: Arc<dyn ProposerRepository>,
+    pub proposer_duty_repository: Arc<dyn ProposerDutyRepository>,
+    pub slot_repository: Arc<dyn SlotRepository>,
+    pub slashing_repository: Arc<dyn SlashingRepository>,
+    pub voluntary_exit_repository: Arc<dyn VoluntaryExitRepository>,
+    pub bls_to_execution_change_repository: Arc<dyn BlsToExecutionChangeRepository>,
+    pub deposit_repository: Arc<dyn DepositRepository>,
+    pub withdrawal_repository: Arc<dyn WithdrawalRepository>,
+    pub execution_payload_repository: Arc<dyn ExecutionPayloadRepository>,
+    pub relay_payload_repository: Arc<dyn RelayPayloadRepository>,
+    pub blob_sidecar_repository: Arc<dyn BlobSidecarRepository>,
+    pub validator_group_repository: Arc<dyn ValidatorGroupRepository>,
+    pub event_repository: Arc<dyn EventRepository>,
+    pub block_header_repository: Arc<dyn BlockHeaderRepository>,
+    pub api_key_repository: Arc<dyn ApiKeyRepository>,
 }
 
 #[derive(Clone)]
@@ -87,59 +480,155 @@ pub struct ServiceImpl {
     attestation_repository: Arc<dyn AttestationRepository>,
     committee_repository: Arc<dyn CommitteeRepository>,
     proposer_repository: Arc<dyn ProposerRepository>,
+    proposer_duty_repository: Arc<dyn ProposerDutyRepository>,
+    slot_repository: Arc<dyn SlotRepository>,
+    slashing_repository: Arc<dyn SlashingRepository>,
+    voluntary_exit_repository: Arc<dyn VoluntaryExitRepository>,
+    bls_to_execution_change_repository: Arc<dyn BlsToExecutionChangeRepository>,
+    deposit_repository: Arc<dyn DepositRepository>,
+    withdrawal_repository: Arc<dyn WithdrawalRepository>,
+    execution_payload_repository: Arc<dyn ExecutionPayloadRepository>,
+    relay_payload_repository: Arc<dyn RelayPayloadRepository>,
+    blob_sidecar_repository: Arc<dyn BlobSidecarRepository>,
+    validator_group_repository: Arc<dyn ValidatorGroupRepository>,
+    event_repository: Arc<dyn EventRepository>,
+    block_header_repository: Arc<dyn BlockHeaderRepository>,
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    cache: Arc<ResponseCache>,
 }
 
 impl ServiceImpl {
-    pub fn new(
-        epoch_repository: Arc<dyn EpochRepository>,
-        validator_repository: Arc<dyn ValidatorRepository>,
-        attestation_repository: Arc<dyn AttestationRepository>,
-        committee_repository: Arc<dyn CommitteeRepository>,
-        proposer_repository: Arc<dyn ProposerRepository>,
-    ) -> Self {
+    pub fn new(repositories: Repositories) -> Self {
+        let Repositories {
+            epoch_repository,
+            validator_repository,
+            attestation_repository,
+            committee_repository,
+            proposer_repository,
+            proposer_duty_repository,
+            slot_repository,
+            slashing_repository,
+            voluntary_exit_repository,
+            bls_to_execution_change_repository,
+            deposit_repository,
+            withdrawal_repository,
+            execution_payload_repository,
+            relay_payload_repository,
+            blob_sidecar_repository,
+            validator_group_repository,
+            event_repository,
+            block_header_repository,
+            api_key_repository,
+        } = repositories;
         Self {
             epoch_repository,
             validator_repository,
             attestation_repository,
             committee_repository,
             proposer_repository,
+            proposer_duty_repository,
+            slot_repository,
+            slashing_repository,
+            voluntary_exit_repository,
+            bls_to_execution_change_repository,
+            deposit_repository,
+            withdrawal_repository,
+            execution_payload_repository,
+            relay_payload_repository,
+            blob_sidecar_repository,
+            validator_group_repository,
+            event_repository,
+            block_header_repository,
+            api_key_repository,
+            cache: Arc::new(ResponseCache::default()),
         }
     }
+
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Arc::new(cache);
+        self
+    }
+
+    pub async fn apply_chain_event(&self, event: &ChainEvent) -> Result<()> {
+        self.cache.apply_chain_event(event).await
+    }
 }
 
 #[async_trait]
 impl Service for ServiceImpl {
     async fn get_participation_rate_for_epoch(&self, epoch: u64) -> Result<f64> {
-        let active_validator_count = self.validator_repository.active_validator_count(epoch).await?;
-        let epoch = self
-            .epoch_repository
-            .get_epoch(epoch)
-            .await?
-            .ok_or(anyhow!("Epoch not found"))?;
-        let attestation_count = epoch.attestations;
-        Ok(attestation_count as f64 / active_validator_count as f64)
+        self.cache
+            .get_or_fetch(CacheKind::ParticipationRate, epoch, async {
+                let active_validator_count = self.validator_repository.active_validator_count(epoch).await?;
+                let epoch = self
+                    .epoch_repository
+                    .get_epoch(epoch)
+                    .await?
+                    .ok_or(anyhow!("Epoch not found"))?;
+                let attestation_count = epoch.attestations;
+                Ok(attestation_count as f64 / active_validator_count as f64)
+            })
+            .await
     }
 
     async fn get_participation_rate_for_validator(&self, validator: u64) -> Result<f64> {
-        let current_epoch = self.epoch_repository.current_epoch().await?;
-        let validator = self
-            .validator_repository
-            .get_validator(validator)
+        self.get_participation_rates_for_validators(&[validator])
             .await?
-            .ok_or(anyhow!("Validator not found"))?;
-        let active_epoch_count = if current_epoch > validator.exit_epoch {
-            validator.exit_epoch - validator.activation_epoch
-        } else {
-            current_epoch - validator.activation_epoch
-        };
-        let attestation_count = validator.attestations;
-        Ok(attestation_count as f64 / active_epoch_count as f64)
+            .pop()
+            .map(|(_, rate)| rate)
+            .ok_or(anyhow!("Validator not found"))
+    }
+
+    async fn get_participation_rates_for_validators(&self, validators: &[u64]) -> Result<Vec<(u64, f64)>> {
+        let current_epoch = self.epoch_repository.current_epoch().await?;
+        let validators = self.validator_repository.get_validators(validators).await?;
+        Ok(validators
+            .iter()
+            .map(|validator| {
+                let attestation_count = validator.attestations;
+                (
+                    validator.index,
+                    attestation_count as f64 / active_epoch_count(validator, current_epoch) as f64,
+                )
+            })
+            .collect())
     }
 
     async fn get_validator(&self, index: u64) -> Result<Option<Validator>> {
         self.validator_repository.get_validator(index).await
     }
 
+    async fn get_validators(&self, indices: &[u64]) -> Result<Vec<Validator>> {
+        self.validator_repository.get_validators(indices).await
+    }
+
+    async fn get_validator_by_pubkey(&self, pubkey: &str) -> Result<Option<Validator>> {
+        self.validator_repository
+            .get_validator_by_pubkey(&pubkey.trim().to_lowercase())
+            .await
+    }
+
+    async fn get_validators_by_pubkey_prefix(&self, prefix: &str, page: &PageRequest) -> Result<Page<Validator>> {
+        let prefix = prefix.trim().to_lowercase();
+        let hex = prefix.strip_prefix("0x").unwrap_or(&prefix);
+        if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Invalid pubkey prefix"));
+        }
+        self.validator_repository
+            .get_validator_page_by_pubkey_prefix(&format!("0x{hex}"), page)
+            .await
+    }
+
+    async fn get_validators_for_withdrawal_address(
+        &self,
+        address: &str,
+        page: &PageRequest,
+    ) -> Result<Page<Validator>> {
+        self.validator_repository
+            .get_validator_page_by_withdrawal_credentials(&execution_withdrawal_credentials(address), page)
+            .await
+    }
+
     async fn get_active_validators(&self, epoch: u64) -> Result<Vec<Validator>> {
         self.validator_repository.get_active_validators(epoch).await
     }
@@ -163,7 +652,9 @@ impl Service for ServiceImpl {
     }
 
     async fn get_epoch(&self, index: u64) -> Result<Option<Epoch>> {
-        self.epoch_repository.get_epoch(index).await
+        self.cache
+            .get_or_fetch(CacheKind::Epoch, index, self.epoch_repository.get_epoch(index))
+            .await
     }
 
     async fn create_epoch(&self, epoch_index: u64, active_validators: u64, total_validators: u64) -> Result<()> {
@@ -198,6 +689,16 @@ impl Service for ServiceImpl {
         self.attestation_repository.create_attestation_batch(&batch).await
     }
 
+    async fn get_attestation(&self, epoch: u64, validator: u64) -> Result<Option<AttestationData>> {
+        self.attestation_repository.get_attestation(epoch, validator).await
+    }
+
+    async fn get_attestations_for_validator(&self, validator: u64, limit: u64) -> Result<Vec<AttestationData>> {
+        self.attestation_repository
+            .get_attestations_for_validator(validator, limit)
+            .await
+    }
+
     async fn create_or_update_committee(&self, committee: &Committee) -> Result<()> {
         self.committee_repository.create_committee(committee).await
     }
@@ -214,6 +715,14 @@ impl Service for ServiceImpl {
         self.committee_repository.get_committees(inputs).await
     }
 
+    async fn get_committees_for_slot(&self, slot: u64) -> Result<Vec<Committee>> {
+        self.committee_repository.get_committees_for_slot(slot).await
+    }
+
+    async fn get_committee_page_for_slot(&self, slot: u64, page: &PageRequest) -> Result<Page<Committee>> {
+        self.committee_repository.get_committee_page_for_slot(slot, page).await
+    }
+
     async fn create_proposer(&self, slot: u64, validator: u64) -> Result<()> {
         self.proposer_repository.create_proposer(slot, validator).await
     }
@@ -230,18 +739,728 @@ impl Service for ServiceImpl {
         Ok(self.proposer_repository.get_proposer_for_slot(slot).await?.is_some())
     }
 
-    async fn block_count_for_epoch(&self, epoch: u64) -> Result<u8> {
-        let proposers = self.proposer_repository.get_proposers_for_epoch(epoch).await?;
-        let mut count = 0;
-        for proposer in proposers {
-            if let Some(true) = self
-                .attestation_repository
-                .get_attestation_for_slot_and_validator(epoch * 32, proposer)
-                .await?
-            {
-                count += 1
+    async fn block_count_for_epoch(&self, epoch: u64) -> Result<u64> {
+        Ok(self.get_block_production_for_epoch(epoch).await?.proposed)
+    }
+
+    async fn get_block_production_for_epoch(&self, epoch: u64) -> Result<BlockProduction> {
+        self.cache
+            .get_or_fetch(CacheKind::BlockProduction, epoch, async {
+                let slots = self.slot_repository.get_slots_for_epoch(epoch).await?;
+                Ok(BlockProduction::new(epoch, slots))
+            })
+            .await
+    }
+
+    async fn create_proposer_duties(&self, duties: &[Proposer]) -> Result<()> {
+        self.proposer_duty_repository.create_proposer_duties(duties).await
+    }
+
+    async fn get_proposer_duties_for_epoch(&self, epoch: u64) -> Result<Vec<Proposer>> {
+        self.proposer_duty_repository.get_proposer_duties_for_epoch(epoch).await
+    }
+
+    async fn get_proposal_outcome(&self, slot: u64) -> Result<Option<ProposalOutcome>> {
+        self.proposer_duty_repository.get_proposal_outcome(slot).await
+    }
+
+    async fn get_proposal_outcomes_for_validator(&self, validator: u64) -> Result<Vec<ProposalOutcome>> {
+        self.proposer_duty_repository
+            .get_proposal_outcomes_for_validators(&[validator])
+            .await
+    }
+
+    async fn get_recent_proposal_outcomes_for_validators(
+        &self,
+        validators: &[u64],
+        limit: u64,
+    ) -> Result<Vec<ProposalOutcome>> {
+        self.proposer_duty_repository
+            .get_recent_proposal_outcomes_for_validators(validators, limit)
+            .await
+    }
+
+    async fn get_proposal_outcome_page_for_validator(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<ProposalOutcome>> {
+        self.proposer_duty_repository
+            .get_proposal_outcome_page(&[validator], None, page)
+            .await
+    }
+
+    async fn get_proposal_success_rate_for_validator(&self, validator: u64) -> Result<f64> {
+        let outcomes = self
+            .proposer_duty_repository
+            .get_proposal_outcomes_for_validators(&[validator])
+            .await?;
+        proposal_success_rate(&outcomes).ok_or(anyhow!("No indexed proposer duties for validator"))
+    }
+
+    async fn create_slot(&self, slot: &Slot) -> Result<()> {
+        self.slot_repository.create_slot(slot).await
+    }
+
+    async fn get_slot(&self, slot: u64) -> Result<Option<Slot>> {
+        self.slot_repository.get_slot(slot).await
+    }
+
+    async fn get_last_slot(&self) -> Result<Option<u64>> {
+        self.slot_repository.get_last_slot().await
+    }
+
+    async fn get_slots_for_epoch(&self, epoch: u64) -> Result<Vec<Slot>> {
+        self.slot_repository.get_slots_for_epoch(epoch).await
+    }
+
+    async fn missed_proposals_for_epoch(&self, epoch: u64, page: &PageRequest) -> Result<Page<Slot>> {
+        let filter = SlotFilter {
+            status: Some(SlotStatus::Missed),
+            epoch_from: Some(epoch),
+            epoch_to: Some(epoch),
+            ..Default::default()
+        };
+        self.get_slots_page(&filter, SortDirection::Asc, page).await
+    }
+
+    async fn missed_proposals_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Slot>> {
+        let filter = SlotFilter {
+            status: Some(SlotStatus::Missed),
+            proposer: Some(validator),
+            ..Default::default()
+        };
+        self.get_slots_page(&filter, SortDirection::Asc, page).await
+    }
+
+    async fn create_slashings(&self, slashings: &[Slashing]) -> Result<()> {
+        self.slashing_repository.create_slashings(slashings).await
+    }
+
+    async fn get_slashings(&self, page: &PageRequest) -> Result<Page<Slashing>> {
+        self.slashing_repository.get_slashing_page(page).await
+    }
+
+    async fn get_slashing_status(&self, validator: u64) -> Result<Option<SlashingStatus>> {
+        Ok(self.get_slashing_statuses(&[validator]).await?.pop())
+    }
+
+    async fn get_slashing_statuses(&self, validators: &[u64]) -> Result<Vec<SlashingStatus>> {
+        let validators = self.validator_repository.get_validators(validators).await?;
+        let indices = validators.iter().map(|validator| validator.index).collect::<Vec<_>>();
+        let mut slashings: HashMap<u64, Vec<Slashing>> = HashMap::new();
+        for slashing in self.slashing_repository.get_slashings_for_validators(&indices).await? {
+            slashings.entry(slashing.validator_index).or_default().push(slashing);
+        }
+        Ok(validators
+            .into_iter()
+            .map(|validator| {
+                let slashings = slashings.remove(&validator.index).unwrap_or_default();
+                SlashingStatus {
+                    validator_index: validator.index,
+                    slashed: validator.slashed || !slashings.is_empty(),
+                    slashings,
+                }
+            })
+            .collect())
+    }
+
+    async fn create_voluntary_exits(&self, exits: &[VoluntaryExit]) -> Result<()> {
+        self.voluntary_exit_repository.create_voluntary_exits(exits).await
+    }
+
+    async fn get_voluntary_exit(&self, validator: u64) -> Result<Option<VoluntaryExit>> {
+        self.voluntary_exit_repository.get_voluntary_exit(validator).await
+    }
+
+    async fn get_voluntary_exits(&self, validators: &[u64]) -> Result<Vec<VoluntaryExit>> {
+        self.voluntary_exit_repository.get_voluntary_exits(validators).await
+    }
+
+    async fn get_pending_voluntary_exits(&self, page: &PageRequest) -> Result<Page<VoluntaryExit>> {
+        self.voluntary_exit_repository
+            .get_voluntary_exit_page(false, page)
+            .await
+    }
+
+    async fn get_processed_voluntary_exits(&self, page: &PageRequest) -> Result<Page<VoluntaryExit>> {
+        self.voluntary_exit_repository.get_voluntary_exit_page(true, page).await
+    }
+
+    async fn expire_voluntary_exits(&self) -> Result<u64> {
+        self.voluntary_exit_repository.expire_voluntary_exits().await
+    }
+
+    async fn create_bls_to_execution_changes(&self, changes: &[BlsToExecutionChange]) -> Result<()> {
+        self.bls_to_execution_change_repository
+            .create_bls_to_execution_changes(changes)
+            .await
+    }
+
+    async fn get_bls_to_execution_change(&self, validator: u64) -> Result<Option<BlsToExecutionChange>> {
+        self.bls_to_execution_change_repository
+            .get_bls_to_execution_change(validator)
+            .await
+    }
+
+    async fn get_pending_bls_to_execution_changes(&self, page: &PageRequest) -> Result<Page<BlsToExecutionChange>> {
+        self.bls_to_execution_change_repository
+            .get_bls_to_execution_change_page(false, page)
+            .await
+    }
+
+    async fn get_processed_bls_to_execution_changes(&self, page: &PageRequest) -> Result<Page<BlsToExecutionChange>> {
+        self.bls_to_execution_change_repository
+            .get_bls_to_execution_change_page(true, page)
+            .await
+    }
+
+    async fn create_deposits(&self, deposits: &[Deposit]) -> Result<()> {
+        self.deposit_repository.create_deposits(deposits).await
+    }
+
+    async fn get_recent_deposits_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Deposit>> {
+        self.deposit_repository
+            .get_recent_deposits_for_validators(validators, limit)
+            .await
+    }
+
+    async fn get_deposit_page_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Deposit>> {
+        self.deposit_repository
+            .get_deposit_page_for_validator(validator, page)
+            .await
+    }
+
+    async fn get_deposits_for_address(&self, address: &str, page: &PageRequest) -> Result<Page<Deposit>> {
+        let withdrawal_credentials = execution_withdrawal_credentials(address);
+        self.deposit_repository
+            .get_deposit_page_for_withdrawal_credentials(&withdrawal_credentials, page)
+            .await
+    }
+
+    async fn create_withdrawals(&self, withdrawals: &[Withdrawal]) -> Result<()> {
+        self.withdrawal_repository.create_withdrawals(withdrawals).await
+    }
+
+    async fn get_withdrawals_for_validator(&self, validator: u64) -> Result<Vec<Withdrawal>> {
+        self.withdrawal_repository
+            .get_withdrawals_for_validator(validator)
+            .await
+    }
+
+    async fn get_recent_withdrawals_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Withdrawal>> {
+        self.withdrawal_repository
+            .get_recent_withdrawals_for_validators(validators, limit)
+            .await
+    }
+
+    async fn get_withdrawal_page_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Withdrawal>> {
+        self.withdrawal_repository
+            .get_withdrawal_page(Some(validator), None, page)
+            .await
+    }
+
+    async fn get_withdrawals_for_address(&self, address: &str, page: &PageRequest) -> Result<Page<Withdrawal>> {
+        self.withdrawal_repository
+            .get_withdrawal_page(None, Some(&address.to_lowercase()), page)
+            .await
+    }
+
+    async fn withdrawal_total_for_validator(&self, validator: u64) -> Result<u64> {
+        self.withdrawal_repository
+            .withdrawal_total_for_validators(&[validator])
+            .await
+    }
+
+    async fn withdrawal_total_for_address(&self, address: &str) -> Result<u64> {
+        self.withdrawal_repository
+            .withdrawal_total_for_address(&address.to_lowercase())
+            .await
+    }
+
+    async fn create_execution_payload(&self, payload: &ExecutionPayload) -> Result<()> {
+        self.execution_payload_repository
+            .create_execution_payload(payload)
+            .await
+    }
+
+    async fn get_execution_payload(&self, slot: u64) -> Result<Option<ExecutionPayload>> {
+        self.execution_payload_repository.get_execution_payload(slot).await
+    }
+
+    async fn get_execution_payload_by_block_hash(&self, block_hash: &str) -> Result<Option<ExecutionPayload>> {
+        self.execution_payload_repository
+            .get_execution_payload_by_block_hash(&block_hash.to_lowercase())
+            .await
+    }
+
+    async fn get_execution_payload_by_block_number(&self, block_number: u64) -> Result<Option<ExecutionPayload>> {
+        self.execution_payload_repository
+            .get_execution_payload_by_block_number(block_number)
+            .await
+    }
+
+    async fn get_execution_payloads_for_proposer(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<ExecutionPayload>> {
+        self.execution_payload_repository
+            .get_execution_payload_page_for_proposer(validator, page)
+            .await
+    }
+
+    async fn get_fee_recipients_for_proposer(&self, validator: u64, page: &PageRequest) -> Result<Page<FeeRecipient>> {
+        self.execution_payload_repository
+            .get_fee_recipient_page_for_proposer(validator, page)
+            .await
+    }
+
+    async fn create_relay_payloads(&self, payloads: &[RelayPayload]) -> Result<()> {
+        self.relay_payload_repository.create_relay_payloads(payloads).await
+    }
+
+    async fn get_relay_payloads_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<RelayPayload>> {
+        self.relay_payload_repository
+            .get_relay_payload_page_for_validator(validator, page)
+            .await
+    }
+
+    async fn get_mev_income_for_validator(&self, validator: u64) -> Result<MevIncome> {
+        self.relay_payload_repository
+            .get_mev_income_for_validators(&[validator])
+            .await
+    }
+
+    async fn get_relay_usage_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<RelayUsage>> {
+        self.relay_payload_repository
+            .get_relay_usage_page_for_validators(&[validator], page)
+            .await
+    }
+
+    async fn record_relay_failures(&self, relay: &str, slots: &[u64]) -> Result<()> {
+        self.relay_payload_repository.record_relay_failures(relay, slots).await
+    }
+
+    async fn get_relay_failures(&self, max_attempts: u32, limit: u64) -> Result<Vec<RelayFailure>> {
+        self.relay_payload_repository
+            .get_relay_failures(max_attempts, limit)
+            .await
+    }
+
+    async fn delete_relay_failure(&self, relay: &str, slot: u64) -> Result<()> {
+        self.relay_payload_repository.delete_relay_failure(relay, slot).await
+    }
+
+    async fn create_blob_sidecars(&self, sidecars: &[BlobSidecar]) -> Result<()> {
+        self.blob_sidecar_repository.create_blob_sidecars(sidecars).await
+    }
+
+    async fn get_blob_sidecars(&self, slot: u64) -> Result<Vec<BlobSidecar>> {
+        self.blob_sidecar_repository.get_blob_sidecars(slot).await
+    }
+
+    async fn get_blob_sidecar_page(&self, slot: u64, page: &PageRequest) -> Result<Page<BlobSidecar>> {
+        self.blob_sidecar_repository.get_blob_sidecar_page(slot, page).await
+    }
+
+    async fn get_blob_throughput_for_epoch(&self, epoch: u64) -> Result<BlobThroughput> {
+        self.cache
+            .get_or_fetch(
+                CacheKind::BlobThroughput,
+                epoch,
+                self.blob_sidecar_repository.get_blob_throughput_for_epoch(epoch),
+            )
+            .await
+    }
+
+    async fn get_blob_throughput_for_proposer(&self, validator: u64) -> Result<BlobThroughput> {
+        self.blob_sidecar_repository
+            .get_blob_throughput_for_proposers(&[validator])
+            .await
+    }
+
+    async fn create_validator_group(&self, name: &str, labels: &[String]) -> Result<ValidatorGroup> {
+        let labels = normalize_labels(labels);
+        if !self.validator_group_repository.create_group(name, &labels).await? {
+            return Err(anyhow!("Validator group already exists"));
+        }
+        self.validator_group_repository
+            .get_group(name)
+            .await?
+            .ok_or(anyhow!("Validator group not found"))
+    }
+
+    async fn delete_validator_group(&self, name: &str) -> Result<bool> {
+        self.validator_group_repository.delete_group(name).await
+    }
+
+    async fn set_validator_group_labels(&self, name: &str, labels: &[String]) -> Result<ValidatorGroup> {
+        let labels = normalize_labels(labels);
+        if !self.validator_group_repository.set_group_labels(name, &labels).await? {
+            return Err(anyhow!("Validator group not found"));
+        }
+        self.validator_group_repository
+            .get_group(name)
+            .await?
+            .ok_or(anyhow!("Validator group not found"))
+    }
+
+    async fn add_validator_group_members(&self, name: &str, members: &[GroupMember]) -> Result<ValidatorGroup> {
+        if self.validator_group_repository.get_group(name).await?.is_none() {
+            return Err(anyhow!("Validator group not found"));
+        }
+        self.validator_group_repository.add_group_members(name, members).await?;
+        self.validator_group_repository
+            .get_group(name)
+            .await?
+            .ok_or(anyhow!("Validator group not found"))
+    }
+
+    async fn remove_validator_group_members(&self, name: &str, members: &[GroupMember]) -> Result<ValidatorGroup> {
+        if self.validator_group_repository.get_group(name).await?.is_none() {
+            return Err(anyhow!("Validator group not found"));
+        }
+        self.validator_group_repository
+            .remove_group_members(name, members)
+            .await?;
+        self.validator_group_repository
+            .get_group(name)
+            .await?
+            .ok_or(anyhow!("Validator group not found"))
+    }
+
+    async fn get_validator_group(&self, name: &str) -> Result<Option<ValidatorGroup>> {
+        self.validator_group_repository.get_group(name).await
+    }
+
+    async fn get_validator_groups(&self, label: Option<&str>, page: &PageRequest) -> Result<Page<ValidatorGroup>> {
+        self.validator_group_repository.get_group_page(label, page).await
+    }
+
+    async fn get_validator_group_indices(&self, name: &str) -> Result<Vec<u64>> {
+        if self.validator_group_repository.get_group(name).await?.is_none() {
+            return Err(anyhow!("Validator group not found"));
+        }
+        self.validator_group_repository.get_group_validator_indices(name).await
+    }
+
+    async fn get_validator_group_index_page(&self, name: &str, page: &PageRequest) -> Result<Page<u64>> {
+        if self.validator_group_repository.get_group(name).await?.is_none() {
+            return Err(anyhow!("Validator group not found"));
+        }
+        self.validator_group_repository
+            .get_group_validator_index_page(name, page)
+            .await
+    }
+
+    async fn get_participation_rate_for_group(&self, name: &str, epoch: Option<u64>) -> Result<f64> {
+        let indices = self.get_validator_group_indices(name).await?;
+        let validators = self.validator_repository.get_validators(&indices).await?;
+        if validators.is_empty() {
+            return Err(anyhow!("No indexed validators in group"));
+        }
+        let (attestation_count, active_epoch_count) = match epoch {
+            Some(epoch) => {
+                let active = validators
+                    .iter()
+                    .filter(|validator| validator.activation_epoch <= epoch && epoch < validator.exit_epoch)
+                    .map(|validator| (epoch, validator.index))
+                    .collect::<Vec<_>>();
+                let attested = self
+                    .attestation_repository
+                    .get_attestations(&active)
+                    .await?
+                    .into_iter()
+                    .filter(|attested| *attested == Some(true))
+                    .count();
+                (attested as u64, active.len() as u64)
+            }
+            None => {
+                let current_epoch = self.epoch_repository.current_epoch().await?;
+                let attestation_count = validators.iter().map(|validator| validator.attestations).sum::<u64>();
+                let active_epoch_count = validators
+                    .iter()
+                    .map(|validator| active_epoch_count(validator, current_epoch))
+                    .sum::<u64>();
+                (attestation_count, active_epoch_count)
             }
+        };
+        if active_epoch_count == 0 {
+            return Err(anyhow!("No active validators in group"));
         }
-        Ok(count)
+        Ok(attestation_count as f64 / active_epoch_count as f64)
+    }
+
+    async fn get_proposal_outcomes_for_group(
+        &self,
+        name: &str,
+        epoch: Option<u64>,
+        page: &PageRequest,
+    ) -> Result<Page<ProposalOutcome>> {
+        let indices = self.get_validator_group_indices(name).await?;
+        self.proposer_duty_repository
+            .get_proposal_outcome_page(&indices, epoch, page)
+            .await
+    }
+
+    async fn get_proposal_success_rate_for_group(&self, name: &str, epoch: Option<u64>) -> Result<f64> {
+        let indices = self.get_validator_group_indices(name).await?;
+        let outcomes = self
+            .proposer_duty_repository
+            .get_proposal_outcomes_for_validators(&indices)
+            .await?
+            .into_iter()
+            .filter(|outcome| epoch.is_none_or(|epoch| outcome.epoch == epoch))
+            .collect::<Vec<_>>();
+        proposal_success_rate(&outcomes).ok_or(anyhow!("No indexed proposer duties for group"))
+    }
+
+    async fn missed_proposals_for_group(&self, name: &str, page: &PageRequest) -> Result<Page<Slot>> {
+        let filter = SlotFilter {
+            status: Some(SlotStatus::Missed),
+            group: Some(name.to_string()),
+            ..Default::default()
+        };
+        self.get_slots_page(&filter, SortDirection::Asc, page).await
+    }
+
+    async fn withdrawal_total_for_group(&self, name: &str) -> Result<u64> {
+        let indices = self.get_validator_group_indices(name).await?;
+        self.withdrawal_repository
+            .withdrawal_total_for_validators(&indices)
+            .await
+    }
+
+    async fn get_mev_income_for_group(&self, name: &str) -> Result<MevIncome> {
+        let indices = self.get_validator_group_indices(name).await?;
+        self.relay_payload_repository
+            .get_mev_income_for_validators(&indices)
+            .await
+    }
+
+    async fn get_relay_usage_for_group(&self, name: &str, page: &PageRequest) -> Result<Page<RelayUsage>> {
+        let indices = self.get_validator_group_indices(name).await?;
+        self.relay_payload_repository
+            .get_relay_usage_page_for_validators(&indices, page)
+            .await
+    }
+
+    async fn get_blob_throughput_for_group(&self, name: &str) -> Result<BlobThroughput> {
+        let indices = self.get_validator_group_indices(name).await?;
+        self.blob_sidecar_repository
+            .get_blob_throughput_for_proposers(&indices)
+            .await
+    }
+
+    async fn get_validators_page(
+        &self,
+        filter: &ValidatorFilter,
+        sort: ValidatorSortField,
+        direction: SortDirection,
+        page: &PageRequest,
+    ) -> Result<Page<Validator>> {
+        let indices = match &filter.group {
+            Some(group) => Some(self.get_validator_group_indices(group).await?),
+            None => None,
+        };
+        let query = ValidatorQuery {
+            status: filter.status,
+            active_from: filter.active_from,
+            active_to: filter.active_to,
+            indices,
+            min_participation_rate: filter.min_participation_rate,
+            current_epoch: self.epoch_repository.current_epoch().await?,
+            sort,
+            direction,
+        };
+        self.validator_repository.get_validator_page(&query, page).await
+    }
+
+    async fn get_slots_page(
+        &self,
+        filter: &SlotFilter,
+        direction: SortDirection,
+        page: &PageRequest,
+    ) -> Result<Page<Slot>> {
+        let mut proposers = match &filter.group {
+            Some(group) => Some(self.get_validator_group_indices(group).await?),
+            None => None,
+        };
+        if let Some(proposer) = filter.proposer {
+            proposers = Some(match proposers {
+                Some(indices) => indices.into_iter().filter(|index| *index == proposer).collect(),
+                None => vec![proposer],
+            });
+        }
+        let query = SlotQuery {
+            status: filter.status,
+            epoch_from: filter.epoch_from,
+            epoch_to: filter.epoch_to,
+            proposers,
+            direction,
+        };
+        self.slot_repository.get_slot_page(&query, page).await
+    }
+
+    async fn publish_event(&self, event: &ChainEvent) -> Result<()> {
+        self.event_repository.publish_event(event).await
+    }
+
+    async fn get_duty_outcomes_for_event(&self, event: &ChainEvent, validators: &[u64]) -> Result<Vec<DutyOutcome>> {
+        match event {
+            ChainEvent::Head(slot) if validators.contains(&slot.proposer_index) => Ok(vec![DutyOutcome {
+                validator: slot.proposer_index,
+                epoch: slot.epoch,
+                slot: Some(slot.slot),
+                kind: DutyKind::Proposal,
+                success: slot.status == SlotStatus::Proposed,
+            }]),
+            ChainEvent::Epoch { index } => {
+                let epoch_validators = validators
+                    .iter()
+                    .map(|validator| (*index, *validator))
+                    .collect::<Vec<_>>();
+                let attestations = self.attestation_repository.get_attestations(&epoch_validators).await?;
+                Ok(validators
+                    .iter()
+                    .zip(attestations)
+                    .filter_map(|(validator, attested)| {
+                        attested.map(|attested| DutyOutcome {
+                            validator: *validator,
+                            epoch: *index,
+                            slot: None,
+                            kind: DutyKind::Attestation,
+                            success: attested,
+                        })
+                    })
+                    .collect())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_epochs(&self, indices: &[u64]) -> Result<Vec<Epoch>> {
+        self.epoch_repository.get_epochs(indices).await
     }
+
+    async fn get_attestation_data(&self, epoch_validators: &[(u64, u64)]) -> Result<Vec<AttestationData>> {
+        self.attestation_repository.get_attestation_data(epoch_validators).await
+    }
+
+    async fn get_committees_for_epoch(&self, epoch: u64) -> Result<Vec<Committee>> {
+        self.committee_repository.get_committees_for_epoch(epoch).await
+    }
+
+    async fn create_block_header(&self, header: &BlockHeader) -> Result<()> {
+        self.block_header_repository.create_block_header(header).await
+    }
+
+    async fn get_block_header(&self, slot: u64) -> Result<Option<BlockHeader>> {
+        self.block_header_repository.get_block_header(slot).await
+    }
+
+    async fn get_block_header_by_root(&self, root: &str) -> Result<Option<BlockHeader>> {
+        self.block_header_repository.get_block_header_by_root(root).await
+    }
+
+    async fn get_block_header_by_state_root(&self, state_root: &str) -> Result<Option<BlockHeader>> {
+        self.block_header_repository
+            .get_block_header_by_state_root(state_root)
+            .await
+    }
+
+    async fn get_latest_block_header(&self) -> Result<Option<BlockHeader>> {
+        self.block_header_repository.get_latest_block_header().await
+    }
+
+    async fn get_block_header_before(&self, slot: u64) -> Result<Option<BlockHeader>> {
+        self.block_header_repository.get_block_header_before(slot).await
+    }
+
+    async fn delete_block(&self, slot: u64) -> Result<()> {
+        self.block_header_repository.delete_block(slot).await
+    }
+
+    async fn stream_attestations(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<AttestationData>>> {
+        self.attestation_repository
+            .stream_attestations(from_epoch, to_epoch)
+            .await
+    }
+
+    async fn stream_epochs(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Epoch>>> {
+        self.epoch_repository.stream_epochs(from_epoch, to_epoch).await
+    }
+
+    async fn stream_slots(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Slot>>> {
+        self.slot_repository.stream_slots(from_epoch, to_epoch).await
+    }
+
+    async fn stream_validator_history(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<ValidatorHistory>>> {
+        self.attestation_repository
+            .stream_validator_history(from_epoch, to_epoch)
+            .await
+    }
+
+    async fn create_api_key(&self, key: &ApiKey, key_hash: &str) -> Result<bool> {
+        self.api_key_repository.create_api_key(key, key_hash).await
+    }
+
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        self.api_key_repository.get_api_key_by_hash(key_hash).await
+    }
+
+    async fn get_api_keys(&self) -> Result<Vec<ApiKey>> {
+        self.api_key_repository.get_api_keys().await
+    }
+
+    async fn revoke_api_key(&self, name: &str) -> Result<bool> {
+        self.api_key_repository.revoke_api_key(name).await
+    }
+
+    async fn record_api_key_usage(&self, usage: &[(String, u64)]) -> Result<()> {
+        self.api_key_repository.record_api_key_usage(usage).await
+    }
+}
+
+fn active_epoch_count(validator: &Validator, current_epoch: u64) -> u64 {
+    if current_epoch > validator.exit_epoch {
+        validator.exit_epoch.saturating_sub(validator.activation_epoch)
+    } else {
+        current_epoch.saturating_sub(validator.activation_epoch)
+    }
+}
+
+fn proposal_success_rate(outcomes: &[ProposalOutcome]) -> Option<f64> {
+    let indexed = outcomes
+        .iter()
+        .filter(|outcome| outcome.status.is_some())
+        .collect::<Vec<_>>();
+    if indexed.is_empty() {
+        return None;
+    }
+    let proposed = indexed
+        .iter()
+        .filter(|outcome| outcome.status == Some(SlotStatus::Proposed))
+        .count();
+    Some(proposed as f64 / indexed.len() as f64)
+}
+
+fn normalize_labels(labels: &[String]) -> Vec<String> {
+    let mut labels = labels
+        .iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+        .collect::<Vec<_>>();
+    labels.sort();
+    labels.dedup();
+    labels
 }
@@ -1,6 +1,8 @@
+use std::{fmt::Display, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Epoch {
     pub index: u64,
     pub active_validators: u64,
@@ -8,21 +10,31 @@ pub struct Epoch {
     pub attestations: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Validator {
     pub index: u64,
     pub pubkey: String,
     pub attestations: u64,
+    pub activation_eligibility_epoch: u64,
     pub activation_epoch: u64,
     pub exit_epoch: u64,
+    pub withdrawable_epoch: u64,
+    pub slashed: bool,
+    pub withdrawal_credentials: String,
 }
 
+pub const FAR_FUTURE_EPOCH: u64 = u64::MAX;
+
 #[derive(Debug)]
 pub struct ValidatorDataInput {
     pub index: u64,
     pub pubkey: String,
+    pub activation_eligibility_epoch: u64,
     pub activation_epoch: u64,
     pub exit_epoch: u64,
+    pub withdrawable_epoch: u64,
+    pub slashed: bool,
+    pub withdrawal_credentials: String,
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +44,16 @@ pub struct AttestationData {
     pub slot: u64,
     pub committee_index: u8,
     pub attested: bool,
+    pub inclusion_slot: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidatorHistory {
+    pub epoch: u64,
+    pub validator_index: u64,
+    pub attested: bool,
+    pub proposed: u64,
+    pub missed: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +73,51 @@ impl From<client::model::committee::Committee> for Committee {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct BlockHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub root: String,
+    pub parent_root: String,
+    pub state_root: String,
+    pub body_root: String,
+    pub signature: String,
+}
+
+impl From<client::model::block::BlockHeaderData> for BlockHeader {
+    fn from(data: client::model::block::BlockHeaderData) -> Self {
+        Self {
+            slot: data.header.message.slot,
+            proposer_index: data.header.message.proposer_index,
+            root: data.root,
+            parent_root: data.header.message.parent_root,
+            state_root: data.header.message.state_root,
+            body_root: data.header.message.body_root,
+            signature: data.header.signature,
+        }
+    }
+}
+
+impl From<BlockHeader> for client::model::block::BlockHeaderData {
+    fn from(header: BlockHeader) -> Self {
+        use client::model::block::{BlockHeader as Header, BlockHeaderMessage};
+        Self {
+            root: header.root,
+            canonical: true,
+            header: Header {
+                message: BlockHeaderMessage {
+                    slot: header.slot,
+                    proposer_index: header.proposer_index,
+                    parent_root: header.parent_root,
+                    state_root: header.state_root,
+                    body_root: header.body_root,
+                },
+                signature: header.signature,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Proposer {
     pub slot: u64,
@@ -65,3 +132,795 @@ impl From<client::model::proposer::Proposer> for Proposer {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotStatus {
+    Proposed,
+    Missed,
+    Orphaned,
+}
+
+impl FromStr for SlotStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "proposed" => Ok(Self::Proposed),
+            "missed" => Ok(Self::Missed),
+            "orphaned" => Ok(Self::Orphaned),
+            _ => Err(anyhow::anyhow!("Invalid slot status: {}", s)),
+        }
+    }
+}
+
+impl Display for SlotStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlotStatus::Proposed => write!(f, "proposed"),
+            SlotStatus::Missed => write!(f, "missed"),
+            SlotStatus::Orphaned => write!(f, "orphaned"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Slot {
+    pub slot: u64,
+    pub epoch: u64,
+    pub proposer_index: u64,
+    pub status: SlotStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockProduction {
+    pub epoch: u64,
+    pub proposed: u64,
+    pub missed: u64,
+    pub orphaned: u64,
+    pub slots: Vec<Slot>,
+}
+
+impl BlockProduction {
+    pub fn new(epoch: u64, slots: Vec<Slot>) -> Self {
+        let count = |status: SlotStatus| slots.iter().filter(|slot| slot.status == status).count() as u64;
+        Self {
+            epoch,
+            proposed: count(SlotStatus::Proposed),
+            missed: count(SlotStatus::Missed),
+            orphaned: count(SlotStatus::Orphaned),
+            slots,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalOutcome {
+    pub slot: u64,
+    pub epoch: u64,
+    pub scheduled_proposer: u64,
+    pub actual_proposer: Option<u64>,
+    pub status: Option<SlotStatus>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingKind {
+    Proposer,
+    Attester,
+}
+
+impl FromStr for SlashingKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "proposer" => Ok(Self::Proposer),
+            "attester" => Ok(Self::Attester),
+            _ => Err(anyhow::anyhow!("Invalid slashing kind: {}", s)),
+        }
+    }
+}
+
+impl Display for SlashingKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlashingKind::Proposer => write!(f, "proposer"),
+            SlashingKind::Attester => write!(f, "attester"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Slashing {
+    pub slot: u64,
+    pub epoch: u64,
+    pub kind: SlashingKind,
+    pub validator_index: u64,
+    pub proposer_index: u64,
+    pub whistleblower_index: u64,
+    pub evidence: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingStatus {
+    pub validator_index: u64,
+    pub slashed: bool,
+    pub slashings: Vec<Slashing>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoluntaryExit {
+    pub validator_index: u64,
+    pub epoch: u64,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl From<client::model::voluntary_exit::SignedVoluntaryExit> for VoluntaryExit {
+    fn from(exit: client::model::voluntary_exit::SignedVoluntaryExit) -> Self {
+        Self {
+            validator_index: exit.message.validator_index,
+            epoch: exit.message.epoch,
+            inclusion_slot: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlsToExecutionChange {
+    pub validator_index: u64,
+    pub from_bls_pubkey: String,
+    pub to_execution_address: String,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl From<client::model::bls_to_execution_change::SignedBlsToExecutionChange> for BlsToExecutionChange {
+    fn from(change: client::model::bls_to_execution_change::SignedBlsToExecutionChange) -> Self {
+        Self {
+            validator_index: change.message.validator_index,
+            from_bls_pubkey: change.message.from_bls_pubkey,
+            to_execution_address: change.message.to_execution_address,
+            inclusion_slot: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deposit {
+    pub slot: u64,
+    pub position: u64,
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub amount: u64,
+    pub validator_index: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Withdrawal {
+    pub index: u64,
+    pub slot: u64,
+    pub validator_index: u64,
+    pub address: String,
+    pub amount: u64,
+}
+
+impl Withdrawal {
+    pub fn new(slot: u64, withdrawal: client::model::execution::Withdrawal) -> Self {
+        Self {
+            index: withdrawal.index,
+            slot,
+            validator_index: withdrawal.validator_index,
+            address: withdrawal.address.to_lowercase(),
+            amount: withdrawal.amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionPayload {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub block_number: u64,
+    pub block_hash: String,
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: u64,
+    pub timestamp: u64,
+    pub transaction_count: u64,
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl ExecutionPayload {
+    pub fn new(slot: u64, proposer_index: u64, payload: &client::model::execution::ExecutionPayload) -> Self {
+        Self {
+            slot,
+            proposer_index,
+            block_number: payload.block_number,
+            block_hash: payload.block_hash.to_lowercase(),
+            parent_hash: payload.parent_hash.to_lowercase(),
+            fee_recipient: payload.fee_recipient.to_lowercase(),
+            gas_used: payload.gas_used,
+            gas_limit: payload.gas_limit,
+            base_fee_per_gas: payload.base_fee_per_gas,
+            timestamp: payload.timestamp,
+            transaction_count: payload.transactions.len() as u64,
+            blob_gas_used: payload.blob_gas_used,
+            excess_blob_gas: payload.excess_blob_gas,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeRecipient {
+    pub address: String,
+    pub block_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayPayload {
+    pub relay: String,
+    pub slot: u64,
+    pub block_hash: String,
+    pub builder_pubkey: String,
+    pub proposer_pubkey: String,
+    pub proposer_fee_recipient: String,
+    pub value: u128,
+}
+
+impl RelayPayload {
+    pub fn new(relay: &str, trace: client::model::relay::BidTrace) -> Self {
+        Self {
+            relay: relay.to_string(),
+            slot: trace.slot,
+            block_hash: trace.block_hash.to_lowercase(),
+            builder_pubkey: trace.builder_pubkey.to_lowercase(),
+            proposer_pubkey: trace.proposer_pubkey.to_lowercase(),
+            proposer_fee_recipient: trace.proposer_fee_recipient.to_lowercase(),
+            value: trace.value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayFailure {
+    pub relay: String,
+    pub slot: u64,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MevIncome {
+    pub block_count: u64,
+    pub total_value: u128,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayUsage {
+    pub relay: String,
+    pub block_count: u64,
+    pub total_value: u128,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobSidecar {
+    pub slot: u64,
+    pub epoch: u64,
+    pub proposer_index: u64,
+    pub index: u64,
+    pub kzg_commitment: String,
+    pub kzg_proof: Option<String>,
+    pub size: u64,
+    pub blob: Option<String>,
+}
+
+// Every blob is 4096 field elements of 32 bytes
+pub const BYTES_PER_BLOB: u64 = 131_072;
+
+impl BlobSidecar {
+    pub fn new(slot: u64, proposer_index: u64, sidecar: client::model::blob::BlobSidecar) -> Self {
+        Self {
+            slot,
+            epoch: slot / 32,
+            proposer_index,
+            index: sidecar.index,
+            kzg_commitment: sidecar.kzg_commitment.to_lowercase(),
+            kzg_proof: Some(sidecar.kzg_proof.to_lowercase()),
+            size: sidecar.size(),
+            blob: Some(sidecar.blob),
+        }
+    }
+
+    pub fn from_commitment(slot: u64, proposer_index: u64, index: u64, kzg_commitment: &str) -> Self {
+        Self {
+            slot,
+            epoch: slot / 32,
+            proposer_index,
+            index,
+            kzg_commitment: kzg_commitment.to_lowercase(),
+            kzg_proof: None,
+            size: BYTES_PER_BLOB,
+            blob: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobThroughput {
+    pub block_count: u64,
+    pub blob_count: u64,
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupMemberKind {
+    Index,
+    Pubkey,
+    WithdrawalCredentials,
+}
+
+impl FromStr for GroupMemberKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "index" => Ok(Self::Index),
+            "pubkey" => Ok(Self::Pubkey),
+            "withdrawal_credentials" => Ok(Self::WithdrawalCredentials),
+            _ => Err(anyhow::anyhow!("Invalid group member kind: {}", s)),
+        }
+    }
+}
+
+impl Display for GroupMemberKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupMemberKind::Index => write!(f, "index"),
+            GroupMemberKind::Pubkey => write!(f, "pubkey"),
+            GroupMemberKind::WithdrawalCredentials => write!(f, "withdrawal_credentials"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub kind: GroupMemberKind,
+    pub value: String,
+}
+
+impl GroupMember {
+    pub fn new(kind: GroupMemberKind, value: &str) -> anyhow::Result<Self> {
+        let value = match kind {
+            GroupMemberKind::Index => value.trim().parse::<u64>()?.to_string(),
+            GroupMemberKind::Pubkey | GroupMemberKind::WithdrawalCredentials => value.trim().to_lowercase(),
+        };
+        Ok(Self { kind, value })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorGroup {
+    pub name: String,
+    pub labels: Vec<String>,
+    pub members: Vec<GroupMember>,
+}
+
+// Both execution (0x01) and compounding (0x02) credentials withdraw to an execution address
+pub fn execution_withdrawal_credentials(address: &str) -> Vec<String> {
+    let address = address.trim_start_matches("0x").to_lowercase();
+    ["0x01", "0x02"]
+        .iter()
+        .map(|prefix| format!("{prefix}{}{address}", "0".repeat(22)))
+        .collect()
+}
+
+pub const DEFAULT_PAGE_SIZE: u64 = 100;
+pub const MAX_PAGE_SIZE: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub value: u64,
+    pub index: u64,
+}
+
+impl Cursor {
+    pub fn new(value: u64, index: u64) -> Self {
+        Self { value, index }
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 32 || !s.is_ascii() {
+            return Err(anyhow::anyhow!("Invalid cursor: {}", s));
+        }
+        let value = u64::from_str_radix(&s[..16], 16).map_err(|_| anyhow::anyhow!("Invalid cursor: {}", s))?;
+        let index = u64::from_str_radix(&s[16..], 16).map_err(|_| anyhow::anyhow!("Invalid cursor: {}", s))?;
+        Ok(Self { value, index })
+    }
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}{:016x}", self.value, self.index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub limit: u64,
+    pub cursor: Option<Cursor>,
+    pub backward: bool,
+}
+
+impl PageRequest {
+    pub fn new(
+        first: Option<u64>,
+        after: Option<&str>,
+        last: Option<u64>,
+        before: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        if first.is_some() && last.is_some() {
+            return Err(anyhow::anyhow!("Only one of first and last can be set"));
+        }
+        if after.is_some() && before.is_some() {
+            return Err(anyhow::anyhow!("Only one of after and before can be set"));
+        }
+        let backward = last.is_some() || before.is_some();
+        let limit = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit > MAX_PAGE_SIZE {
+            return Err(anyhow::anyhow!("Page size cannot exceed {}", MAX_PAGE_SIZE));
+        }
+        let cursor = after.or(before).map(str::parse).transpose()?;
+        Ok(Self {
+            limit,
+            cursor,
+            backward,
+        })
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_PAGE_SIZE,
+            cursor: None,
+            backward: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<(Cursor, T)>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+    pub total_count: u64,
+}
+
+impl<T> Page<T> {
+    pub fn new(mut items: Vec<(Cursor, T)>, request: &PageRequest, total_count: u64) -> Self {
+        let has_more = items.len() as u64 > request.limit;
+        items.truncate(request.limit as usize);
+        if request.backward {
+            items.reverse();
+        }
+        let has_cursor = request.cursor.is_some();
+        Self {
+            items,
+            has_previous_page: if request.backward { has_more } else { has_cursor },
+            has_next_page: if request.backward { has_cursor } else { has_more },
+            total_count,
+        }
+    }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(|(cursor, item)| (cursor, f(item))).collect(),
+            has_previous_page: self.has_previous_page,
+            has_next_page: self.has_next_page,
+            total_count: self.total_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidatorStatus {
+    Pending,
+    Active,
+    Exited,
+    Slashed,
+}
+
+impl FromStr for ValidatorStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "active" => Ok(Self::Active),
+            "exited" => Ok(Self::Exited),
+            "slashed" => Ok(Self::Slashed),
+            _ => Err(anyhow::anyhow!("Invalid validator status: {}", s)),
+        }
+    }
+}
+
+impl Display for ValidatorStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidatorStatus::Pending => write!(f, "pending"),
+            ValidatorStatus::Active => write!(f, "active"),
+            ValidatorStatus::Exited => write!(f, "exited"),
+            ValidatorStatus::Slashed => write!(f, "slashed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ValidatorSortField {
+    #[default]
+    Index,
+    ActivationEpoch,
+    ExitEpoch,
+    Attestations,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorFilter {
+    pub status: Option<ValidatorStatus>,
+    pub active_from: Option<u64>,
+    pub active_to: Option<u64>,
+    pub group: Option<String>,
+    pub min_participation_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidatorQuery {
+    pub status: Option<ValidatorStatus>,
+    pub active_from: Option<u64>,
+    pub active_to: Option<u64>,
+    pub indices: Option<Vec<u64>>,
+    pub min_participation_rate: Option<f64>,
+    pub current_epoch: u64,
+    pub sort: ValidatorSortField,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SlotFilter {
+    pub status: Option<SlotStatus>,
+    pub epoch_from: Option<u64>,
+    pub epoch_to: Option<u64>,
+    pub proposer: Option<u64>,
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SlotQuery {
+    pub status: Option<SlotStatus>,
+    pub epoch_from: Option<u64>,
+    pub epoch_to: Option<u64>,
+    pub proposers: Option<Vec<u64>>,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    Read,
+    Admin,
+}
+
+impl FromStr for ApiScope {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Self::Read),
+            "admin" => Ok(Self::Admin),
+            _ => Err(anyhow::anyhow!("Invalid API scope: {}", s)),
+        }
+    }
+}
+
+impl Display for ApiScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiScope::Read => write!(f, "read"),
+            ApiScope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    pub rate_limit: u64,
+    pub request_count: u64,
+    pub last_used_at: Option<u64>,
+    pub revoked: bool,
+}
+
+impl ApiKey {
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope) || self.scopes.contains(&ApiScope::Admin)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub epoch: u64,
+    pub root: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChainEvent {
+    Head(Slot),
+    FinalizedCheckpoint(Checkpoint),
+    Epoch { index: u64 },
+    Reorg { slot: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DutyKind {
+    Proposal,
+    Attestation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DutyOutcome {
+    pub validator: u64,
+    pub epoch: u64,
+    pub slot: Option<u64>,
+    pub kind: DutyKind,
+    pub success: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_status_round_trip() {
+        for status in [SlotStatus::Proposed, SlotStatus::Missed, SlotStatus::Orphaned] {
+            assert_eq!(status.to_string().parse::<SlotStatus>().unwrap(), status);
+        }
+        assert!("skipped".parse::<SlotStatus>().is_err());
+    }
+
+    #[test]
+    fn api_key_admin_implies_read() {
+        let mut key = ApiKey {
+            name: "notebooks".to_string(),
+            scopes: vec![ApiScope::Read],
+            rate_limit: 60,
+            request_count: 0,
+            last_used_at: None,
+            revoked: false,
+        };
+        assert!(key.has_scope(ApiScope::Read));
+        assert!(!key.has_scope(ApiScope::Admin));
+        key.scopes = vec![ApiScope::Admin];
+        assert!(key.has_scope(ApiScope::Read));
+        assert_eq!("admin".parse::<ApiScope>().unwrap(), ApiScope::Admin);
+        assert!("write".parse::<ApiScope>().is_err());
+    }
+
+    #[test]
+    fn group_member_normalizes_value() {
+        let member = GroupMember::new(GroupMemberKind::Index, " 0042 ").unwrap();
+        assert_eq!(member.value, "42");
+        let member = GroupMember::new(GroupMemberKind::Pubkey, "0xABCD").unwrap();
+        assert_eq!(member.value, "0xabcd");
+        assert!(GroupMember::new(GroupMemberKind::Index, "0xabcd").is_err());
+    }
+
+    #[test]
+    fn execution_withdrawal_credentials_pads_address() {
+        assert_eq!(
+            execution_withdrawal_credentials("0xAbCdEf0123456789abcdef0123456789ABCDEF01"),
+            [
+                "0x010000000000000000000000abcdef0123456789abcdef0123456789abcdef01",
+                "0x020000000000000000000000abcdef0123456789abcdef0123456789abcdef01"
+            ]
+        );
+    }
+
+    #[test]
+    fn blob_sidecar_from_commitment_has_no_proof() {
+        let sidecar = BlobSidecar::from_commitment(65, 7, 2, "0xABCD");
+        assert_eq!(sidecar.epoch, 2);
+        assert_eq!(sidecar.kzg_commitment, "0xabcd");
+        assert_eq!(sidecar.kzg_proof, None);
+        assert_eq!(sidecar.size, BYTES_PER_BLOB);
+    }
+
+    #[test]
+    fn block_production_counts_statuses() {
+        let slots = [
+            SlotStatus::Proposed,
+            SlotStatus::Missed,
+            SlotStatus::Proposed,
+            SlotStatus::Orphaned,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, status)| Slot {
+            slot: 64 + i as u64,
+            epoch: 2,
+            proposer_index: i as u64,
+            status,
+        })
+        .collect::<Vec<_>>();
+        let production = BlockProduction::new(2, slots);
+        assert_eq!(production.proposed, 2);
+        assert_eq!(production.missed, 1);
+        assert_eq!(production.orphaned, 1);
+        assert_eq!(production.slots.len(), 4);
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let cursor = Cursor::new(42, u64::MAX);
+        assert_eq!(cursor.to_string().parse::<Cursor>().unwrap(), cursor);
+        assert!("42".parse::<Cursor>().is_err());
+    }
+
+    #[test]
+    fn page_request_rejects_invalid_arguments() {
+        assert!(PageRequest::new(Some(10), None, Some(10), None).is_err());
+        assert!(PageRequest::new(Some(MAX_PAGE_SIZE + 1), None, None, None).is_err());
+        let request = PageRequest::new(None, None, Some(5), None).unwrap();
+        assert!(request.backward);
+        assert_eq!(request.limit, 5);
+    }
+
+    #[test]
+    fn page_trims_extra_item() {
+        let items = (0..3).map(|i| (Cursor::new(i, i), i)).collect::<Vec<_>>();
+        let request = PageRequest::new(Some(2), None, None, None).unwrap();
+        let page = Page::new(items.clone(), &request, 10);
+        assert!(page.has_next_page);
+        assert!(!page.has_previous_page);
+        assert_eq!(page.items.iter().map(|(_, i)| *i).collect::<Vec<_>>(), vec![0, 1]);
+
+        let request = PageRequest::new(None, None, Some(2), None).unwrap();
+        let page = Page::new(items, &request, 10);
+        assert!(page.has_previous_page);
+        assert_eq!(page.items.iter().map(|(_, i)| *i).collect::<Vec<_>>(), vec![1, 0]);
+    }
+
+    #[test]
+    fn chain_event_round_trip() {
+        let event = ChainEvent::Head(Slot {
+            slot: 64,
+            epoch: 2,
+            proposer_index: 7,
+            status: SlotStatus::Missed,
+        });
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"head","slot":64,"epoch":2,"proposer_index":7,"status":"missed"}"#
+        );
+        match serde_json::from_str::<ChainEvent>(&json).unwrap() {
+            ChainEvent::Head(slot) => assert_eq!(slot.status, SlotStatus::Missed),
+            event => panic!("Unexpected event {event:?}"),
+        }
+    }
+}
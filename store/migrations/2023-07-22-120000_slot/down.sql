@@ -0,0 +1 @@
+DROP TABLE IF EXISTS slot;
\ No newline at end of file
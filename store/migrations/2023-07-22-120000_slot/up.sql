@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS slot (
+    slot NUMERIC(20,0) NOT NULL PRIMARY KEY,
+    epoch_index NUMERIC(20,0) NOT NULL,
+    proposer_index NUMERIC(20,0) NOT NULL,
+    status VARCHAR NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS slot_epoch_index_idx ON slot (epoch_index);
+CREATE INDEX IF NOT EXISTS slot_proposer_index_idx ON slot (proposer_index);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS proposer_duty;
\ No newline at end of file
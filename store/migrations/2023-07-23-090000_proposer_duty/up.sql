@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS proposer_duty (
+    slot NUMERIC(20,0) NOT NULL PRIMARY KEY,
+    epoch_index NUMERIC(20,0) NOT NULL,
+    validator_index NUMERIC(20,0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS proposer_duty_epoch_index_idx ON proposer_duty (epoch_index);
+CREATE INDEX IF NOT EXISTS proposer_duty_validator_index_idx ON proposer_duty (validator_index);
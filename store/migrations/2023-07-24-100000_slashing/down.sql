@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS slashing;
+
+ALTER TABLE validator DROP COLUMN IF EXISTS slashed;
\ No newline at end of file
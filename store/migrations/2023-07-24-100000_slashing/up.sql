@@ -0,0 +1,14 @@
+ALTER TABLE validator ADD COLUMN IF NOT EXISTS slashed BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TABLE IF NOT EXISTS slashing (
+    slot NUMERIC(20,0) NOT NULL,
+    epoch_index NUMERIC(20,0) NOT NULL,
+    kind VARCHAR NOT NULL,
+    validator_index NUMERIC(20,0) NOT NULL,
+    proposer_index NUMERIC(20,0) NOT NULL,
+    whistleblower_index NUMERIC(20,0) NOT NULL,
+    evidence VARCHAR NOT NULL,
+    PRIMARY KEY (slot, validator_index, kind)
+);
+
+CREATE INDEX IF NOT EXISTS slashing_validator_index_idx ON slashing (validator_index);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS voluntary_exit;
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS voluntary_exit (
+    validator_index NUMERIC(20,0) NOT NULL PRIMARY KEY,
+    epoch_index NUMERIC(20,0) NOT NULL,
+    inclusion_slot NUMERIC(20,0)
+);
+
+CREATE INDEX IF NOT EXISTS voluntary_exit_inclusion_slot_idx ON voluntary_exit (inclusion_slot);
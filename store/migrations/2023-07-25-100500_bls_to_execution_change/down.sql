@@ -0,0 +1 @@
+DROP TABLE IF EXISTS bls_to_execution_change;
\ No newline at end of file
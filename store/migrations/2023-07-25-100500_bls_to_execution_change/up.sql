@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS bls_to_execution_change (
+    validator_index NUMERIC(20,0) NOT NULL PRIMARY KEY,
+    from_bls_pubkey VARCHAR NOT NULL,
+    to_execution_address VARCHAR NOT NULL,
+    inclusion_slot NUMERIC(20,0)
+);
+
+CREATE INDEX IF NOT EXISTS bls_to_execution_change_inclusion_slot_idx ON bls_to_execution_change (inclusion_slot);
+CREATE INDEX IF NOT EXISTS bls_to_execution_change_to_execution_address_idx ON bls_to_execution_change (to_execution_address);
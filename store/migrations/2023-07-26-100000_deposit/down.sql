@@ -0,0 +1 @@
+DROP TABLE IF EXISTS deposit;
\ No newline at end of file
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS deposit (
+    slot NUMERIC(20,0) NOT NULL,
+    position NUMERIC(20,0) NOT NULL,
+    pubkey VARCHAR NOT NULL,
+    withdrawal_credentials VARCHAR NOT NULL,
+    amount NUMERIC(20,0) NOT NULL,
+    PRIMARY KEY (slot, position)
+);
+
+CREATE INDEX IF NOT EXISTS deposit_pubkey_idx ON deposit (pubkey);
+CREATE INDEX IF NOT EXISTS deposit_withdrawal_credentials_idx ON deposit (withdrawal_credentials);
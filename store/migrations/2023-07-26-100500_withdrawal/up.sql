@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS withdrawal (
+    index NUMERIC(20,0) NOT NULL PRIMARY KEY,
+    slot NUMERIC(20,0) NOT NULL,
+    validator_index NUMERIC(20,0) NOT NULL,
+    address VARCHAR NOT NULL,
+    amount NUMERIC(20,0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS withdrawal_validator_index_idx ON withdrawal (validator_index);
+CREATE INDEX IF NOT EXISTS withdrawal_address_idx ON withdrawal (address);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS execution_payload;
\ No newline at end of file
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS execution_payload (
+    slot NUMERIC(20,0) NOT NULL PRIMARY KEY,
+    proposer_index NUMERIC(20,0) NOT NULL,
+    block_number NUMERIC(20,0) NOT NULL,
+    block_hash VARCHAR NOT NULL,
+    parent_hash VARCHAR NOT NULL,
+    fee_recipient VARCHAR NOT NULL,
+    gas_used NUMERIC(20,0) NOT NULL,
+    gas_limit NUMERIC(20,0) NOT NULL,
+    base_fee_per_gas NUMERIC(20,0) NOT NULL,
+    timestamp NUMERIC(20,0) NOT NULL,
+    transaction_count NUMERIC(20,0) NOT NULL,
+    blob_gas_used NUMERIC(20,0),
+    excess_blob_gas NUMERIC(20,0)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS execution_payload_block_hash_idx ON execution_payload (block_hash);
+CREATE INDEX IF NOT EXISTS execution_payload_block_number_idx ON execution_payload (block_number);
+CREATE INDEX IF NOT EXISTS execution_payload_proposer_index_idx ON execution_payload (proposer_index);
+CREATE INDEX IF NOT EXISTS execution_payload_fee_recipient_idx ON execution_payload (fee_recipient);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS relay_payload;
\ No newline at end of file
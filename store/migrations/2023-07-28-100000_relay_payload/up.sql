@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS relay_payload (
+    relay VARCHAR NOT NULL,
+    slot NUMERIC(20,0) NOT NULL,
+    block_hash VARCHAR NOT NULL,
+    builder_pubkey VARCHAR NOT NULL,
+    proposer_pubkey VARCHAR NOT NULL,
+    proposer_fee_recipient VARCHAR NOT NULL,
+    value NUMERIC(39,0) NOT NULL,
+    PRIMARY KEY (relay, slot, block_hash)
+);
+
+CREATE INDEX IF NOT EXISTS relay_payload_block_hash_idx ON relay_payload (block_hash);
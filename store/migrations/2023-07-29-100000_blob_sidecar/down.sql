@@ -0,0 +1 @@
+DROP TABLE IF EXISTS blob_sidecar;
\ No newline at end of file
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS blob_sidecar (
+    slot NUMERIC(20,0) NOT NULL,
+    blob_index NUMERIC(20,0) NOT NULL,
+    epoch_index NUMERIC(20,0) NOT NULL,
+    proposer_index NUMERIC(20,0) NOT NULL,
+    kzg_commitment VARCHAR NOT NULL,
+    kzg_proof VARCHAR NOT NULL,
+    size NUMERIC(20,0) NOT NULL,
+    blob VARCHAR,
+    PRIMARY KEY (slot, blob_index)
+);
+
+CREATE INDEX IF NOT EXISTS blob_sidecar_epoch_index_idx ON blob_sidecar (epoch_index);
+CREATE INDEX IF NOT EXISTS blob_sidecar_proposer_index_idx ON blob_sidecar (proposer_index);
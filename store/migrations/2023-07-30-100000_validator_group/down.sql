@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS validator_group_member;
+DROP TABLE IF EXISTS validator_group;
\ No newline at end of file
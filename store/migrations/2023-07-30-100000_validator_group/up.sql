@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS validator_group (
+    name VARCHAR NOT NULL PRIMARY KEY,
+    labels VARCHAR[] NOT NULL DEFAULT '{}'
+);
+
+CREATE TABLE IF NOT EXISTS validator_group_member (
+    group_name VARCHAR NOT NULL REFERENCES validator_group (name) ON DELETE CASCADE,
+    kind VARCHAR NOT NULL,
+    value VARCHAR NOT NULL,
+    PRIMARY KEY (group_name, kind, value)
+);
+
+CREATE INDEX IF NOT EXISTS validator_group_labels_idx ON validator_group USING GIN (labels);
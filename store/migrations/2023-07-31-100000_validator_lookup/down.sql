@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS validator_withdrawal_credentials_idx;
+DROP INDEX IF EXISTS validator_pubkey_idx;
+
+ALTER TABLE validator DROP COLUMN IF EXISTS withdrawal_credentials;
\ No newline at end of file
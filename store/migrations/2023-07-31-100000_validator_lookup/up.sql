@@ -0,0 +1,4 @@
+ALTER TABLE validator ADD COLUMN IF NOT EXISTS withdrawal_credentials VARCHAR NOT NULL DEFAULT '';
+
+CREATE INDEX IF NOT EXISTS validator_pubkey_idx ON validator (pubkey varchar_pattern_ops);
+CREATE INDEX IF NOT EXISTS validator_withdrawal_credentials_idx ON validator (withdrawal_credentials);
@@ -0,0 +1,4 @@
+ALTER TABLE validator DROP COLUMN IF EXISTS withdrawable_epoch;
+ALTER TABLE validator DROP COLUMN IF EXISTS activation_eligibility_epoch;
+
+DROP TABLE IF EXISTS block_header;
\ No newline at end of file
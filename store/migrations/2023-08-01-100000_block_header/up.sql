@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS block_header (
+    slot NUMERIC(20,0) PRIMARY KEY,
+    proposer_index NUMERIC(20,0) NOT NULL,
+    root VARCHAR NOT NULL,
+    parent_root VARCHAR NOT NULL,
+    state_root VARCHAR NOT NULL,
+    body_root VARCHAR NOT NULL,
+    signature VARCHAR NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS block_header_root_idx ON block_header (root);
+CREATE INDEX IF NOT EXISTS block_header_state_root_idx ON block_header (state_root);
+
+ALTER TABLE validator ADD COLUMN IF NOT EXISTS activation_eligibility_epoch NUMERIC(20,0) NOT NULL DEFAULT 18446744073709551615;
+ALTER TABLE validator ADD COLUMN IF NOT EXISTS withdrawable_epoch NUMERIC(20,0) NOT NULL DEFAULT 18446744073709551615;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS api_key (
+    name VARCHAR PRIMARY KEY,
+    key_hash VARCHAR NOT NULL UNIQUE,
+    scopes VARCHAR[] NOT NULL,
+    rate_limit NUMERIC(20,0) NOT NULL,
+    request_count NUMERIC(20,0) NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMPTZ,
+    revoked BOOLEAN NOT NULL DEFAULT false
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS cache_entry;
\ No newline at end of file
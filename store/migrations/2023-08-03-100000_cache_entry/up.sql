@@ -0,0 +1,7 @@
+CREATE UNLOGGED TABLE IF NOT EXISTS cache_entry (
+    key VARCHAR PRIMARY KEY,
+    epoch NUMERIC(20,0) NOT NULL,
+    value VARCHAR NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS cache_entry_epoch_idx ON cache_entry (epoch);
@@ -0,0 +1 @@
+-- Lowercasing is not reversible
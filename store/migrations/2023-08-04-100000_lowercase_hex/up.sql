@@ -0,0 +1,3 @@
+UPDATE deposit SET pubkey = LOWER(pubkey), withdrawal_credentials = LOWER(withdrawal_credentials)
+WHERE pubkey <> LOWER(pubkey) OR withdrawal_credentials <> LOWER(withdrawal_credentials);
+UPDATE validator SET pubkey = LOWER(pubkey) WHERE pubkey <> LOWER(pubkey);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS relay_failure;
\ No newline at end of file
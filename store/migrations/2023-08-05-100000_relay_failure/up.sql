@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS relay_failure (
+    relay VARCHAR NOT NULL,
+    slot NUMERIC(20,0) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (relay, slot)
+);
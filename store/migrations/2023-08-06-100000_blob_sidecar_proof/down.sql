@@ -0,0 +1,2 @@
+UPDATE blob_sidecar SET kzg_proof = '' WHERE kzg_proof IS NULL;
+ALTER TABLE blob_sidecar ALTER COLUMN kzg_proof SET NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE blob_sidecar ALTER COLUMN kzg_proof DROP NOT NULL;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS validator_attestation_count;
\ No newline at end of file
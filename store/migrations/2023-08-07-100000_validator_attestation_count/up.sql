@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS validator_attestation_count (
+    validator_index NUMERIC(20,0) PRIMARY KEY NOT NULL,
+    attestations BIGINT NOT NULL
+);
+
+INSERT INTO validator_attestation_count (validator_index, attestations)
+SELECT validator_index, COUNT(*) FROM attestation WHERE attested GROUP BY validator_index
+ON CONFLICT (validator_index) DO UPDATE SET attestations = EXCLUDED.attestations;
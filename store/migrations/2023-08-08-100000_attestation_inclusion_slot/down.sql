@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS attestation_inclusion_slot_idx;
+
+ALTER TABLE attestation DROP COLUMN IF EXISTS inclusion_slot;
@@ -0,0 +1,3 @@
+ALTER TABLE attestation ADD COLUMN IF NOT EXISTS inclusion_slot NUMERIC(20,0);
+
+CREATE INDEX IF NOT EXISTS attestation_inclusion_slot_idx ON attestation (inclusion_slot);
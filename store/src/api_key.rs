@@ -0,0 +1,155 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{model::ApiKey, ApiKeyRepository};
+use tokio_postgres::Row;
+
+pub struct PostgresApiKey {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub rate_limit: u64,
+    pub request_count: u64,
+    pub last_used_at: Option<i64>,
+    pub revoked: bool,
+}
+
+impl TryFrom<Row> for PostgresApiKey {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresApiKey {
+            name: value.try_get("name")?,
+            scopes: value.try_get("scopes")?,
+            rate_limit: value
+                .get::<_, Decimal>("rate_limit")
+                .to_u64()
+                .ok_or(anyhow!("Invalid rate limit"))?,
+            request_count: value
+                .get::<_, Decimal>("request_count")
+                .to_u64()
+                .ok_or(anyhow!("Invalid request count"))?,
+            last_used_at: value.try_get("last_used_at")?,
+            revoked: value.try_get("revoked")?,
+        })
+    }
+}
+
+impl TryFrom<PostgresApiKey> for ApiKey {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresApiKey) -> Result<Self, Self::Error> {
+        Ok(ApiKey {
+            name: value.name,
+            scopes: value
+                .scopes
+                .iter()
+                .map(|scope| scope.parse())
+                .collect::<Result<Vec<_>>>()?,
+            rate_limit: value.rate_limit,
+            request_count: value.request_count,
+            last_used_at: value.last_used_at.map(u64::try_from).transpose()?,
+            revoked: value.revoked,
+        })
+    }
+}
+
+pub struct PostgresApiKeyRepository {
+    pool: Pool,
+}
+
+impl PostgresApiKeyRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for PostgresApiKeyRepository {
+    #[tracing::instrument(skip(self, key, key_hash))]
+    async fn create_api_key(&self, key: &ApiKey, key_hash: &str) -> Result<bool> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["api_key", "create_api_key"]);
+        let client = self.pool.get().await?;
+        let scopes = key.scopes.iter().map(|scope| scope.to_string()).collect::<Vec<_>>();
+        let created = client
+            .execute(
+                "INSERT INTO api_key (name, key_hash, scopes, rate_limit)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (name) DO NOTHING",
+                &[&key.name, &key_hash, &scopes, &Decimal::from(key.rate_limit)],
+            )
+            .await?;
+        Ok(created > 0)
+    }
+
+    #[tracing::instrument(skip(self, key_hash))]
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["api_key", "get_api_key_by_hash"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT name, scopes, rate_limit, request_count, EXTRACT(EPOCH FROM last_used_at)::BIGINT AS last_used_at, revoked
+                FROM api_key
+                WHERE key_hash = $1",
+                &[&key_hash],
+            )
+            .await?;
+        row.map(PostgresApiKey::try_from)
+            .transpose()?
+            .map(ApiKey::try_from)
+            .transpose()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_api_keys(&self) -> Result<Vec<ApiKey>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["api_key", "get_api_keys"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT name, scopes, rate_limit, request_count, EXTRACT(EPOCH FROM last_used_at)::BIGINT AS last_used_at, revoked
+                FROM api_key
+                ORDER BY name",
+                &[],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresApiKey::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(ApiKey::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn revoke_api_key(&self, name: &str) -> Result<bool> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["api_key", "revoke_api_key"]);
+        let client = self.pool.get().await?;
+        let revoked = client
+            .execute(
+                "UPDATE api_key SET revoked = true WHERE name = $1 AND NOT revoked",
+                &[&name],
+            )
+            .await?;
+        Ok(revoked > 0)
+    }
+
+    #[tracing::instrument(skip(self, usage))]
+    async fn record_api_key_usage(&self, usage: &[(String, u64)]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["api_key", "record_api_key_usage"]);
+        if usage.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let names = usage.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        let counts = usage.iter().map(|(_, count)| Decimal::from(*count)).collect::<Vec<_>>();
+        client
+            .execute(
+                "UPDATE api_key SET request_count = api_key.request_count + usage.count, last_used_at = NOW()
+                FROM UNNEST($1::VARCHAR[], $2::NUMERIC(20,0)[]) AS usage(name, count)
+                WHERE api_key.name = usage.name",
+                &[&names, &counts],
+            )
+            .await?;
+        Ok(())
+    }
+}
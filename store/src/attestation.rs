@@ -1,8 +1,72 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
-use rust_decimal::Decimal;
-use service::{model::AttestationData, AttestationRepository};
+use futures_util::stream::BoxStream;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{AttestationData, ValidatorHistory},
+    AttestationRepository,
+};
+use tokio_postgres::Row;
+
+use crate::query_stream;
+
+pub struct PostgresAttestation {
+    pub epoch_index: u64,
+    pub validator_index: u64,
+    pub slot: u64,
+    pub committee_index: i16,
+    pub attested: bool,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl TryFrom<Row> for PostgresAttestation {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresAttestation {
+            epoch_index: value
+                .get::<_, Decimal>("epoch_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid epoch index"))?,
+            validator_index: value
+                .get::<_, Decimal>("validator_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid validator index"))?,
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            committee_index: value.try_get("committee_index")?,
+            attested: value.try_get("attested")?,
+            inclusion_slot: value
+                .get::<_, Option<Decimal>>("inclusion_slot")
+                .map(|slot| slot.to_u64().ok_or(anyhow!("Invalid inclusion slot")))
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<PostgresAttestation> for AttestationData {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresAttestation) -> Result<Self, Self::Error> {
+        Ok(AttestationData {
+            epoch: value.epoch_index,
+            validator: value.validator_index,
+            slot: value.slot,
+            committee_index: u8::try_from(value.committee_index)?,
+            attested: value.attested,
+            inclusion_slot: value.inclusion_slot,
+        })
+    }
+}
+
+// Keeps the per validator count of attested epochs in step with the rows actually inserted, so validator queries
+// don't have to aggregate the attestation table
+const COUNT_INSERTED: &str = "INSERT INTO validator_attestation_count (validator_index, attestations)
+    SELECT validator_index, COUNT(*) FROM inserted WHERE attested GROUP BY validator_index
+    ON CONFLICT (validator_index) DO UPDATE SET attestations = validator_attestation_count.attestations + EXCLUDED.attestations";
 
 pub struct PostgresAttestationRepository {
     pool: Pool,
@@ -16,7 +80,9 @@ impl PostgresAttestationRepository {
 
 #[async_trait]
 impl AttestationRepository for PostgresAttestationRepository {
+    #[tracing::instrument(skip(self))]
     async fn get_attestation_for_slot_and_validator(&self, slot: u64, validator: u64) -> Result<Option<bool>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "get_attestation_for_slot_and_validator"]);
         let client = self.pool.get().await?;
         let row = client
             .query_opt(
@@ -28,7 +94,10 @@ impl AttestationRepository for PostgresAttestationRepository {
         Ok(row.map(|row| row.get("attested")))
     }
 
+    #[tracing::instrument(skip(self))]
     async fn get_attestation_for_epoch_and_validator(&self, epoch: u64, validator: u64) -> Result<Option<bool>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["attestation", "get_attestation_for_epoch_and_validator"]);
         let client = self.pool.get().await?;
         let row = client
             .query_opt(
@@ -40,7 +109,77 @@ impl AttestationRepository for PostgresAttestationRepository {
         Ok(row.map(|row| row.get("attested")))
     }
 
+    #[tracing::instrument(skip(self))]
+    async fn get_attestation(&self, epoch: u64, validator: u64) -> Result<Option<AttestationData>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "get_attestation"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT epoch_index, validator_index, slot, committee_index, attested, inclusion_slot FROM attestation
+                WHERE epoch_index = $1 AND validator_index = $2",
+                &[&Decimal::from(epoch), &Decimal::from(validator)],
+            )
+            .await?;
+        row.map(PostgresAttestation::try_from)
+            .transpose()?
+            .map(AttestationData::try_from)
+            .transpose()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_attestations_for_validator(&self, validator: u64, limit: u64) -> Result<Vec<AttestationData>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "get_attestations_for_validator"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT epoch_index, validator_index, slot, committee_index, attested, inclusion_slot FROM attestation
+                WHERE validator_index = $1
+                ORDER BY epoch_index DESC
+                LIMIT $2",
+                &[&Decimal::from(validator), &i64::try_from(limit)?],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresAttestation::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(AttestationData::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, epoch_validators))]
+    async fn get_attestation_data(&self, epoch_validators: &[(u64, u64)]) -> Result<Vec<AttestationData>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "get_attestation_data"]);
+        let client = self.pool.get().await?;
+        let epoch_indices = epoch_validators
+            .iter()
+            .map(|(epoch, _)| Decimal::from(*epoch))
+            .collect::<Vec<_>>();
+        let validator_indices = epoch_validators
+            .iter()
+            .map(|(_, validator)| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT attestation.epoch_index, attestation.validator_index, attestation.slot, attestation.committee_index, attestation.attested,
+                attestation.inclusion_slot
+                FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[]) as epochs(epoch_index, validator_index)
+                JOIN attestation
+                ON attestation.epoch_index = epochs.epoch_index AND attestation.validator_index = epochs.validator_index",
+                &[&epoch_indices, &validator_indices],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresAttestation::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(AttestationData::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, epoch_validators))]
     async fn get_attestations(&self, epoch_validators: &[(u64, u64)]) -> Result<Vec<Option<bool>>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "get_attestations"]);
         let client = self.pool.get().await?;
         let epoch_indices = epoch_validators
             .iter()
@@ -70,7 +209,9 @@ impl AttestationRepository for PostgresAttestationRepository {
         Ok(attestations)
     }
 
+    #[tracing::instrument(skip(self))]
     async fn attestation_count_for_slot(&self, slot: u64) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "attestation_count_for_slot"]);
         let client = self.pool.get().await?;
         let row = client
             .query_one(
@@ -83,26 +224,37 @@ impl AttestationRepository for PostgresAttestationRepository {
         Ok(u64::try_from(count)?)
     }
 
+    #[tracing::instrument(skip(self, data))]
     async fn create_attestation(&self, data: AttestationData) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "create_attestation"]);
         let client = self.pool.get().await?;
         client
             .execute(
-                "INSERT INTO attestation (epoch_index, validator_index, slot, committee_index, attestation)
-                VALUES ($1, $2, $3, $4, $5)
-                ON CONFLICT (epoch_index, validator_index) DO NOTHING",
+                &format!(
+                    "WITH inserted AS (
+                        INSERT INTO attestation (epoch_index, validator_index, slot, committee_index, attested, inclusion_slot)
+                        VALUES ($1, $2, $3, $4, $5, $6)
+                        ON CONFLICT (epoch_index, validator_index) DO NOTHING
+                        RETURNING validator_index, attested
+                    )
+                    {COUNT_INSERTED}"
+                ),
                 &[
                     &Decimal::from(data.epoch),
                     &Decimal::from(data.validator),
                     &Decimal::from(data.slot),
                     &(data.committee_index as i16),
                     &data.attested,
+                    &data.inclusion_slot.map(Decimal::from),
                 ],
             )
             .await?;
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, batch))]
     async fn create_attestation_batch(&self, batch: &[AttestationData]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "create_attestation_batch"]);
         if batch.is_empty() {
             return Ok(());
         }
@@ -115,20 +267,87 @@ impl AttestationRepository for PostgresAttestationRepository {
         let slots = batch.iter().map(|data| Decimal::from(data.slot)).collect::<Vec<_>>();
         let committee_indices = batch.iter().map(|data| data.committee_index as i16).collect::<Vec<_>>();
         let attested = batch.iter().map(|data| data.attested).collect::<Vec<_>>();
+        let inclusion_slots = batch
+            .iter()
+            .map(|data| data.inclusion_slot.map(Decimal::from))
+            .collect::<Vec<_>>();
         client
             .execute(
-                "INSERT INTO attestation (epoch_index, validator_index, slot, committee_index, attested)
-                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::NUMERIC(20,0)[], $4::SMALLINT[], $5::BOOLEAN[])
-                ON CONFLICT (epoch_index, validator_index) DO NOTHING",
+                &format!(
+                    "WITH inserted AS (
+                        INSERT INTO attestation (epoch_index, validator_index, slot, committee_index, attested, inclusion_slot)
+                        SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::NUMERIC(20,0)[], $4::SMALLINT[], $5::BOOLEAN[], $6::NUMERIC(20,0)[])
+                        ON CONFLICT (epoch_index, validator_index) DO NOTHING
+                        RETURNING validator_index, attested
+                    )
+                    {COUNT_INSERTED}"
+                ),
                 &[
                     &epoch_indices,
                     &validator_indices,
                     &slots,
                     &committee_indices,
                     &attested,
+                    &inclusion_slots,
                 ],
             )
             .await?;
         Ok(())
     }
+
+    #[tracing::instrument(skip(self))]
+    async fn stream_attestations(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<AttestationData>>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "stream_attestations"]);
+        query_stream(
+            &self.pool,
+            "SELECT epoch_index, validator_index, slot, committee_index, attested, inclusion_slot FROM attestation
+            WHERE epoch_index >= $1 AND epoch_index <= $2
+            ORDER BY epoch_index, validator_index",
+            vec![Decimal::from(from_epoch), Decimal::from(to_epoch)],
+            |row| AttestationData::try_from(PostgresAttestation::try_from(row)?),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn stream_validator_history(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<BoxStream<'static, Result<ValidatorHistory>>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["attestation", "stream_validator_history"]);
+        query_stream(
+            &self.pool,
+            "SELECT attestation.epoch_index, attestation.validator_index, attestation.attested,
+                COUNT(slot.slot) FILTER (WHERE slot.status = 'proposed') AS proposed,
+                COUNT(slot.slot) FILTER (WHERE slot.status = 'missed') AS missed
+            FROM attestation
+            LEFT JOIN slot
+            ON slot.epoch_index = attestation.epoch_index AND slot.proposer_index = attestation.validator_index
+            WHERE attestation.epoch_index >= $1 AND attestation.epoch_index <= $2
+            GROUP BY attestation.epoch_index, attestation.validator_index, attestation.attested
+            ORDER BY attestation.epoch_index, attestation.validator_index",
+            vec![Decimal::from(from_epoch), Decimal::from(to_epoch)],
+            |row| {
+                Ok(ValidatorHistory {
+                    epoch: row
+                        .get::<_, Decimal>("epoch_index")
+                        .to_u64()
+                        .ok_or(anyhow!("Invalid epoch index"))?,
+                    validator_index: row
+                        .get::<_, Decimal>("validator_index")
+                        .to_u64()
+                        .ok_or(anyhow!("Invalid validator index"))?,
+                    attested: row.try_get("attested")?,
+                    proposed: u64::try_from(row.get::<_, i64>("proposed"))?,
+                    missed: u64::try_from(row.get::<_, i64>("missed"))?,
+                })
+            },
+        )
+        .await
+    }
 }
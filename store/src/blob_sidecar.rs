@@ -0,0 +1,209 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{BlobSidecar, BlobThroughput, Page, PageRequest, SortDirection},
+    BlobSidecarRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresBlobSidecar {
+    pub slot: u64,
+    pub blob_index: u64,
+    pub epoch_index: u64,
+    pub proposer_index: u64,
+    pub kzg_commitment: String,
+    pub kzg_proof: Option<String>,
+    pub size: u64,
+    pub blob: Option<String>,
+}
+
+impl TryFrom<Row> for PostgresBlobSidecar {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresBlobSidecar {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            blob_index: value
+                .get::<_, Decimal>("blob_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid blob index"))?,
+            epoch_index: value
+                .get::<_, Decimal>("epoch_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid epoch index"))?,
+            proposer_index: value
+                .get::<_, Decimal>("proposer_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid proposer index"))?,
+            kzg_commitment: value.try_get("kzg_commitment")?,
+            kzg_proof: value.try_get("kzg_proof")?,
+            size: value
+                .get::<_, Decimal>("size")
+                .to_u64()
+                .ok_or(anyhow!("Invalid size"))?,
+            blob: value.try_get("blob")?,
+        })
+    }
+}
+
+impl From<PostgresBlobSidecar> for BlobSidecar {
+    fn from(value: PostgresBlobSidecar) -> Self {
+        BlobSidecar {
+            slot: value.slot,
+            epoch: value.epoch_index,
+            proposer_index: value.proposer_index,
+            index: value.blob_index,
+            kzg_commitment: value.kzg_commitment,
+            kzg_proof: value.kzg_proof,
+            size: value.size,
+            blob: value.blob,
+        }
+    }
+}
+
+fn blob_throughput_from_row(row: Row) -> Result<BlobThroughput> {
+    Ok(BlobThroughput {
+        block_count: u64::try_from(row.get::<_, i64>("block_count"))?,
+        blob_count: u64::try_from(row.get::<_, i64>("blob_count"))?,
+        total_size: row
+            .get::<_, Decimal>("total_size")
+            .to_u64()
+            .ok_or(anyhow!("Invalid total size"))?,
+    })
+}
+
+pub struct PostgresBlobSidecarRepository {
+    pool: Pool,
+}
+
+impl PostgresBlobSidecarRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BlobSidecarRepository for PostgresBlobSidecarRepository {
+    #[tracing::instrument(skip(self, sidecars))]
+    async fn create_blob_sidecars(&self, sidecars: &[BlobSidecar]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["blob_sidecar", "create_blob_sidecars"]);
+        if sidecars.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let slots = sidecars.iter().map(|data| Decimal::from(data.slot)).collect::<Vec<_>>();
+        let blob_indices = sidecars
+            .iter()
+            .map(|data| Decimal::from(data.index))
+            .collect::<Vec<_>>();
+        let epoch_indices = sidecars
+            .iter()
+            .map(|data| Decimal::from(data.epoch))
+            .collect::<Vec<_>>();
+        let proposer_indices = sidecars
+            .iter()
+            .map(|data| Decimal::from(data.proposer_index))
+            .collect::<Vec<_>>();
+        let kzg_commitments = sidecars.iter().map(|data| &data.kzg_commitment).collect::<Vec<_>>();
+        let kzg_proofs = sidecars.iter().map(|data| data.kzg_proof.as_ref()).collect::<Vec<_>>();
+        let sizes = sidecars.iter().map(|data| Decimal::from(data.size)).collect::<Vec<_>>();
+        let blobs = sidecars.iter().map(|data| data.blob.as_ref()).collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO blob_sidecar (slot, blob_index, epoch_index, proposer_index, kzg_commitment, kzg_proof, size, blob)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::NUMERIC(20,0)[], $4::NUMERIC(20,0)[], $5::VARCHAR[], $6::VARCHAR[], $7::NUMERIC(20,0)[], $8::VARCHAR[])
+                ON CONFLICT (slot, blob_index) DO NOTHING",
+                &[
+                    &slots,
+                    &blob_indices,
+                    &epoch_indices,
+                    &proposer_indices,
+                    &kzg_commitments,
+                    &kzg_proofs,
+                    &sizes,
+                    &blobs,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_blob_sidecars(&self, slot: u64) -> Result<Vec<BlobSidecar>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["blob_sidecar", "get_blob_sidecars"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT slot, blob_index, epoch_index, proposer_index, kzg_commitment, kzg_proof, size, blob
+                FROM blob_sidecar
+                WHERE slot = $1
+                ORDER BY blob_index",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        let sidecars = rows
+            .into_iter()
+            .map(PostgresBlobSidecar::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(BlobSidecar::from)
+            .collect();
+        Ok(sidecars)
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_blob_sidecar_page(&self, slot: u64, page: &PageRequest) -> Result<Page<BlobSidecar>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["blob_sidecar", "get_blob_sidecar_page"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT slot, blob_index, epoch_index, proposer_index, kzg_commitment, kzg_proof, size, blob,
+                blob_index AS cursor_value, blob_index AS cursor_index
+            FROM blob_sidecar
+            WHERE slot = $1",
+            &[&Decimal::from(slot)],
+            SortDirection::Asc,
+            page,
+            |row| Ok(BlobSidecar::from(PostgresBlobSidecar::try_from(row)?)),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_blob_throughput_for_epoch(&self, epoch: u64) -> Result<BlobThroughput> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["blob_sidecar", "get_blob_throughput_for_epoch"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_one(
+                "SELECT COUNT(DISTINCT slot) AS block_count, COUNT(*) AS blob_count, COALESCE(SUM(size), 0) AS total_size
+                FROM blob_sidecar
+                WHERE epoch_index = $1",
+                &[&Decimal::from(epoch)],
+            )
+            .await?;
+        blob_throughput_from_row(row)
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_blob_throughput_for_proposers(&self, validators: &[u64]) -> Result<BlobThroughput> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["blob_sidecar", "get_blob_throughput_for_proposers"]);
+        let client = self.pool.get().await?;
+        let validators = validators.iter().map(|index| Decimal::from(*index)).collect::<Vec<_>>();
+        let row = client
+            .query_one(
+                "SELECT COUNT(DISTINCT slot) AS block_count, COUNT(*) AS blob_count, COALESCE(SUM(size), 0) AS total_size
+                FROM blob_sidecar
+                WHERE proposer_index = ANY($1::NUMERIC(20,0)[])",
+                &[&validators],
+            )
+            .await?;
+        blob_throughput_from_row(row)
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{model::BlockHeader, BlockHeaderRepository};
+use tokio_postgres::Row;
+
+pub struct PostgresBlockHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub root: String,
+    pub parent_root: String,
+    pub state_root: String,
+    pub body_root: String,
+    pub signature: String,
+}
+
+impl TryFrom<Row> for PostgresBlockHeader {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresBlockHeader {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            proposer_index: value
+                .get::<_, Decimal>("proposer_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid proposer index"))?,
+            root: value.try_get("root")?,
+            parent_root: value.try_get("parent_root")?,
+            state_root: value.try_get("state_root")?,
+            body_root: value.try_get("body_root")?,
+            signature: value.try_get("signature")?,
+        })
+    }
+}
+
+impl From<PostgresBlockHeader> for BlockHeader {
+    fn from(value: PostgresBlockHeader) -> Self {
+        BlockHeader {
+            slot: value.slot,
+            proposer_index: value.proposer_index,
+            root: value.root,
+            parent_root: value.parent_root,
+            state_root: value.state_root,
+            body_root: value.body_root,
+            signature: value.signature,
+        }
+    }
+}
+
+pub struct PostgresBlockHeaderRepository {
+    pool: Pool,
+}
+
+impl PostgresBlockHeaderRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BlockHeaderRepository for PostgresBlockHeaderRepository {
+    #[tracing::instrument(skip(self, header))]
+    async fn create_block_header(&self, header: &BlockHeader) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "create_block_header"]);
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO block_header (slot, proposer_index, root, parent_root, state_root, body_root, signature)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (slot) DO UPDATE SET proposer_index = EXCLUDED.proposer_index, root = EXCLUDED.root, parent_root = EXCLUDED.parent_root, state_root = EXCLUDED.state_root, body_root = EXCLUDED.body_root, signature = EXCLUDED.signature",
+                &[
+                    &Decimal::from(header.slot),
+                    &Decimal::from(header.proposer_index),
+                    &header.root,
+                    &header.parent_root,
+                    &header.state_root,
+                    &header.body_root,
+                    &header.signature,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_block_header(&self, slot: u64) -> Result<Option<BlockHeader>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "get_block_header"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, root, parent_root, state_root, body_root, signature
+                FROM block_header
+                WHERE slot = $1",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresBlockHeader::try_from)
+            .transpose()?
+            .map(BlockHeader::from))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_block_header_by_root(&self, root: &str) -> Result<Option<BlockHeader>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "get_block_header_by_root"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, root, parent_root, state_root, body_root, signature
+                FROM block_header
+                WHERE root = $1",
+                &[&root],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresBlockHeader::try_from)
+            .transpose()?
+            .map(BlockHeader::from))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_block_header_by_state_root(&self, state_root: &str) -> Result<Option<BlockHeader>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "get_block_header_by_state_root"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, root, parent_root, state_root, body_root, signature
+                FROM block_header
+                WHERE state_root = $1",
+                &[&state_root],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresBlockHeader::try_from)
+            .transpose()?
+            .map(BlockHeader::from))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_latest_block_header(&self) -> Result<Option<BlockHeader>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "get_latest_block_header"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, root, parent_root, state_root, body_root, signature
+                FROM block_header
+                ORDER BY slot DESC
+                LIMIT 1",
+                &[],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresBlockHeader::try_from)
+            .transpose()?
+            .map(BlockHeader::from))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_block_header_before(&self, slot: u64) -> Result<Option<BlockHeader>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "get_block_header_before"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, root, parent_root, state_root, body_root, signature
+                FROM block_header
+                WHERE slot < $1
+                ORDER BY slot DESC
+                LIMIT 1",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresBlockHeader::try_from)
+            .transpose()?
+            .map(BlockHeader::from))
+    }
+
+    // Everything derived from the block at a slot goes in one statement, so a slot is never left half replaced
+    #[tracing::instrument(skip(self))]
+    async fn delete_block(&self, slot: u64) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["block_header", "delete_block"]);
+        let client = self.pool.get().await?;
+        // Attestations first included by the replaced block are dropped along with their share of the per validator
+        // counts, the replacing block inserts its own again
+        client
+            .execute(
+                "WITH proposers AS (DELETE FROM proposer WHERE slot = $1),
+                payloads AS (DELETE FROM execution_payload WHERE slot = $1),
+                withdrawals AS (DELETE FROM withdrawal WHERE slot = $1),
+                deposits AS (DELETE FROM deposit WHERE slot = $1),
+                slashings AS (DELETE FROM slashing WHERE slot = $1),
+                blobs AS (DELETE FROM blob_sidecar WHERE slot = $1),
+                exits AS (UPDATE voluntary_exit SET inclusion_slot = NULL WHERE inclusion_slot = $1),
+                changes AS (UPDATE bls_to_execution_change SET inclusion_slot = NULL WHERE inclusion_slot = $1),
+                attestations AS (DELETE FROM attestation WHERE inclusion_slot = $1 RETURNING validator_index, attested),
+                counts AS (
+                    UPDATE validator_attestation_count SET attestations = validator_attestation_count.attestations - removed.attestations
+                    FROM (SELECT validator_index, COUNT(*) AS attestations FROM attestations WHERE attested GROUP BY validator_index) removed
+                    WHERE validator_attestation_count.validator_index = removed.validator_index
+                )
+                DELETE FROM block_header WHERE slot = $1",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        Ok(())
+    }
+}
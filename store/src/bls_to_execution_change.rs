@@ -0,0 +1,134 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{BlsToExecutionChange, Page, PageRequest, SortDirection},
+    BlsToExecutionChangeRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresBlsToExecutionChange {
+    pub validator_index: u64,
+    pub from_bls_pubkey: String,
+    pub to_execution_address: String,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl TryFrom<Row> for PostgresBlsToExecutionChange {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresBlsToExecutionChange {
+            validator_index: value
+                .get::<_, Decimal>("validator_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid validator index"))?,
+            from_bls_pubkey: value.try_get("from_bls_pubkey")?,
+            to_execution_address: value.try_get("to_execution_address")?,
+            inclusion_slot: value
+                .get::<_, Option<Decimal>>("inclusion_slot")
+                .map(|slot| slot.to_u64().ok_or(anyhow!("Invalid inclusion slot")))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<PostgresBlsToExecutionChange> for BlsToExecutionChange {
+    fn from(value: PostgresBlsToExecutionChange) -> Self {
+        BlsToExecutionChange {
+            validator_index: value.validator_index,
+            from_bls_pubkey: value.from_bls_pubkey,
+            to_execution_address: value.to_execution_address,
+            inclusion_slot: value.inclusion_slot,
+        }
+    }
+}
+
+pub struct PostgresBlsToExecutionChangeRepository {
+    pool: Pool,
+}
+
+impl PostgresBlsToExecutionChangeRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BlsToExecutionChangeRepository for PostgresBlsToExecutionChangeRepository {
+    #[tracing::instrument(skip(self, changes))]
+    async fn create_bls_to_execution_changes(&self, changes: &[BlsToExecutionChange]) -> Result<()> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["bls_to_execution_change", "create_bls_to_execution_changes"]);
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let validator_indices = changes
+            .iter()
+            .map(|change| Decimal::from(change.validator_index))
+            .collect::<Vec<_>>();
+        let from_bls_pubkeys = changes.iter().map(|change| &change.from_bls_pubkey).collect::<Vec<_>>();
+        let to_execution_addresses = changes
+            .iter()
+            .map(|change| &change.to_execution_address)
+            .collect::<Vec<_>>();
+        let inclusion_slots = changes
+            .iter()
+            .map(|change| change.inclusion_slot.map(Decimal::from))
+            .collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO bls_to_execution_change (validator_index, from_bls_pubkey, to_execution_address, inclusion_slot)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::VARCHAR[], $3::VARCHAR[], $4::NUMERIC(20,0)[])
+                ON CONFLICT (validator_index) DO UPDATE SET inclusion_slot = COALESCE(EXCLUDED.inclusion_slot, bls_to_execution_change.inclusion_slot)",
+                &[&validator_indices, &from_bls_pubkeys, &to_execution_addresses, &inclusion_slots],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_bls_to_execution_change(&self, validator: u64) -> Result<Option<BlsToExecutionChange>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["bls_to_execution_change", "get_bls_to_execution_change"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT validator_index, from_bls_pubkey, to_execution_address, inclusion_slot FROM bls_to_execution_change
+                WHERE validator_index = $1",
+                &[&Decimal::from(validator)],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresBlsToExecutionChange::try_from)
+            .transpose()?
+            .map(BlsToExecutionChange::from))
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_bls_to_execution_change_page(
+        &self,
+        included: bool,
+        page: &PageRequest,
+    ) -> Result<Page<BlsToExecutionChange>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["bls_to_execution_change", "get_bls_to_execution_change_page"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT validator_index, from_bls_pubkey, to_execution_address, inclusion_slot,
+                COALESCE(inclusion_slot, 0) AS cursor_value, validator_index AS cursor_index
+            FROM bls_to_execution_change
+            WHERE (inclusion_slot IS NOT NULL) = $1",
+            &[&included],
+            SortDirection::Desc,
+            page,
+            |row| Ok(BlsToExecutionChange::from(PostgresBlsToExecutionChange::try_from(row)?)),
+        )
+        .await
+    }
+}
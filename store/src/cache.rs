@@ -0,0 +1,53 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::Decimal;
+use service::CacheRepository;
+
+pub struct PostgresCacheRepository {
+    pool: Pool,
+}
+
+impl PostgresCacheRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CacheRepository for PostgresCacheRepository {
+    #[tracing::instrument(skip(self))]
+    async fn get_cache_entry(&self, key: &str) -> Result<Option<String>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["cache", "get_cache_entry"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt("SELECT value FROM cache_entry WHERE key = $1", &[&key])
+            .await?;
+        Ok(row.map(|row| row.try_get("value")).transpose()?)
+    }
+
+    #[tracing::instrument(skip(self, value))]
+    async fn set_cache_entry(&self, key: &str, epoch: u64, value: &str) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["cache", "set_cache_entry"]);
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO cache_entry (key, epoch, value)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (key) DO UPDATE SET epoch = EXCLUDED.epoch, value = EXCLUDED.value",
+                &[&key, &Decimal::from(epoch), &value],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_cache_entries_from_epoch(&self, epoch: u64) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["cache", "delete_cache_entries_from_epoch"]);
+        let client = self.pool.get().await?;
+        client
+            .execute("DELETE FROM cache_entry WHERE epoch >= $1", &[&Decimal::from(epoch)])
+            .await?;
+        Ok(())
+    }
+}
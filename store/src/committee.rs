@@ -2,9 +2,14 @@ use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
 use rust_decimal::{prelude::ToPrimitive, Decimal};
-use service::{model::Committee, CommitteeRepository};
+use service::{
+    model::{Committee, Page, PageRequest, SortDirection},
+    CommitteeRepository,
+};
 use tokio_postgres::{types::ToSql, Row};
 
+use crate::keyset_page;
+
 pub struct PostgresCommittee {
     pub index: i16,
     pub slot: u64,
@@ -60,7 +65,9 @@ impl PostgresCommitteeRepository {
 
 #[async_trait]
 impl CommitteeRepository for PostgresCommitteeRepository {
+    #[tracing::instrument(skip(self))]
     async fn get_committee(&self, slot: u64, index: u8) -> Result<Option<Committee>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "get_committee"]);
         let client = self.pool.get().await?;
         let row = client
             .query_opt(
@@ -75,14 +82,17 @@ impl CommitteeRepository for PostgresCommitteeRepository {
             .transpose()
     }
 
+    #[tracing::instrument(skip(self, inputs))]
     async fn get_committees(&self, inputs: &[(u64, u8)]) -> Result<Vec<Committee>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "get_committees"]);
         let client = self.pool.get().await?;
         let slots = inputs.iter().map(|(slot, _)| Decimal::from(*slot)).collect::<Vec<_>>();
         let indices = inputs.iter().map(|(_, index)| i16::from(*index)).collect::<Vec<_>>();
         let rows = client
             .query(
-                "SELECT index, slot, validators FROM UNNEST($1::NUMERIC(20,0)[], $2::SMALLINT[]) as committees(slot, index)
-                LEFT JOIN committee
+                "SELECT committee.index, committee.slot, committee.validators
+                FROM UNNEST($1::NUMERIC(20,0)[], $2::SMALLINT[]) as committees(slot, index)
+                JOIN committee
                 ON committee.slot = committees.slot AND committee.index = committees.index",
                 &[&slots, &indices],
             )
@@ -95,12 +105,15 @@ impl CommitteeRepository for PostgresCommitteeRepository {
             .collect()
     }
 
+    #[tracing::instrument(skip(self))]
     async fn get_committees_for_slot(&self, slot: u64) -> Result<Vec<Committee>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "get_committees_for_slot"]);
         let client = self.pool.get().await?;
         let rows = client
             .query(
                 "SELECT index, slot, validators FROM committee
-                WHERE slot = $1",
+                WHERE slot = $1
+                ORDER BY index",
                 &[&Decimal::from(slot)],
             )
             .await?;
@@ -112,7 +125,46 @@ impl CommitteeRepository for PostgresCommitteeRepository {
             .collect()
     }
 
+    #[tracing::instrument(skip(self, page))]
+    async fn get_committee_page_for_slot(&self, slot: u64, page: &PageRequest) -> Result<Page<Committee>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "get_committee_page_for_slot"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT index, slot, validators, index::NUMERIC AS cursor_value, index::NUMERIC AS cursor_index
+            FROM committee
+            WHERE slot = $1",
+            &[&Decimal::from(slot)],
+            SortDirection::Asc,
+            page,
+            |row| Committee::try_from(PostgresCommittee::try_from(row)?),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_committees_for_epoch(&self, epoch: u64) -> Result<Vec<Committee>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "get_committees_for_epoch"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT index, slot, validators FROM committee
+                WHERE slot >= $1 AND slot < $2
+                ORDER BY slot, index",
+                &[&Decimal::from(epoch * 32), &Decimal::from((epoch + 1) * 32)],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresCommittee::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Committee::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, committee))]
     async fn create_committee(&self, committee: &Committee) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "create_committee"]);
         let client = self.pool.get().await?;
         let validators = committee
             .validators
@@ -127,15 +179,13 @@ impl CommitteeRepository for PostgresCommitteeRepository {
                 &[&i16::from(committee.index), &Decimal::from(committee.slot), &validators],
             )
             .await?;
-        log::info!(
-            "Created committee with slot = {}, index = {}",
-            committee.slot,
-            committee.index
-        );
+        tracing::info!(slot = committee.slot, index = committee.index, "Created committee");
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, committees))]
     async fn create_committee_batch(&self, committees: &[Committee]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["committee", "create_committee_batch"]);
         if committees.is_empty() {
             return Ok(());
         }
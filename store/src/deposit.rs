@@ -0,0 +1,183 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{Deposit, Page, PageRequest, SortDirection},
+    DepositRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresDeposit {
+    pub slot: u64,
+    pub position: u64,
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub amount: u64,
+    pub validator_index: Option<u64>,
+}
+
+impl TryFrom<Row> for PostgresDeposit {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresDeposit {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            position: value
+                .get::<_, Decimal>("position")
+                .to_u64()
+                .ok_or(anyhow!("Invalid deposit position"))?,
+            pubkey: value.try_get("pubkey")?,
+            withdrawal_credentials: value.try_get("withdrawal_credentials")?,
+            amount: value
+                .get::<_, Decimal>("amount")
+                .to_u64()
+                .ok_or(anyhow!("Invalid deposit amount"))?,
+            validator_index: value
+                .get::<_, Option<Decimal>>("validator_index")
+                .map(|index| index.to_u64().ok_or(anyhow!("Invalid validator index")))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<PostgresDeposit> for Deposit {
+    fn from(value: PostgresDeposit) -> Self {
+        Deposit {
+            slot: value.slot,
+            position: value.position,
+            pubkey: value.pubkey,
+            withdrawal_credentials: value.withdrawal_credentials,
+            amount: value.amount,
+            validator_index: value.validator_index,
+        }
+    }
+}
+
+pub struct PostgresDepositRepository {
+    pool: Pool,
+}
+
+impl PostgresDepositRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DepositRepository for PostgresDepositRepository {
+    #[tracing::instrument(skip(self, deposits))]
+    async fn create_deposits(&self, deposits: &[Deposit]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["deposit", "create_deposits"]);
+        if deposits.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let slots = deposits
+            .iter()
+            .map(|deposit| Decimal::from(deposit.slot))
+            .collect::<Vec<_>>();
+        let positions = deposits
+            .iter()
+            .map(|deposit| Decimal::from(deposit.position))
+            .collect::<Vec<_>>();
+        let pubkeys = deposits.iter().map(|deposit| &deposit.pubkey).collect::<Vec<_>>();
+        let withdrawal_credentials = deposits
+            .iter()
+            .map(|deposit| &deposit.withdrawal_credentials)
+            .collect::<Vec<_>>();
+        let amounts = deposits
+            .iter()
+            .map(|deposit| Decimal::from(deposit.amount))
+            .collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO deposit (slot, position, pubkey, withdrawal_credentials, amount)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::VARCHAR[], $4::VARCHAR[], $5::NUMERIC(20,0)[])
+                ON CONFLICT (slot, position) DO NOTHING",
+                &[&slots, &positions, &pubkeys, &withdrawal_credentials, &amounts],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_recent_deposits_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Deposit>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["deposit", "get_recent_deposits_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT slot, position, pubkey, withdrawal_credentials, amount, validator_index FROM (
+                    SELECT deposit.slot, deposit.position, deposit.pubkey, deposit.withdrawal_credentials, deposit.amount, validator.index AS validator_index,
+                        ROW_NUMBER() OVER (PARTITION BY validator.index ORDER BY deposit.slot DESC, deposit.position DESC) AS rank
+                    FROM deposit
+                    INNER JOIN validator ON validator.pubkey = deposit.pubkey
+                    WHERE validator.index = ANY($1::NUMERIC(20,0)[])
+                ) AS deposits
+                WHERE rank <= $2
+                ORDER BY validator_index, slot DESC, position DESC",
+                &[&validators, &i64::try_from(limit)?],
+            )
+            .await?;
+        let deposits = rows
+            .into_iter()
+            .map(PostgresDeposit::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Deposit::from)
+            .collect();
+        Ok(deposits)
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_deposit_page_for_validator(&self, validator: u64, page: &PageRequest) -> Result<Page<Deposit>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["deposit", "get_deposit_page_for_validator"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT deposit.slot, deposit.position, deposit.pubkey, deposit.withdrawal_credentials, deposit.amount, validator.index AS validator_index,
+                deposit.slot AS cursor_value, deposit.position AS cursor_index
+            FROM deposit
+            INNER JOIN validator ON validator.pubkey = deposit.pubkey
+            WHERE validator.index = $1",
+            &[&Decimal::from(validator)],
+            SortDirection::Asc,
+            page,
+            |row| Ok(Deposit::from(PostgresDeposit::try_from(row)?)),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_deposit_page_for_withdrawal_credentials(
+        &self,
+        withdrawal_credentials: &[String],
+        page: &PageRequest,
+    ) -> Result<Page<Deposit>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["deposit", "get_deposit_page_for_withdrawal_credentials"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT deposit.slot, deposit.position, deposit.pubkey, deposit.withdrawal_credentials, deposit.amount, validator.index AS validator_index,
+                deposit.slot AS cursor_value, deposit.position AS cursor_index
+            FROM deposit
+            LEFT JOIN validator ON validator.pubkey = deposit.pubkey
+            WHERE deposit.withdrawal_credentials = ANY($1)",
+            &[&withdrawal_credentials],
+            SortDirection::Asc,
+            page,
+            |row| Ok(Deposit::from(PostgresDeposit::try_from(row)?)),
+        )
+        .await
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
+use futures_util::stream::BoxStream;
 use rust_decimal::{prelude::ToPrimitive, Decimal};
 use service::{model::Epoch, EpochRepository};
 use tokio_postgres::Row;
 
+use crate::query_stream;
+
 pub struct PostgresEpoch {
     pub index: u64,
     pub active_validators: u64,
@@ -59,7 +62,9 @@ impl PostgresEpochRepository {
 
 #[async_trait]
 impl EpochRepository for PostgresEpochRepository {
+    #[tracing::instrument(skip(self))]
     async fn get_epoch(&self, index: u64) -> Result<Option<Epoch>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["epoch", "get_epoch"]);
         let client = self.pool.get().await?;
         let row = client
             .query_opt(
@@ -83,7 +88,38 @@ impl EpochRepository for PostgresEpochRepository {
             .transpose()
     }
 
+    #[tracing::instrument(skip(self, indices))]
+    async fn get_epochs(&self, indices: &[u64]) -> Result<Vec<Epoch>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["epoch", "get_epochs"]);
+        let client = self.pool.get().await?;
+        let indices = indices.iter().map(|index| Decimal::from(*index)).collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT index, active_validators, total_validators, COALESCE(attestation.attestations, 0) as attestations
+                FROM epoch
+                LEFT JOIN (
+                    SELECT epoch_index, COUNT(attested) AS attestations
+                    FROM attestation
+                    WHERE attested = true AND epoch_index = ANY($1::NUMERIC(20,0)[])
+                    GROUP BY epoch_index
+                ) AS attestation
+                ON epoch.index = attestation.epoch_index
+                WHERE index = ANY($1::NUMERIC(20,0)[])
+                ORDER BY index",
+                &[&indices],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresEpoch::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Epoch::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self))]
     async fn create_epoch(&self, epoch_index: u64, active_validators: u64, total_validators: u64) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["epoch", "create_epoch"]);
         let client = self.pool.get().await?;
         client
             .execute(
@@ -100,7 +136,9 @@ impl EpochRepository for PostgresEpochRepository {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
     async fn current_epoch(&self) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["epoch", "current_epoch"]);
         let client = self.pool.get().await?;
         let row = client
             .query_one(
@@ -116,4 +154,26 @@ impl EpochRepository for PostgresEpochRepository {
             .ok_or(anyhow!("Invalid epoch index"))?;
         Ok(index)
     }
+
+    #[tracing::instrument(skip(self))]
+    async fn stream_epochs(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Epoch>>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["epoch", "stream_epochs"]);
+        query_stream(
+            &self.pool,
+            "SELECT index, active_validators, total_validators, COALESCE(attestation.attestations, 0) as attestations
+            FROM epoch
+            LEFT JOIN (
+                SELECT epoch_index, COUNT(attested) AS attestations
+                FROM attestation
+                WHERE attested = true AND epoch_index >= $1 AND epoch_index <= $2
+                GROUP BY epoch_index
+            ) AS attestation
+            ON epoch.index = attestation.epoch_index
+            WHERE index >= $1 AND index <= $2
+            ORDER BY index",
+            vec![Decimal::from(from_epoch), Decimal::from(to_epoch)],
+            |row| Epoch::try_from(PostgresEpoch::try_from(row)?),
+        )
+        .await
+    }
 }
@@ -0,0 +1,67 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use futures_util::{stream, StreamExt};
+use service::{model::ChainEvent, EventRepository};
+use tokio::sync::{broadcast, mpsc};
+use tokio_postgres::{AsyncMessage, NoTls};
+
+use crate::DbConfig;
+
+const CHANNEL: &str = "chain_event";
+
+pub struct PostgresEventRepository {
+    pool: Pool,
+}
+
+impl PostgresEventRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EventRepository for PostgresEventRepository {
+    #[tracing::instrument(skip(self, event))]
+    async fn publish_event(&self, event: &ChainEvent) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["event", "publish_event"]);
+        let client = self.pool.get().await?;
+        client
+            .execute("SELECT pg_notify($1, $2)", &[&CHANNEL, &serde_json::to_string(event)?])
+            .await?;
+        Ok(())
+    }
+}
+
+pub async fn listen_events(db_config: DbConfig, sender: broadcast::Sender<ChainEvent>) -> Result<()> {
+    let (client, mut connection) = tokio_postgres::connect(&db_config.connection_string(), NoTls).await?;
+    let (notification_sender, mut notifications) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(notification)) => {
+                    if notification_sender.send(notification).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::error!("Event listener connection error: {err}");
+                    break;
+                }
+            }
+        }
+    });
+    client.batch_execute(&format!("LISTEN {CHANNEL}")).await?;
+    while let Some(notification) = notifications.recv().await {
+        match serde_json::from_str::<ChainEvent>(notification.payload()) {
+            // Sending only fails when nobody is subscribed, which is fine
+            Ok(event) => {
+                let _ = sender.send(event);
+            }
+            Err(err) => tracing::warn!("Invalid chain event payload: {err}"),
+        }
+    }
+    Err(anyhow!("Event listener connection closed"))
+}
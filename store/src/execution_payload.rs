@@ -0,0 +1,255 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{ExecutionPayload, FeeRecipient, Page, PageRequest, SortDirection},
+    ExecutionPayloadRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresExecutionPayload {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub block_number: u64,
+    pub block_hash: String,
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: u64,
+    pub timestamp: u64,
+    pub transaction_count: u64,
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl TryFrom<Row> for PostgresExecutionPayload {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresExecutionPayload {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            proposer_index: value
+                .get::<_, Decimal>("proposer_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid proposer index"))?,
+            block_number: value
+                .get::<_, Decimal>("block_number")
+                .to_u64()
+                .ok_or(anyhow!("Invalid block number"))?,
+            block_hash: value.try_get("block_hash")?,
+            parent_hash: value.try_get("parent_hash")?,
+            fee_recipient: value.try_get("fee_recipient")?,
+            gas_used: value
+                .get::<_, Decimal>("gas_used")
+                .to_u64()
+                .ok_or(anyhow!("Invalid gas used"))?,
+            gas_limit: value
+                .get::<_, Decimal>("gas_limit")
+                .to_u64()
+                .ok_or(anyhow!("Invalid gas limit"))?,
+            base_fee_per_gas: value
+                .get::<_, Decimal>("base_fee_per_gas")
+                .to_u64()
+                .ok_or(anyhow!("Invalid base fee per gas"))?,
+            timestamp: value
+                .get::<_, Decimal>("timestamp")
+                .to_u64()
+                .ok_or(anyhow!("Invalid timestamp"))?,
+            transaction_count: value
+                .get::<_, Decimal>("transaction_count")
+                .to_u64()
+                .ok_or(anyhow!("Invalid transaction count"))?,
+            blob_gas_used: value
+                .get::<_, Option<Decimal>>("blob_gas_used")
+                .map(|gas| gas.to_u64().ok_or(anyhow!("Invalid blob gas used")))
+                .transpose()?,
+            excess_blob_gas: value
+                .get::<_, Option<Decimal>>("excess_blob_gas")
+                .map(|gas| gas.to_u64().ok_or(anyhow!("Invalid excess blob gas")))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<PostgresExecutionPayload> for ExecutionPayload {
+    fn from(value: PostgresExecutionPayload) -> Self {
+        ExecutionPayload {
+            slot: value.slot,
+            proposer_index: value.proposer_index,
+            block_number: value.block_number,
+            block_hash: value.block_hash,
+            parent_hash: value.parent_hash,
+            fee_recipient: value.fee_recipient,
+            gas_used: value.gas_used,
+            gas_limit: value.gas_limit,
+            base_fee_per_gas: value.base_fee_per_gas,
+            timestamp: value.timestamp,
+            transaction_count: value.transaction_count,
+            blob_gas_used: value.blob_gas_used,
+            excess_blob_gas: value.excess_blob_gas,
+        }
+    }
+}
+
+pub struct PostgresExecutionPayloadRepository {
+    pool: Pool,
+}
+
+impl PostgresExecutionPayloadRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ExecutionPayloadRepository for PostgresExecutionPayloadRepository {
+    #[tracing::instrument(skip(self, payload))]
+    async fn create_execution_payload(&self, payload: &ExecutionPayload) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["execution_payload", "create_execution_payload"]);
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO execution_payload (slot, proposer_index, block_number, block_hash, parent_hash, fee_recipient, gas_used, gas_limit, base_fee_per_gas, timestamp, transaction_count, blob_gas_used, excess_blob_gas)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                ON CONFLICT (slot) DO NOTHING",
+                &[
+                    &Decimal::from(payload.slot),
+                    &Decimal::from(payload.proposer_index),
+                    &Decimal::from(payload.block_number),
+                    &payload.block_hash,
+                    &payload.parent_hash,
+                    &payload.fee_recipient,
+                    &Decimal::from(payload.gas_used),
+                    &Decimal::from(payload.gas_limit),
+                    &Decimal::from(payload.base_fee_per_gas),
+                    &Decimal::from(payload.timestamp),
+                    &Decimal::from(payload.transaction_count),
+                    &payload.blob_gas_used.map(Decimal::from),
+                    &payload.excess_blob_gas.map(Decimal::from),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_execution_payload(&self, slot: u64) -> Result<Option<ExecutionPayload>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["execution_payload", "get_execution_payload"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, block_number, block_hash, parent_hash, fee_recipient, gas_used, gas_limit, base_fee_per_gas, timestamp, transaction_count, blob_gas_used, excess_blob_gas
+                FROM execution_payload
+                WHERE slot = $1",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresExecutionPayload::try_from)
+            .transpose()?
+            .map(ExecutionPayload::from))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_execution_payload_by_block_hash(&self, block_hash: &str) -> Result<Option<ExecutionPayload>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["execution_payload", "get_execution_payload_by_block_hash"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, block_number, block_hash, parent_hash, fee_recipient, gas_used, gas_limit, base_fee_per_gas, timestamp, transaction_count, blob_gas_used, excess_blob_gas
+                FROM execution_payload
+                WHERE block_hash = $1",
+                &[&block_hash],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresExecutionPayload::try_from)
+            .transpose()?
+            .map(ExecutionPayload::from))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_execution_payload_by_block_number(&self, block_number: u64) -> Result<Option<ExecutionPayload>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["execution_payload", "get_execution_payload_by_block_number"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, proposer_index, block_number, block_hash, parent_hash, fee_recipient, gas_used, gas_limit, base_fee_per_gas, timestamp, transaction_count, blob_gas_used, excess_blob_gas
+                FROM execution_payload
+                WHERE block_number = $1
+                ORDER BY slot DESC
+                LIMIT 1",
+                &[&Decimal::from(block_number)],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresExecutionPayload::try_from)
+            .transpose()?
+            .map(ExecutionPayload::from))
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_execution_payload_page_for_proposer(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<ExecutionPayload>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["execution_payload", "get_execution_payload_page_for_proposer"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT slot, proposer_index, block_number, block_hash, parent_hash, fee_recipient, gas_used, gas_limit, base_fee_per_gas, timestamp, transaction_count, blob_gas_used, excess_blob_gas,
+                slot AS cursor_value, slot AS cursor_index
+            FROM execution_payload
+            WHERE proposer_index = $1",
+            &[&Decimal::from(validator)],
+            SortDirection::Asc,
+            page,
+            |row| Ok(ExecutionPayload::from(PostgresExecutionPayload::try_from(row)?)),
+        )
+        .await
+    }
+
+    // Recipients are ranked by block count, so the rank itself serves as the cursor
+    #[tracing::instrument(skip(self, page))]
+    async fn get_fee_recipient_page_for_proposer(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<FeeRecipient>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["execution_payload", "get_fee_recipient_page_for_proposer"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT fee_recipient, block_count,
+                ROW_NUMBER() OVER (ORDER BY block_count DESC, fee_recipient)::NUMERIC AS cursor_value, 0::NUMERIC AS cursor_index
+            FROM (
+                SELECT fee_recipient, COUNT(*) AS block_count
+                FROM execution_payload
+                WHERE proposer_index = $1
+                GROUP BY fee_recipient
+            ) AS recipients",
+            &[&Decimal::from(validator)],
+            SortDirection::Asc,
+            page,
+            |row| {
+                Ok(FeeRecipient {
+                    address: row.try_get("fee_recipient")?,
+                    block_count: u64::try_from(row.get::<_, i64>("block_count"))?,
+                })
+            },
+        )
+        .await
+    }
+}
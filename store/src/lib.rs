@@ -1,12 +1,61 @@
-use deadpool_postgres::{Config, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use api_key::PostgresApiKeyRepository;
+use attestation::PostgresAttestationRepository;
+use blob_sidecar::PostgresBlobSidecarRepository;
+use block_header::PostgresBlockHeaderRepository;
+use bls_to_execution_change::PostgresBlsToExecutionChangeRepository;
+use committee::PostgresCommitteeRepository;
+use deadpool_postgres::{Config, ManagerConfig, RecyclingMethod, Runtime};
+use deposit::PostgresDepositRepository;
 use envconfig::Envconfig;
-use tokio_postgres::NoTls;
+use epoch::PostgresEpochRepository;
+use event::PostgresEventRepository;
+use execution_payload::PostgresExecutionPayloadRepository;
+use futures_util::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use proposer::PostgresProposerRepository;
+use proposer_duty::PostgresProposerDutyRepository;
+use relay_payload::PostgresRelayPayloadRepository;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use serde::Serialize;
+use service::{
+    model::{Cursor, Page, PageRequest, SortDirection},
+    Repositories,
+};
+use slashing::PostgresSlashingRepository;
+use slot::PostgresSlotRepository;
+use tokio_postgres::{types::ToSql, NoTls, Row};
+use validator::PostgresValidatorRepository;
+use validator_group::PostgresValidatorGroupRepository;
+use voluntary_exit::PostgresVoluntaryExitRepository;
+use withdrawal::PostgresWithdrawalRepository;
+
+pub use deadpool_postgres::Pool;
 
+pub mod api_key;
 pub mod attestation;
+pub mod blob_sidecar;
+pub mod block_header;
+pub mod bls_to_execution_change;
+pub mod cache;
 pub mod committee;
+pub mod deposit;
 pub mod epoch;
+pub mod event;
+pub mod execution_payload;
 pub mod proposer;
+pub mod proposer_duty;
+pub mod relay_payload;
+pub mod slashing;
+pub mod slot;
 pub mod validator;
+pub mod validator_group;
+pub mod voluntary_exit;
+pub mod withdrawal;
 
 #[derive(Envconfig, Clone)]
 pub struct DbConfig {
@@ -47,3 +96,132 @@ pub async fn connect(db_config: DbConfig) -> Pool {
     });
     cfg.create_pool(Some(Runtime::Tokio1), NoTls).unwrap()
 }
+
+#[derive(Debug, Serialize)]
+pub struct DbHealth {
+    pub connected: bool,
+    pub error: Option<String>,
+    pub pool_size: usize,
+    pub pool_max_size: usize,
+    // Negative when requests are queued waiting for a connection
+    pub pool_available: isize,
+}
+
+pub async fn check_health(pool: &Pool, timeout: Duration) -> DbHealth {
+    let query = async {
+        pool.get().await?.simple_query("SELECT 1").await?;
+        Ok::<_, anyhow::Error>(())
+    };
+    let result = match tokio::time::timeout(timeout, query).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow!("Timed out waiting for a database connection")),
+    };
+    let status = pool.status();
+    DbHealth {
+        connected: result.is_ok(),
+        error: result.err().map(|err| err.to_string()),
+        pool_size: status.size,
+        pool_max_size: status.max_size,
+        pool_available: status.available,
+    }
+}
+
+pub fn repositories(pool: Pool) -> Repositories {
+    Repositories {
+        epoch_repository: Arc::new(PostgresEpochRepository::new(pool.clone())),
+        validator_repository: Arc::new(PostgresValidatorRepository::new(pool.clone())),
+        attestation_repository: Arc::new(PostgresAttestationRepository::new(pool.clone())),
+        committee_repository: Arc::new(PostgresCommitteeRepository::new(pool.clone())),
+        proposer_repository: Arc::new(PostgresProposerRepository::new(pool.clone())),
+        proposer_duty_repository: Arc::new(PostgresProposerDutyRepository::new(pool.clone())),
+        slot_repository: Arc::new(PostgresSlotRepository::new(pool.clone())),
+        slashing_repository: Arc::new(PostgresSlashingRepository::new(pool.clone())),
+        voluntary_exit_repository: Arc::new(PostgresVoluntaryExitRepository::new(pool.clone())),
+        bls_to_execution_change_repository: Arc::new(PostgresBlsToExecutionChangeRepository::new(pool.clone())),
+        deposit_repository: Arc::new(PostgresDepositRepository::new(pool.clone())),
+        withdrawal_repository: Arc::new(PostgresWithdrawalRepository::new(pool.clone())),
+        execution_payload_repository: Arc::new(PostgresExecutionPayloadRepository::new(pool.clone())),
+        relay_payload_repository: Arc::new(PostgresRelayPayloadRepository::new(pool.clone())),
+        blob_sidecar_repository: Arc::new(PostgresBlobSidecarRepository::new(pool.clone())),
+        validator_group_repository: Arc::new(PostgresValidatorGroupRepository::new(pool.clone())),
+        event_repository: Arc::new(PostgresEventRepository::new(pool.clone())),
+        block_header_repository: Arc::new(PostgresBlockHeaderRepository::new(pool.clone())),
+        api_key_repository: Arc::new(PostgresApiKeyRepository::new(pool)),
+    }
+}
+
+fn keyset_order(direction: SortDirection, backward: bool) -> (&'static str, &'static str) {
+    match (direction, backward) {
+        (SortDirection::Asc, false) | (SortDirection::Desc, true) => (">", "ASC"),
+        (SortDirection::Desc, false) | (SortDirection::Asc, true) => ("<", "DESC"),
+    }
+}
+
+// Pages through `query` by its `cursor_value` and `cursor_index` columns. The query's own parameters come first, and
+// the cursor and limit are appended after them.
+pub(crate) async fn keyset_page<T>(
+    client: &deadpool_postgres::Client,
+    query: &str,
+    params: &[&(dyn ToSql + Sync)],
+    direction: SortDirection,
+    page: &PageRequest,
+    map: fn(Row) -> anyhow::Result<T>,
+) -> anyhow::Result<Page<T>> {
+    let count = client
+        .query_one(&format!("SELECT COUNT(*) FROM ({query}) AS page"), params)
+        .await?;
+    let total_count = u64::try_from(count.get::<_, i64>(0))?;
+    let (operator, order) = keyset_order(direction, page.backward);
+    let value = page.cursor.map(|cursor| Decimal::from(cursor.value));
+    let index = page.cursor.map(|cursor| Decimal::from(cursor.index));
+    let limit = i64::try_from(page.limit + 1)?;
+    let mut page_params = params.to_vec();
+    page_params.extend([&value as &(dyn ToSql + Sync), &index, &limit]);
+    let next = params.len() + 1;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT * FROM ({query}) AS page
+                WHERE ${next}::NUMERIC(20,0) IS NULL OR (cursor_value, cursor_index) {operator} (${next}, ${}::NUMERIC(20,0))
+                ORDER BY cursor_value {order}, cursor_index {order}
+                LIMIT ${}",
+                next + 1,
+                next + 2
+            ),
+            &page_params,
+        )
+        .await?;
+    let items = rows
+        .into_iter()
+        .map(|row| {
+            let cursor = Cursor::new(
+                row.get::<_, Decimal>("cursor_value")
+                    .to_u64()
+                    .ok_or(anyhow!("Invalid cursor value"))?,
+                row.get::<_, Decimal>("cursor_index")
+                    .to_u64()
+                    .ok_or(anyhow!("Invalid cursor index"))?,
+            );
+            Ok((cursor, map(row)?))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(Page::new(items, page, total_count))
+}
+
+// Holds the pooled connection for as long as the stream is alive, so rows are read lazily instead of being collected
+pub(crate) async fn query_stream<T: Send + 'static>(
+    pool: &Pool,
+    statement: &str,
+    params: Vec<Decimal>,
+    map: fn(Row) -> anyhow::Result<T>,
+) -> anyhow::Result<BoxStream<'static, anyhow::Result<T>>> {
+    let client = pool.get().await?;
+    let rows = client.query_raw(statement, params).await?;
+    Ok(
+        stream::unfold((client, Box::pin(rows)), move |(client, mut rows)| async move {
+            let row = rows.next().await?;
+            Some((row.map_err(anyhow::Error::from).and_then(map), (client, rows)))
+        })
+        .boxed(),
+    )
+}
@@ -17,7 +17,9 @@ impl PostgresProposerRepository {
 
 #[async_trait]
 impl ProposerRepository for PostgresProposerRepository {
+    #[tracing::instrument(skip(self))]
     async fn create_proposer(&self, slot: u64, validator: u64) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer", "create_proposer"]);
         let client = self.pool.get().await?;
         client
             .execute(
@@ -30,7 +32,9 @@ impl ProposerRepository for PostgresProposerRepository {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, proposers))]
     async fn create_proposers(&self, proposers: &[Proposer]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer", "create_proposers"]);
         if proposers.is_empty() {
             return Ok(());
         }
@@ -53,7 +57,9 @@ impl ProposerRepository for PostgresProposerRepository {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
     async fn get_proposer_for_slot(&self, slot: u64) -> Result<Option<u64>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer", "get_proposer_for_slot"]);
         let client = self.pool.get().await?;
         let row = client
             .query_opt(
@@ -70,7 +76,9 @@ impl ProposerRepository for PostgresProposerRepository {
         .transpose()
     }
 
+    #[tracing::instrument(skip(self))]
     async fn get_proposers_for_epoch(&self, epoch: u64) -> Result<Vec<u64>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer", "get_proposers_for_epoch"]);
         let client = self.pool.get().await?;
         let rows = client
             .query(
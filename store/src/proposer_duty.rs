@@ -0,0 +1,241 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{Page, PageRequest, ProposalOutcome, Proposer, SortDirection},
+    ProposerDutyRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresProposalOutcome {
+    pub slot: u64,
+    pub epoch_index: u64,
+    pub scheduled_proposer: u64,
+    pub actual_proposer: Option<u64>,
+    pub status: Option<String>,
+}
+
+impl TryFrom<Row> for PostgresProposalOutcome {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresProposalOutcome {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            epoch_index: value
+                .get::<_, Decimal>("epoch_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid epoch index"))?,
+            scheduled_proposer: value
+                .get::<_, Decimal>("scheduled_proposer")
+                .to_u64()
+                .ok_or(anyhow!("Invalid scheduled proposer"))?,
+            actual_proposer: value
+                .get::<_, Option<Decimal>>("actual_proposer")
+                .map(|proposer| proposer.to_u64().ok_or(anyhow!("Invalid actual proposer")))
+                .transpose()?,
+            status: value.try_get("status")?,
+        })
+    }
+}
+
+impl TryFrom<PostgresProposalOutcome> for ProposalOutcome {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresProposalOutcome) -> Result<Self, Self::Error> {
+        Ok(ProposalOutcome {
+            slot: value.slot,
+            epoch: value.epoch_index,
+            scheduled_proposer: value.scheduled_proposer,
+            actual_proposer: value.actual_proposer,
+            status: value.status.map(|status| status.parse()).transpose()?,
+        })
+    }
+}
+
+pub struct PostgresProposerDutyRepository {
+    pool: Pool,
+}
+
+impl PostgresProposerDutyRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ProposerDutyRepository for PostgresProposerDutyRepository {
+    #[tracing::instrument(skip(self, duties))]
+    async fn create_proposer_duties(&self, duties: &[Proposer]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer_duty", "create_proposer_duties"]);
+        if duties.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let slots = duties.iter().map(|duty| Decimal::from(duty.slot)).collect::<Vec<_>>();
+        let epoch_indices = duties
+            .iter()
+            .map(|duty| Decimal::from(duty.slot / 32))
+            .collect::<Vec<_>>();
+        let validator_indices = duties
+            .iter()
+            .map(|duty| Decimal::from(duty.validator_index))
+            .collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO proposer_duty (slot, epoch_index, validator_index)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::NUMERIC(20,0)[])
+                ON CONFLICT (slot) DO UPDATE SET validator_index = EXCLUDED.validator_index",
+                &[&slots, &epoch_indices, &validator_indices],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_proposer_duties_for_epoch(&self, epoch: u64) -> Result<Vec<Proposer>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer_duty", "get_proposer_duties_for_epoch"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT slot, validator_index FROM proposer_duty
+                WHERE epoch_index = $1
+                ORDER BY slot",
+                &[&Decimal::from(epoch)],
+            )
+            .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(Proposer {
+                    slot: row.get::<_, Decimal>("slot").to_u64().ok_or(anyhow!("Invalid slot"))?,
+                    validator_index: row
+                        .get::<_, Decimal>("validator_index")
+                        .to_u64()
+                        .ok_or(anyhow!("Invalid validator index"))?,
+                })
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_proposal_outcome(&self, slot: u64) -> Result<Option<ProposalOutcome>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer_duty", "get_proposal_outcome"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT proposer_duty.slot, proposer_duty.epoch_index, proposer_duty.validator_index AS scheduled_proposer,
+                    proposer.validator_index AS actual_proposer, slot.status
+                FROM proposer_duty
+                LEFT JOIN proposer ON proposer.slot = proposer_duty.slot
+                LEFT JOIN slot ON slot.slot = proposer_duty.slot
+                WHERE proposer_duty.slot = $1",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        row.map(PostgresProposalOutcome::try_from)
+            .transpose()?
+            .map(ProposalOutcome::try_from)
+            .transpose()
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_proposal_outcomes_for_validators(&self, validators: &[u64]) -> Result<Vec<ProposalOutcome>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer_duty", "get_proposal_outcomes_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT proposer_duty.slot, proposer_duty.epoch_index, proposer_duty.validator_index AS scheduled_proposer,
+                    proposer.validator_index AS actual_proposer, slot.status
+                FROM proposer_duty
+                LEFT JOIN proposer ON proposer.slot = proposer_duty.slot
+                LEFT JOIN slot ON slot.slot = proposer_duty.slot
+                WHERE proposer_duty.validator_index = ANY($1::NUMERIC(20,0)[])
+                ORDER BY proposer_duty.slot",
+                &[&validators],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresProposalOutcome::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(ProposalOutcome::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_recent_proposal_outcomes_for_validators(
+        &self,
+        validators: &[u64],
+        limit: u64,
+    ) -> Result<Vec<ProposalOutcome>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["proposer_duty", "get_recent_proposal_outcomes_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT slot, epoch_index, scheduled_proposer, actual_proposer, status FROM (
+                    SELECT proposer_duty.slot, proposer_duty.epoch_index, proposer_duty.validator_index AS scheduled_proposer,
+                        proposer.validator_index AS actual_proposer, slot.status,
+                        ROW_NUMBER() OVER (PARTITION BY proposer_duty.validator_index ORDER BY proposer_duty.slot DESC) AS rank
+                    FROM proposer_duty
+                    LEFT JOIN proposer ON proposer.slot = proposer_duty.slot
+                    LEFT JOIN slot ON slot.slot = proposer_duty.slot
+                    WHERE proposer_duty.validator_index = ANY($1::NUMERIC(20,0)[])
+                ) AS outcomes
+                WHERE rank <= $2
+                ORDER BY scheduled_proposer, slot DESC",
+                &[&validators, &i64::try_from(limit)?],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresProposalOutcome::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(ProposalOutcome::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, validators, page))]
+    async fn get_proposal_outcome_page(
+        &self,
+        validators: &[u64],
+        epoch: Option<u64>,
+        page: &PageRequest,
+    ) -> Result<Page<ProposalOutcome>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["proposer_duty", "get_proposal_outcome_page"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        keyset_page(
+            &client,
+            "SELECT proposer_duty.slot, proposer_duty.epoch_index, proposer_duty.validator_index AS scheduled_proposer,
+                proposer.validator_index AS actual_proposer, slot.status,
+                proposer_duty.slot AS cursor_value, proposer_duty.slot AS cursor_index
+            FROM proposer_duty
+            LEFT JOIN proposer ON proposer.slot = proposer_duty.slot
+            LEFT JOIN slot ON slot.slot = proposer_duty.slot
+            WHERE proposer_duty.validator_index = ANY($1::NUMERIC(20,0)[])
+                AND ($2::NUMERIC(20,0) IS NULL OR proposer_duty.epoch_index = $2)",
+            &[&validators, &epoch.map(Decimal::from)],
+            SortDirection::Asc,
+            page,
+            |row| ProposalOutcome::try_from(PostgresProposalOutcome::try_from(row)?),
+        )
+        .await
+    }
+}
@@ -0,0 +1,290 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{MevIncome, Page, PageRequest, RelayFailure, RelayPayload, RelayUsage, SortDirection},
+    RelayPayloadRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresRelayPayload {
+    pub relay: String,
+    pub slot: u64,
+    pub block_hash: String,
+    pub builder_pubkey: String,
+    pub proposer_pubkey: String,
+    pub proposer_fee_recipient: String,
+    pub value: u128,
+}
+
+impl TryFrom<Row> for PostgresRelayPayload {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresRelayPayload {
+            relay: value.try_get("relay")?,
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            block_hash: value.try_get("block_hash")?,
+            builder_pubkey: value.try_get("builder_pubkey")?,
+            proposer_pubkey: value.try_get("proposer_pubkey")?,
+            proposer_fee_recipient: value.try_get("proposer_fee_recipient")?,
+            value: value
+                .get::<_, Decimal>("value")
+                .to_u128()
+                .ok_or(anyhow!("Invalid value"))?,
+        })
+    }
+}
+
+impl From<PostgresRelayPayload> for RelayPayload {
+    fn from(value: PostgresRelayPayload) -> Self {
+        RelayPayload {
+            relay: value.relay,
+            slot: value.slot,
+            block_hash: value.block_hash,
+            builder_pubkey: value.builder_pubkey,
+            proposer_pubkey: value.proposer_pubkey,
+            proposer_fee_recipient: value.proposer_fee_recipient,
+            value: value.value,
+        }
+    }
+}
+
+pub struct PostgresRelayFailure {
+    pub relay: String,
+    pub slot: u64,
+    pub attempts: u32,
+}
+
+impl TryFrom<Row> for PostgresRelayFailure {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresRelayFailure {
+            relay: value.try_get("relay")?,
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            attempts: u32::try_from(value.get::<_, i32>("attempts"))?,
+        })
+    }
+}
+
+impl From<PostgresRelayFailure> for RelayFailure {
+    fn from(value: PostgresRelayFailure) -> Self {
+        RelayFailure {
+            relay: value.relay,
+            slot: value.slot,
+            attempts: value.attempts,
+        }
+    }
+}
+
+pub struct PostgresRelayPayloadRepository {
+    pool: Pool,
+}
+
+impl PostgresRelayPayloadRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RelayPayloadRepository for PostgresRelayPayloadRepository {
+    #[tracing::instrument(skip(self, payloads))]
+    async fn create_relay_payloads(&self, payloads: &[RelayPayload]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "create_relay_payloads"]);
+        if payloads.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let relays = payloads.iter().map(|data| &data.relay).collect::<Vec<_>>();
+        let slots = payloads.iter().map(|data| Decimal::from(data.slot)).collect::<Vec<_>>();
+        let block_hashes = payloads.iter().map(|data| &data.block_hash).collect::<Vec<_>>();
+        let builder_pubkeys = payloads.iter().map(|data| &data.builder_pubkey).collect::<Vec<_>>();
+        let proposer_pubkeys = payloads.iter().map(|data| &data.proposer_pubkey).collect::<Vec<_>>();
+        let fee_recipients = payloads
+            .iter()
+            .map(|data| &data.proposer_fee_recipient)
+            .collect::<Vec<_>>();
+        let values = payloads
+            .iter()
+            .map(|data| Decimal::from(data.value))
+            .collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO relay_payload (relay, slot, block_hash, builder_pubkey, proposer_pubkey, proposer_fee_recipient, value)
+                SELECT * FROM UNNEST($1::VARCHAR[], $2::NUMERIC(20,0)[], $3::VARCHAR[], $4::VARCHAR[], $5::VARCHAR[], $6::VARCHAR[], $7::NUMERIC(39,0)[])
+                ON CONFLICT (relay, slot, block_hash) DO NOTHING",
+                &[
+                    &relays,
+                    &slots,
+                    &block_hashes,
+                    &builder_pubkeys,
+                    &proposer_pubkeys,
+                    &fee_recipients,
+                    &values,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_relay_payload_page_for_validator(
+        &self,
+        validator: u64,
+        page: &PageRequest,
+    ) -> Result<Page<RelayPayload>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "get_relay_payload_page_for_validator"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT relay, relay_payload.slot, relay_payload.block_hash, builder_pubkey, proposer_pubkey, proposer_fee_recipient, value,
+                relay_payload.slot AS cursor_value,
+                ROW_NUMBER() OVER (PARTITION BY relay_payload.slot ORDER BY relay)::NUMERIC AS cursor_index
+            FROM relay_payload
+            INNER JOIN execution_payload ON execution_payload.block_hash = relay_payload.block_hash
+            WHERE execution_payload.proposer_index = $1",
+            &[&Decimal::from(validator)],
+            SortDirection::Asc,
+            page,
+            |row| Ok(RelayPayload::from(PostgresRelayPayload::try_from(row)?)),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_mev_income_for_validators(&self, validators: &[u64]) -> Result<MevIncome> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "get_mev_income_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let row = client
+            .query_one(
+                "SELECT COUNT(*) AS block_count, COALESCE(SUM(value), 0) AS total_value
+                FROM (
+                    SELECT DISTINCT ON (relay_payload.block_hash) value
+                    FROM relay_payload
+                    INNER JOIN execution_payload ON execution_payload.block_hash = relay_payload.block_hash
+                    WHERE execution_payload.proposer_index = ANY($1::NUMERIC(20,0)[])
+                    ORDER BY relay_payload.block_hash, value DESC
+                ) delivered",
+                &[&validators],
+            )
+            .await?;
+        Ok(MevIncome {
+            block_count: u64::try_from(row.get::<_, i64>("block_count"))?,
+            total_value: row
+                .get::<_, Decimal>("total_value")
+                .to_u128()
+                .ok_or(anyhow!("Invalid total value"))?,
+        })
+    }
+
+    // Relays are ranked by block count, so the rank itself serves as the cursor
+    #[tracing::instrument(skip(self, validators, page))]
+    async fn get_relay_usage_page_for_validators(
+        &self,
+        validators: &[u64],
+        page: &PageRequest,
+    ) -> Result<Page<RelayUsage>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "get_relay_usage_page_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        keyset_page(
+            &client,
+            "SELECT relay, block_count, total_value,
+                ROW_NUMBER() OVER (ORDER BY block_count DESC, relay)::NUMERIC AS cursor_value, 0::NUMERIC AS cursor_index
+            FROM (
+                SELECT relay, COUNT(*) AS block_count, SUM(value) AS total_value
+                FROM relay_payload
+                INNER JOIN execution_payload ON execution_payload.block_hash = relay_payload.block_hash
+                WHERE execution_payload.proposer_index = ANY($1::NUMERIC(20,0)[])
+                GROUP BY relay
+            ) AS usage",
+            &[&validators],
+            SortDirection::Asc,
+            page,
+            |row| {
+                Ok(RelayUsage {
+                    relay: row.try_get("relay")?,
+                    block_count: u64::try_from(row.get::<_, i64>("block_count"))?,
+                    total_value: row
+                        .get::<_, Decimal>("total_value")
+                        .to_u128()
+                        .ok_or(anyhow!("Invalid total value"))?,
+                })
+            },
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, slots))]
+    async fn record_relay_failures(&self, relay: &str, slots: &[u64]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "record_relay_failures"]);
+        if slots.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let slots = slots.iter().map(|slot| Decimal::from(*slot)).collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO relay_failure (relay, slot)
+                SELECT $1, slot FROM UNNEST($2::NUMERIC(20,0)[]) AS slot
+                ON CONFLICT (relay, slot) DO UPDATE SET attempts = relay_failure.attempts + 1",
+                &[&relay, &slots],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_relay_failures(&self, max_attempts: u32, limit: u64) -> Result<Vec<RelayFailure>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "get_relay_failures"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT relay, slot, attempts FROM relay_failure
+                WHERE attempts < $1
+                ORDER BY slot, relay
+                LIMIT $2",
+                &[&i32::try_from(max_attempts)?, &i64::try_from(limit)?],
+            )
+            .await?;
+        let failures = rows
+            .into_iter()
+            .map(PostgresRelayFailure::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(RelayFailure::from)
+            .collect();
+        Ok(failures)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_relay_failure(&self, relay: &str, slot: u64) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["relay_payload", "delete_relay_failure"]);
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "DELETE FROM relay_failure WHERE relay = $1 AND slot = $2",
+                &[&relay, &Decimal::from(slot)],
+            )
+            .await?;
+        Ok(())
+    }
+}
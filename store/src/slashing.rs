@@ -0,0 +1,166 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{Page, PageRequest, Slashing, SortDirection},
+    SlashingRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresSlashing {
+    pub slot: u64,
+    pub epoch_index: u64,
+    pub kind: String,
+    pub validator_index: u64,
+    pub proposer_index: u64,
+    pub whistleblower_index: u64,
+    pub evidence: String,
+}
+
+impl TryFrom<Row> for PostgresSlashing {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresSlashing {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            epoch_index: value
+                .get::<_, Decimal>("epoch_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid epoch index"))?,
+            kind: value.try_get("kind")?,
+            validator_index: value
+                .get::<_, Decimal>("validator_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid validator index"))?,
+            proposer_index: value
+                .get::<_, Decimal>("proposer_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid proposer index"))?,
+            whistleblower_index: value
+                .get::<_, Decimal>("whistleblower_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid whistleblower index"))?,
+            evidence: value.try_get("evidence")?,
+        })
+    }
+}
+
+impl TryFrom<PostgresSlashing> for Slashing {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresSlashing) -> Result<Self, Self::Error> {
+        Ok(Slashing {
+            slot: value.slot,
+            epoch: value.epoch_index,
+            kind: value.kind.parse()?,
+            validator_index: value.validator_index,
+            proposer_index: value.proposer_index,
+            whistleblower_index: value.whistleblower_index,
+            evidence: value.evidence,
+        })
+    }
+}
+
+pub struct PostgresSlashingRepository {
+    pool: Pool,
+}
+
+impl PostgresSlashingRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SlashingRepository for PostgresSlashingRepository {
+    #[tracing::instrument(skip(self, batch))]
+    async fn create_slashings(&self, batch: &[Slashing]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slashing", "create_slashings"]);
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let slots = batch.iter().map(|data| Decimal::from(data.slot)).collect::<Vec<_>>();
+        let epoch_indices = batch.iter().map(|data| Decimal::from(data.epoch)).collect::<Vec<_>>();
+        let kinds = batch.iter().map(|data| data.kind.to_string()).collect::<Vec<_>>();
+        let validator_indices = batch
+            .iter()
+            .map(|data| Decimal::from(data.validator_index))
+            .collect::<Vec<_>>();
+        let proposer_indices = batch
+            .iter()
+            .map(|data| Decimal::from(data.proposer_index))
+            .collect::<Vec<_>>();
+        let whistleblower_indices = batch
+            .iter()
+            .map(|data| Decimal::from(data.whistleblower_index))
+            .collect::<Vec<_>>();
+        let evidence = batch.iter().map(|data| &data.evidence).collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO slashing (slot, epoch_index, kind, validator_index, proposer_index, whistleblower_index, evidence)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::VARCHAR[], $4::NUMERIC(20,0)[], $5::NUMERIC(20,0)[], $6::NUMERIC(20,0)[], $7::VARCHAR[])
+                ON CONFLICT (slot, validator_index, kind) DO NOTHING",
+                &[
+                    &slots,
+                    &epoch_indices,
+                    &kinds,
+                    &validator_indices,
+                    &proposer_indices,
+                    &whistleblower_indices,
+                    &evidence,
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_slashing_page(&self, page: &PageRequest) -> Result<Page<Slashing>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slashing", "get_slashing_page"]);
+        let client = self.pool.get().await?;
+        // A validator can be slashed as proposer and attester in the same slot, so the kind is folded into the index
+        keyset_page(
+            &client,
+            "SELECT slot, epoch_index, kind, validator_index, proposer_index, whistleblower_index, evidence,
+                slot AS cursor_value, validator_index * 2 + CASE WHEN kind = 'attester' THEN 1 ELSE 0 END AS cursor_index
+            FROM slashing",
+            &[],
+            SortDirection::Desc,
+            page,
+            |row| Slashing::try_from(PostgresSlashing::try_from(row)?),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_slashings_for_validators(&self, validators: &[u64]) -> Result<Vec<Slashing>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slashing", "get_slashings_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT slot, epoch_index, kind, validator_index, proposer_index, whistleblower_index, evidence
+                FROM slashing
+                WHERE validator_index = ANY($1::NUMERIC(20,0)[])
+                ORDER BY slot",
+                &[&validators],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresSlashing::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Slashing::try_from)
+            .collect()
+    }
+}
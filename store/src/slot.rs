@@ -0,0 +1,202 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use futures_util::stream::BoxStream;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{Cursor, Page, PageRequest, Slot, SlotQuery},
+    SlotRepository,
+};
+use tokio_postgres::Row;
+
+use crate::{keyset_order, query_stream};
+
+pub struct PostgresSlot {
+    pub slot: u64,
+    pub epoch_index: u64,
+    pub proposer_index: u64,
+    pub status: String,
+}
+
+impl TryFrom<Row> for PostgresSlot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresSlot {
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            epoch_index: value
+                .get::<_, Decimal>("epoch_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid epoch index"))?,
+            proposer_index: value
+                .get::<_, Decimal>("proposer_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid proposer index"))?,
+            status: value.try_get("status")?,
+        })
+    }
+}
+
+impl TryFrom<PostgresSlot> for Slot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresSlot) -> Result<Self, Self::Error> {
+        Ok(Slot {
+            slot: value.slot,
+            epoch: value.epoch_index,
+            proposer_index: value.proposer_index,
+            status: value.status.parse()?,
+        })
+    }
+}
+
+pub struct PostgresSlotRepository {
+    pool: Pool,
+}
+
+impl PostgresSlotRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SlotRepository for PostgresSlotRepository {
+    #[tracing::instrument(skip(self, slot))]
+    async fn create_slot(&self, slot: &Slot) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slot", "create_slot"]);
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO slot (slot, epoch_index, proposer_index, status)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (slot) DO UPDATE SET proposer_index = EXCLUDED.proposer_index, status = EXCLUDED.status",
+                &[
+                    &Decimal::from(slot.slot),
+                    &Decimal::from(slot.epoch),
+                    &Decimal::from(slot.proposer_index),
+                    &slot.status.to_string(),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_slot(&self, slot: u64) -> Result<Option<Slot>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slot", "get_slot"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT slot, epoch_index, proposer_index, status FROM slot
+                WHERE slot = $1",
+                &[&Decimal::from(slot)],
+            )
+            .await?;
+        row.map(PostgresSlot::try_from)
+            .transpose()?
+            .map(Slot::try_from)
+            .transpose()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_last_slot(&self) -> Result<Option<u64>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slot", "get_last_slot"]);
+        let client = self.pool.get().await?;
+        let row = client.query_one("SELECT MAX(slot) AS slot FROM slot", &[]).await?;
+        row.get::<_, Option<Decimal>>("slot")
+            .map(|slot| slot.to_u64().ok_or(anyhow!("Invalid slot")))
+            .transpose()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_slots_for_epoch(&self, epoch: u64) -> Result<Vec<Slot>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slot", "get_slots_for_epoch"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT slot, epoch_index, proposer_index, status FROM slot
+                WHERE epoch_index = $1
+                ORDER BY slot",
+                &[&Decimal::from(epoch)],
+            )
+            .await?;
+        rows.into_iter()
+            .map(PostgresSlot::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Slot::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, query, page))]
+    async fn get_slot_page(&self, query: &SlotQuery, page: &PageRequest) -> Result<Page<Slot>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slot", "get_slot_page"]);
+        let client = self.pool.get().await?;
+        let status = query.status.map(|status| status.to_string());
+        let epoch_from = query.epoch_from.map(Decimal::from);
+        let epoch_to = query.epoch_to.map(Decimal::from);
+        let proposers = query.proposers.as_ref().map(|proposers| {
+            proposers
+                .iter()
+                .map(|proposer| Decimal::from(*proposer))
+                .collect::<Vec<_>>()
+        });
+        let count = client
+            .query_one(
+                &format!("SELECT COUNT(*) FROM slot WHERE {FILTER}"),
+                &[&status, &epoch_from, &epoch_to, &proposers],
+            )
+            .await?;
+        let total_count = u64::try_from(count.get::<_, i64>(0))?;
+        let (operator, order) = keyset_order(query.direction, page.backward);
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT slot, epoch_index, proposer_index, status FROM slot
+                    WHERE {FILTER} AND ($5::NUMERIC(20,0) IS NULL OR slot {operator} $5)
+                    ORDER BY slot {order}
+                    LIMIT $6"
+                ),
+                &[
+                    &status,
+                    &epoch_from,
+                    &epoch_to,
+                    &proposers,
+                    &page.cursor.map(|cursor| Decimal::from(cursor.value)),
+                    &i64::try_from(page.limit + 1)?,
+                ],
+            )
+            .await?;
+        let slots = rows
+            .into_iter()
+            .map(PostgresSlot::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(|slot| Slot::try_from(slot).map(|slot| (Cursor::new(slot.slot, slot.slot), slot)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Page::new(slots, page, total_count))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn stream_slots(&self, from_epoch: u64, to_epoch: u64) -> Result<BoxStream<'static, Result<Slot>>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["slot", "stream_slots"]);
+        query_stream(
+            &self.pool,
+            "SELECT slot, epoch_index, proposer_index, status FROM slot
+            WHERE epoch_index >= $1 AND epoch_index <= $2
+            ORDER BY slot",
+            vec![Decimal::from(from_epoch), Decimal::from(to_epoch)],
+            |row| Slot::try_from(PostgresSlot::try_from(row)?),
+        )
+        .await
+    }
+}
+
+const FILTER: &str = "($1::VARCHAR IS NULL OR status = $1)
+    AND ($2::NUMERIC(20,0) IS NULL OR epoch_index >= $2)
+    AND ($3::NUMERIC(20,0) IS NULL OR epoch_index <= $3)
+    AND ($4::NUMERIC(20,0)[] IS NULL OR proposer_index = ANY($4))";
@@ -3,17 +3,25 @@ use async_trait::async_trait;
 use deadpool_postgres::Pool;
 use rust_decimal::{prelude::ToPrimitive, Decimal};
 use service::{
-    model::{Validator, ValidatorDataInput},
+    model::{
+        Cursor, Page, PageRequest, SortDirection, Validator, ValidatorDataInput, ValidatorQuery, ValidatorSortField,
+    },
     ValidatorRepository,
 };
 use tokio_postgres::Row;
 
+use crate::{keyset_order, keyset_page};
+
 pub struct PostgresValidator {
     pub index: u64,
     pub pubkey: String,
     pub attestations: i64,
+    pub activation_eligibility_epoch: u64,
     pub activation_epoch: u64,
     pub exit_epoch: u64,
+    pub withdrawable_epoch: u64,
+    pub slashed: bool,
+    pub withdrawal_credentials: String,
 }
 
 impl TryFrom<Row> for PostgresValidator {
@@ -27,6 +35,10 @@ impl TryFrom<Row> for PostgresValidator {
                 .ok_or(anyhow!("Invalid validator index"))?,
             pubkey: value.try_get("pubkey")?,
             attestations: value.try_get("attestations")?,
+            activation_eligibility_epoch: value
+                .get::<_, Decimal>("activation_eligibility_epoch")
+                .to_u64()
+                .ok_or(anyhow!("Invalid activation eligibility epoch"))?,
             activation_epoch: value
                 .get::<_, Decimal>("activation_epoch")
                 .to_u64()
@@ -35,6 +47,12 @@ impl TryFrom<Row> for PostgresValidator {
                 .get::<_, Decimal>("exit_epoch")
                 .to_u64()
                 .ok_or(anyhow!("Invalid exit epoch"))?,
+            withdrawable_epoch: value
+                .get::<_, Decimal>("withdrawable_epoch")
+                .to_u64()
+                .ok_or(anyhow!("Invalid withdrawable epoch"))?,
+            slashed: value.try_get("slashed")?,
+            withdrawal_credentials: value.try_get("withdrawal_credentials")?,
         })
     }
 }
@@ -47,8 +65,12 @@ impl TryFrom<PostgresValidator> for Validator {
             index: value.index,
             pubkey: value.pubkey,
             attestations: u64::try_from(value.attestations)?,
+            activation_eligibility_epoch: value.activation_eligibility_epoch,
             activation_epoch: value.activation_epoch,
             exit_epoch: value.exit_epoch,
+            withdrawable_epoch: value.withdrawable_epoch,
+            slashed: value.slashed,
+            withdrawal_credentials: value.withdrawal_credentials,
         })
     }
 }
@@ -65,32 +87,30 @@ impl PostgresValidatorRepository {
 
 #[async_trait]
 impl ValidatorRepository for PostgresValidatorRepository {
+    #[tracing::instrument(skip(self, validator))]
     async fn create_or_update_validator(&self, validator: &ValidatorDataInput) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "create_or_update_validator"]);
         let client = self.pool.get().await?;
         client
             .execute(
-                "INSERT INTO validator (index, pubkey, activation_epoch, exit_epoch)
-                VALUES ($1, $2, $3, $4)
-                ON CONFLICT (index) DO UPDATE SET pubkey = EXCLUDED.pubkey, activation_epoch = EXCLUDED.activation_epoch, exit_epoch = EXCLUDED.exit_epoch",
-                &[&Decimal::from(validator.index), &validator.pubkey, &Decimal::from(validator.activation_epoch), &Decimal::from(validator.exit_epoch)],
+                "INSERT INTO validator (index, pubkey, activation_eligibility_epoch, activation_epoch, exit_epoch, withdrawable_epoch, slashed, withdrawal_credentials)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (index) DO UPDATE SET pubkey = EXCLUDED.pubkey, activation_eligibility_epoch = EXCLUDED.activation_eligibility_epoch, activation_epoch = EXCLUDED.activation_epoch, exit_epoch = EXCLUDED.exit_epoch, withdrawable_epoch = EXCLUDED.withdrawable_epoch, slashed = EXCLUDED.slashed, withdrawal_credentials = EXCLUDED.withdrawal_credentials",
+                &[&Decimal::from(validator.index), &validator.pubkey, &Decimal::from(validator.activation_eligibility_epoch), &Decimal::from(validator.activation_epoch), &Decimal::from(validator.exit_epoch), &Decimal::from(validator.withdrawable_epoch), &validator.slashed, &validator.withdrawal_credentials],
             )
             .await?;
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
     async fn get_active_validators(&self, epoch_index: u64) -> Result<Vec<Validator>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_active_validators"]);
         let client = self.pool.get().await?;
         let rows = client
             .query(
-                "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_epoch, validator.exit_epoch
+                "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials
                 FROM validator
-                LEFT JOIN (
-                    SELECT validator_index, COUNT(attested) AS attestations
-                    FROM attestation
-                    WHERE attested = true
-                    GROUP BY validator_index
-                ) AS attestation
-                ON validator.index = attestation.validator_index
+                LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
                 WHERE validator.activation_epoch <= $1 AND validator.exit_epoch > $1",
                 &[&Decimal::from(epoch_index)],
             )
@@ -105,7 +125,9 @@ impl ValidatorRepository for PostgresValidatorRepository {
         Ok(validators)
     }
 
+    #[tracing::instrument(skip(self))]
     async fn active_validator_count(&self, epoch_index: u64) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "active_validator_count"]);
         let client = self.pool.get().await?;
         let row = client
             .query_one(
@@ -119,7 +141,9 @@ impl ValidatorRepository for PostgresValidatorRepository {
         Ok(u64::try_from(count)?)
     }
 
+    #[tracing::instrument(skip(self))]
     async fn total_validator_count(&self, epoch_index: u64) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "total_validator_count"]);
         let client = self.pool.get().await?;
         let row = client
             .query_one(
@@ -133,13 +157,19 @@ impl ValidatorRepository for PostgresValidatorRepository {
         Ok(u64::try_from(count)?)
     }
 
+    #[tracing::instrument(skip(self, batch))]
     async fn create_or_update_validator_batch(&self, batch: &[ValidatorDataInput]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "create_or_update_validator_batch"]);
         if batch.is_empty() {
             return Ok(());
         }
         let client = self.pool.get().await?;
         let indices = batch.iter().map(|data| Decimal::from(data.index)).collect::<Vec<_>>();
         let pubkeys = batch.iter().map(|data| &data.pubkey).collect::<Vec<_>>();
+        let activation_eligibility_epochs = batch
+            .iter()
+            .map(|data| Decimal::from(data.activation_eligibility_epoch))
+            .collect::<Vec<_>>();
         let activation_epochs = batch
             .iter()
             .map(|data| Decimal::from(data.activation_epoch))
@@ -148,37 +178,60 @@ impl ValidatorRepository for PostgresValidatorRepository {
             .iter()
             .map(|data| Decimal::from(data.exit_epoch))
             .collect::<Vec<_>>();
+        let withdrawable_epochs = batch
+            .iter()
+            .map(|data| Decimal::from(data.withdrawable_epoch))
+            .collect::<Vec<_>>();
+        let slashed = batch.iter().map(|data| data.slashed).collect::<Vec<_>>();
+        let withdrawal_credentials = batch
+            .iter()
+            .map(|data| &data.withdrawal_credentials)
+            .collect::<Vec<_>>();
         client
             .execute(
-                "INSERT INTO validator (index, pubkey, activation_epoch, exit_epoch)
-                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::VARCHAR[], $3::NUMERIC(20,0)[], $4::NUMERIC(20,0)[])
-                ON CONFLICT (index) DO UPDATE SET pubkey = EXCLUDED.pubkey, activation_epoch = EXCLUDED.activation_epoch, exit_epoch = EXCLUDED.exit_epoch",
-                &[&indices, &pubkeys, &activation_epochs, &exit_epochs],
+                "INSERT INTO validator (index, pubkey, activation_eligibility_epoch, activation_epoch, exit_epoch, withdrawable_epoch, slashed, withdrawal_credentials)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::VARCHAR[], $3::NUMERIC(20,0)[], $4::NUMERIC(20,0)[], $5::NUMERIC(20,0)[], $6::NUMERIC(20,0)[], $7::BOOLEAN[], $8::VARCHAR[])
+                ON CONFLICT (index) DO UPDATE SET pubkey = EXCLUDED.pubkey, activation_eligibility_epoch = EXCLUDED.activation_eligibility_epoch, activation_epoch = EXCLUDED.activation_epoch, exit_epoch = EXCLUDED.exit_epoch, withdrawable_epoch = EXCLUDED.withdrawable_epoch, slashed = EXCLUDED.slashed, withdrawal_credentials = EXCLUDED.withdrawal_credentials",
+                &[&indices, &pubkeys, &activation_eligibility_epochs, &activation_epochs, &exit_epochs, &withdrawable_epochs, &slashed, &withdrawal_credentials],
             )
             .await?;
         Ok(())
     }
 
+    #[tracing::instrument(skip(self, indices))]
+    async fn get_validators(&self, indices: &[u64]) -> Result<Vec<Validator>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_validators"]);
+        let client = self.pool.get().await?;
+        let indices = indices.iter().map(|index| Decimal::from(*index)).collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials
+                FROM validator
+                LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
+                WHERE validator.index = ANY($1::NUMERIC(20,0)[])
+                ORDER BY validator.index",
+                &[&indices],
+            )
+            .await?;
+        let validators = rows
+            .into_iter()
+            .map(PostgresValidator::try_from)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(Validator::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(validators)
+    }
+
+    #[tracing::instrument(skip(self))]
     async fn get_validator(&self, index: u64) -> Result<Option<Validator>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_validator"]);
         let client = self.pool.get().await?;
         let row = client
             .query_opt(
-                "SELECT index, pubkey, COALESCE(validator_history.active_epochs, 0) as active_epochs, COALESCE(attestation.attestations, 0) as attestations
+                "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials
                 FROM validator
-                LEFT JOIN (
-                    SELECT validator_index, COUNT(is_active) AS active_epochs
-                    FROM validator_history
-                    WHERE is_active = true
-                    GROUP BY validator_index
-                ) AS validator_history
-                ON validator.index = validator_history.validator_index
-                LEFT JOIN (
-                    SELECT validator_index, COUNT(attested) AS attestations
-                    FROM attestation
-                    WHERE attested = true
-                    GROUP BY validator_index
-                ) AS attestation
-                ON validator.index = attestation.validator_index
+                LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
                 WHERE validator.index = $1",
                 &[&Decimal::from(index)],
             )
@@ -188,4 +241,166 @@ impl ValidatorRepository for PostgresValidatorRepository {
             .map(Validator::try_from)
             .transpose()
     }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_validator_by_pubkey(&self, pubkey: &str) -> Result<Option<Validator>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_validator_by_pubkey"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials
+                FROM validator
+                LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
+                WHERE validator.pubkey = $1",
+                &[&pubkey],
+            )
+            .await?;
+        row.map(PostgresValidator::try_from)
+            .transpose()?
+            .map(Validator::try_from)
+            .transpose()
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_validator_page_by_pubkey_prefix(&self, prefix: &str, page: &PageRequest) -> Result<Page<Validator>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_validator_page_by_pubkey_prefix"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials,
+                validator.index AS cursor_value, validator.index AS cursor_index
+            FROM validator
+            LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
+            WHERE validator.pubkey LIKE $1 || '%'",
+            &[&prefix],
+            SortDirection::Asc,
+            page,
+            |row| Validator::try_from(PostgresValidator::try_from(row)?),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_validator_page_by_withdrawal_credentials(
+        &self,
+        withdrawal_credentials: &[String],
+        page: &PageRequest,
+    ) -> Result<Page<Validator>> {
+        let _timer =
+            metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_validator_page_by_withdrawal_credentials"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials,
+                validator.index AS cursor_value, validator.index AS cursor_index
+            FROM validator
+            LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
+            WHERE validator.withdrawal_credentials = ANY($1)",
+            &[&withdrawal_credentials],
+            SortDirection::Asc,
+            page,
+            |row| Validator::try_from(PostgresValidator::try_from(row)?),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, query, page))]
+    async fn get_validator_page(&self, query: &ValidatorQuery, page: &PageRequest) -> Result<Page<Validator>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator", "get_validator_page"]);
+        let client = self.pool.get().await?;
+        let status = query.status.map(|status| status.to_string());
+        let current_epoch = Decimal::from(query.current_epoch);
+        let active_from = query.active_from.map(Decimal::from);
+        let active_to = query.active_to.map(Decimal::from);
+        let indices = query
+            .indices
+            .as_ref()
+            .map(|indices| indices.iter().map(|index| Decimal::from(*index)).collect::<Vec<_>>());
+        let count = client
+            .query_one(
+                &format!("WITH filtered AS ({FILTERED}) SELECT COUNT(*) FROM filtered"),
+                &[
+                    &status,
+                    &current_epoch,
+                    &active_from,
+                    &active_to,
+                    &indices,
+                    &query.min_participation_rate,
+                ],
+            )
+            .await?;
+        let total_count = u64::try_from(count.get::<_, i64>(0))?;
+        let sort = sort_column(query.sort);
+        let (operator, order) = keyset_order(query.direction, page.backward);
+        let rows = client
+            .query(
+                &format!(
+                    "WITH filtered AS ({FILTERED})
+                    SELECT * FROM filtered
+                    WHERE $7::NUMERIC(20,0) IS NULL OR ({sort}::NUMERIC(20,0), index) {operator} ($7, $8::NUMERIC(20,0))
+                    ORDER BY {sort} {order}, index {order}
+                    LIMIT $9"
+                ),
+                &[
+                    &status,
+                    &current_epoch,
+                    &active_from,
+                    &active_to,
+                    &indices,
+                    &query.min_participation_rate,
+                    &page.cursor.map(|cursor| Decimal::from(cursor.value)),
+                    &page.cursor.map(|cursor| Decimal::from(cursor.index)),
+                    &i64::try_from(page.limit + 1)?,
+                ],
+            )
+            .await?;
+        let validators = rows
+            .into_iter()
+            .map(PostgresValidator::try_from)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|validator| {
+                Validator::try_from(validator).map(|validator| {
+                    (
+                        Cursor::new(sort_value(&validator, query.sort), validator.index),
+                        validator,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(validators, page, total_count))
+    }
+}
+
+const FILTERED: &str = "SELECT validator.index, validator.pubkey, COALESCE(attestation.attestations, 0) as attestations, validator.activation_eligibility_epoch, validator.activation_epoch, validator.exit_epoch, validator.withdrawable_epoch, validator.slashed, validator.withdrawal_credentials
+    FROM validator
+    LEFT JOIN validator_attestation_count AS attestation ON validator.index = attestation.validator_index
+    WHERE ($1::VARCHAR IS NULL
+            OR ($1 = 'slashed' AND validator.slashed)
+            OR ($1 = 'pending' AND NOT validator.slashed AND validator.activation_epoch > $2::NUMERIC(20,0))
+            OR ($1 = 'active' AND NOT validator.slashed AND validator.activation_epoch <= $2 AND validator.exit_epoch > $2)
+            OR ($1 = 'exited' AND NOT validator.slashed AND validator.exit_epoch <= $2))
+        AND ($3::NUMERIC(20,0) IS NULL OR validator.exit_epoch > $3)
+        AND ($4::NUMERIC(20,0) IS NULL OR validator.activation_epoch <= $4)
+        AND ($5::NUMERIC(20,0)[] IS NULL OR validator.index = ANY($5))
+        AND ($6::FLOAT8 IS NULL
+            OR COALESCE(attestation.attestations, 0)::FLOAT8
+                / NULLIF(GREATEST(LEAST(validator.exit_epoch, $2) - validator.activation_epoch, 0), 0)::FLOAT8 >= $6)";
+
+fn sort_column(sort: ValidatorSortField) -> &'static str {
+    match sort {
+        ValidatorSortField::Index => "index",
+        ValidatorSortField::ActivationEpoch => "activation_epoch",
+        ValidatorSortField::ExitEpoch => "exit_epoch",
+        ValidatorSortField::Attestations => "attestations",
+    }
+}
+
+fn sort_value(validator: &Validator, sort: ValidatorSortField) -> u64 {
+    match sort {
+        ValidatorSortField::Index => validator.index,
+        ValidatorSortField::ActivationEpoch => validator.activation_epoch,
+        ValidatorSortField::ExitEpoch => validator.exit_epoch,
+        ValidatorSortField::Attestations => validator.attestations,
+    }
 }
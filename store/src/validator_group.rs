@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{GroupMember, Page, PageRequest, SortDirection, ValidatorGroup},
+    ValidatorGroupRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresGroupMember {
+    pub group_name: String,
+    pub kind: String,
+    pub value: String,
+}
+
+impl TryFrom<Row> for PostgresGroupMember {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresGroupMember {
+            group_name: value.try_get("group_name")?,
+            kind: value.try_get("kind")?,
+            value: value.try_get("value")?,
+        })
+    }
+}
+
+impl TryFrom<PostgresGroupMember> for GroupMember {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresGroupMember) -> Result<Self, Self::Error> {
+        Ok(GroupMember {
+            kind: value.kind.parse()?,
+            value: value.value,
+        })
+    }
+}
+
+pub struct PostgresValidatorGroupRepository {
+    pool: Pool,
+}
+
+impl PostgresValidatorGroupRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ValidatorGroupRepository for PostgresValidatorGroupRepository {
+    #[tracing::instrument(skip(self, labels))]
+    async fn create_group(&self, name: &str, labels: &[String]) -> Result<bool> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "create_group"]);
+        let client = self.pool.get().await?;
+        let created = client
+            .execute(
+                "INSERT INTO validator_group (name, labels)
+                VALUES ($1, $2)
+                ON CONFLICT (name) DO NOTHING",
+                &[&name, &labels],
+            )
+            .await?;
+        Ok(created > 0)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_group(&self, name: &str) -> Result<bool> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "delete_group"]);
+        let client = self.pool.get().await?;
+        let deleted = client
+            .execute("DELETE FROM validator_group WHERE name = $1", &[&name])
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    #[tracing::instrument(skip(self, labels))]
+    async fn set_group_labels(&self, name: &str, labels: &[String]) -> Result<bool> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "set_group_labels"]);
+        let client = self.pool.get().await?;
+        let updated = client
+            .execute(
+                "UPDATE validator_group SET labels = $2 WHERE name = $1",
+                &[&name, &labels],
+            )
+            .await?;
+        Ok(updated > 0)
+    }
+
+    #[tracing::instrument(skip(self, members))]
+    async fn add_group_members(&self, name: &str, members: &[GroupMember]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "add_group_members"]);
+        if members.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let kinds = members.iter().map(|member| member.kind.to_string()).collect::<Vec<_>>();
+        let values = members.iter().map(|member| &member.value).collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO validator_group_member (group_name, kind, value)
+                SELECT $1, * FROM UNNEST($2::VARCHAR[], $3::VARCHAR[])
+                ON CONFLICT (group_name, kind, value) DO NOTHING",
+                &[&name, &kinds, &values],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, members))]
+    async fn remove_group_members(&self, name: &str, members: &[GroupMember]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "remove_group_members"]);
+        if members.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let kinds = members.iter().map(|member| member.kind.to_string()).collect::<Vec<_>>();
+        let values = members.iter().map(|member| &member.value).collect::<Vec<_>>();
+        client
+            .execute(
+                "DELETE FROM validator_group_member
+                WHERE group_name = $1 AND (kind, value) IN (SELECT * FROM UNNEST($2::VARCHAR[], $3::VARCHAR[]))",
+                &[&name, &kinds, &values],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_group(&self, name: &str) -> Result<Option<ValidatorGroup>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "get_group"]);
+        let client = self.pool.get().await?;
+        let row = match client
+            .query_opt("SELECT name, labels FROM validator_group WHERE name = $1", &[&name])
+            .await?
+        {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let rows = client
+            .query(
+                "SELECT group_name, kind, value FROM validator_group_member
+                WHERE group_name = $1
+                ORDER BY kind, value",
+                &[&name],
+            )
+            .await?;
+        let members = rows
+            .into_iter()
+            .map(PostgresGroupMember::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(GroupMember::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(ValidatorGroup {
+            name: row.try_get("name")?,
+            labels: row.try_get("labels")?,
+            members,
+        }))
+    }
+
+    // Group names are not numeric, so their rank by name serves as the cursor
+    #[tracing::instrument(skip(self, page))]
+    async fn get_group_page(&self, label: Option<&str>, page: &PageRequest) -> Result<Page<ValidatorGroup>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "get_group_page"]);
+        let client = self.pool.get().await?;
+        let mut groups = keyset_page(
+            &client,
+            "SELECT name, labels, ROW_NUMBER() OVER (ORDER BY name)::NUMERIC AS cursor_value, 0::NUMERIC AS cursor_index
+            FROM validator_group
+            WHERE $1::VARCHAR IS NULL OR $1 = ANY(labels)",
+            &[&label],
+            SortDirection::Asc,
+            page,
+            |row| {
+                Ok(ValidatorGroup {
+                    name: row.try_get("name")?,
+                    labels: row.try_get("labels")?,
+                    members: vec![],
+                })
+            },
+        )
+        .await?;
+        let names = groups
+            .items
+            .iter()
+            .map(|(_, group)| group.name.as_str())
+            .collect::<Vec<_>>();
+        let member_rows = client
+            .query(
+                "SELECT group_name, kind, value FROM validator_group_member
+                WHERE group_name = ANY($1)
+                ORDER BY kind, value",
+                &[&names],
+            )
+            .await?;
+        let mut members: HashMap<String, Vec<GroupMember>> = HashMap::new();
+        for member in member_rows.into_iter().map(PostgresGroupMember::try_from) {
+            let member = member?;
+            members
+                .entry(member.group_name.clone())
+                .or_default()
+                .push(member.try_into()?);
+        }
+        for (_, group) in groups.items.iter_mut() {
+            group.members = members.remove(&group.name).unwrap_or_default();
+        }
+        Ok(groups)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_group_validator_indices(&self, name: &str) -> Result<Vec<u64>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "get_group_validator_indices"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT validator.index FROM validator_group_member
+                INNER JOIN validator ON validator.index = CASE WHEN validator_group_member.kind = 'index' THEN validator_group_member.value::NUMERIC END
+                WHERE validator_group_member.group_name = $1 AND validator_group_member.kind = 'index'
+                UNION
+                SELECT validator.index FROM validator
+                INNER JOIN validator_group_member ON validator_group_member.kind = 'pubkey' AND validator_group_member.value = validator.pubkey
+                WHERE validator_group_member.group_name = $1
+                UNION
+                SELECT validator.index FROM validator
+                INNER JOIN validator_group_member ON validator_group_member.kind = 'withdrawal_credentials' AND validator_group_member.value = validator.withdrawal_credentials
+                WHERE validator_group_member.group_name = $1
+                ORDER BY index",
+                &[&name],
+            )
+            .await?;
+        rows.into_iter()
+            .map(|row| {
+                row.get::<_, Decimal>("index")
+                    .to_u64()
+                    .ok_or(anyhow!("Invalid validator index"))
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_group_validator_index_page(&self, name: &str, page: &PageRequest) -> Result<Page<u64>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["validator_group", "get_group_validator_index_page"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT index, index AS cursor_value, index AS cursor_index FROM (
+                SELECT validator.index FROM validator_group_member
+                INNER JOIN validator ON validator.index = CASE WHEN validator_group_member.kind = 'index' THEN validator_group_member.value::NUMERIC END
+                WHERE validator_group_member.group_name = $1 AND validator_group_member.kind = 'index'
+                UNION
+                SELECT validator.index FROM validator
+                INNER JOIN validator_group_member ON validator_group_member.kind = 'pubkey' AND validator_group_member.value = validator.pubkey
+                WHERE validator_group_member.group_name = $1
+                UNION
+                SELECT validator.index FROM validator
+                INNER JOIN validator_group_member ON validator_group_member.kind = 'withdrawal_credentials' AND validator_group_member.value = validator.withdrawal_credentials
+                WHERE validator_group_member.group_name = $1
+            ) AS members",
+            &[&name],
+            SortDirection::Asc,
+            page,
+            |row| {
+                row.get::<_, Decimal>("index")
+                    .to_u64()
+                    .ok_or(anyhow!("Invalid validator index"))
+            },
+        )
+        .await
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{Page, PageRequest, SortDirection, VoluntaryExit, FAR_FUTURE_EPOCH},
+    VoluntaryExitRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresVoluntaryExit {
+    pub validator_index: u64,
+    pub epoch_index: u64,
+    pub inclusion_slot: Option<u64>,
+}
+
+impl TryFrom<Row> for PostgresVoluntaryExit {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresVoluntaryExit {
+            validator_index: value
+                .get::<_, Decimal>("validator_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid validator index"))?,
+            epoch_index: value
+                .get::<_, Decimal>("epoch_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid epoch index"))?,
+            inclusion_slot: value
+                .get::<_, Option<Decimal>>("inclusion_slot")
+                .map(|slot| slot.to_u64().ok_or(anyhow!("Invalid inclusion slot")))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<PostgresVoluntaryExit> for VoluntaryExit {
+    fn from(value: PostgresVoluntaryExit) -> Self {
+        VoluntaryExit {
+            validator_index: value.validator_index,
+            epoch: value.epoch_index,
+            inclusion_slot: value.inclusion_slot,
+        }
+    }
+}
+
+pub struct PostgresVoluntaryExitRepository {
+    pool: Pool,
+}
+
+impl PostgresVoluntaryExitRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl VoluntaryExitRepository for PostgresVoluntaryExitRepository {
+    #[tracing::instrument(skip(self, exits))]
+    async fn create_voluntary_exits(&self, exits: &[VoluntaryExit]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["voluntary_exit", "create_voluntary_exits"]);
+        if exits.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let validator_indices = exits
+            .iter()
+            .map(|exit| Decimal::from(exit.validator_index))
+            .collect::<Vec<_>>();
+        let epoch_indices = exits.iter().map(|exit| Decimal::from(exit.epoch)).collect::<Vec<_>>();
+        let inclusion_slots = exits
+            .iter()
+            .map(|exit| exit.inclusion_slot.map(Decimal::from))
+            .collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO voluntary_exit (validator_index, epoch_index, inclusion_slot)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::NUMERIC(20,0)[])
+                ON CONFLICT (validator_index) DO UPDATE SET inclusion_slot = COALESCE(EXCLUDED.inclusion_slot, voluntary_exit.inclusion_slot)",
+                &[&validator_indices, &epoch_indices, &inclusion_slots],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_voluntary_exit(&self, validator: u64) -> Result<Option<VoluntaryExit>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["voluntary_exit", "get_voluntary_exit"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "SELECT validator_index, epoch_index, inclusion_slot FROM voluntary_exit
+                WHERE validator_index = $1",
+                &[&Decimal::from(validator)],
+            )
+            .await?;
+        Ok(row
+            .map(PostgresVoluntaryExit::try_from)
+            .transpose()?
+            .map(VoluntaryExit::from))
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_voluntary_exits(&self, validators: &[u64]) -> Result<Vec<VoluntaryExit>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["voluntary_exit", "get_voluntary_exits"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT validator_index, epoch_index, inclusion_slot FROM voluntary_exit
+                WHERE validator_index = ANY($1::NUMERIC(20,0)[])",
+                &[&validators],
+            )
+            .await?;
+        let exits = rows
+            .into_iter()
+            .map(PostgresVoluntaryExit::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(VoluntaryExit::from)
+            .collect();
+        Ok(exits)
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_voluntary_exit_page(&self, included: bool, page: &PageRequest) -> Result<Page<VoluntaryExit>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["voluntary_exit", "get_voluntary_exit_page"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT validator_index, epoch_index, inclusion_slot,
+                COALESCE(inclusion_slot, epoch_index) AS cursor_value, validator_index AS cursor_index
+            FROM voluntary_exit
+            WHERE (inclusion_slot IS NOT NULL) = $1",
+            &[&included],
+            SortDirection::Desc,
+            page,
+            |row| Ok(VoluntaryExit::from(PostgresVoluntaryExit::try_from(row)?)),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn expire_voluntary_exits(&self) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["voluntary_exit", "expire_voluntary_exits"]);
+        let client = self.pool.get().await?;
+        let expired = client
+            .execute(
+                "DELETE FROM voluntary_exit USING validator
+                WHERE voluntary_exit.inclusion_slot IS NULL AND validator.index = voluntary_exit.validator_index
+                AND (validator.exit_epoch <> $1 OR validator.slashed)",
+                &[&Decimal::from(FAR_FUTURE_EPOCH)],
+            )
+            .await?;
+        Ok(expired)
+    }
+}
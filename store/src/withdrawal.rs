@@ -0,0 +1,219 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use service::{
+    model::{Page, PageRequest, SortDirection, Withdrawal},
+    WithdrawalRepository,
+};
+use tokio_postgres::Row;
+
+use crate::keyset_page;
+
+pub struct PostgresWithdrawal {
+    pub index: u64,
+    pub slot: u64,
+    pub validator_index: u64,
+    pub address: String,
+    pub amount: u64,
+}
+
+impl TryFrom<Row> for PostgresWithdrawal {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Row) -> Result<Self, Self::Error> {
+        Ok(PostgresWithdrawal {
+            index: value
+                .get::<_, Decimal>("index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid withdrawal index"))?,
+            slot: value
+                .get::<_, Decimal>("slot")
+                .to_u64()
+                .ok_or(anyhow!("Invalid slot"))?,
+            validator_index: value
+                .get::<_, Decimal>("validator_index")
+                .to_u64()
+                .ok_or(anyhow!("Invalid validator index"))?,
+            address: value.try_get("address")?,
+            amount: value
+                .get::<_, Decimal>("amount")
+                .to_u64()
+                .ok_or(anyhow!("Invalid withdrawal amount"))?,
+        })
+    }
+}
+
+impl From<PostgresWithdrawal> for Withdrawal {
+    fn from(value: PostgresWithdrawal) -> Self {
+        Withdrawal {
+            index: value.index,
+            slot: value.slot,
+            validator_index: value.validator_index,
+            address: value.address,
+            amount: value.amount,
+        }
+    }
+}
+
+pub struct PostgresWithdrawalRepository {
+    pool: Pool,
+}
+
+impl PostgresWithdrawalRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WithdrawalRepository for PostgresWithdrawalRepository {
+    #[tracing::instrument(skip(self, withdrawals))]
+    async fn create_withdrawals(&self, withdrawals: &[Withdrawal]) -> Result<()> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["withdrawal", "create_withdrawals"]);
+        if withdrawals.is_empty() {
+            return Ok(());
+        }
+        let client = self.pool.get().await?;
+        let indices = withdrawals
+            .iter()
+            .map(|withdrawal| Decimal::from(withdrawal.index))
+            .collect::<Vec<_>>();
+        let slots = withdrawals
+            .iter()
+            .map(|withdrawal| Decimal::from(withdrawal.slot))
+            .collect::<Vec<_>>();
+        let validator_indices = withdrawals
+            .iter()
+            .map(|withdrawal| Decimal::from(withdrawal.validator_index))
+            .collect::<Vec<_>>();
+        let addresses = withdrawals
+            .iter()
+            .map(|withdrawal| &withdrawal.address)
+            .collect::<Vec<_>>();
+        let amounts = withdrawals
+            .iter()
+            .map(|withdrawal| Decimal::from(withdrawal.amount))
+            .collect::<Vec<_>>();
+        client
+            .execute(
+                "INSERT INTO withdrawal (index, slot, validator_index, address, amount)
+                SELECT * FROM UNNEST($1::NUMERIC(20,0)[], $2::NUMERIC(20,0)[], $3::NUMERIC(20,0)[], $4::VARCHAR[], $5::NUMERIC(20,0)[])
+                ON CONFLICT (index) DO NOTHING",
+                &[&indices, &slots, &validator_indices, &addresses, &amounts],
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_withdrawals_for_validator(&self, validator: u64) -> Result<Vec<Withdrawal>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["withdrawal", "get_withdrawals_for_validator"]);
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT index, slot, validator_index, address, amount FROM withdrawal
+                WHERE validator_index = $1
+                ORDER BY index",
+                &[&Decimal::from(validator)],
+            )
+            .await?;
+        let withdrawals = rows
+            .into_iter()
+            .map(PostgresWithdrawal::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Withdrawal::from)
+            .collect();
+        Ok(withdrawals)
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn get_recent_withdrawals_for_validators(&self, validators: &[u64], limit: u64) -> Result<Vec<Withdrawal>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["withdrawal", "get_recent_withdrawals_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let rows = client
+            .query(
+                "SELECT index, slot, validator_index, address, amount FROM (
+                    SELECT index, slot, validator_index, address, amount,
+                        ROW_NUMBER() OVER (PARTITION BY validator_index ORDER BY index DESC) AS rank
+                    FROM withdrawal
+                    WHERE validator_index = ANY($1::NUMERIC(20,0)[])
+                ) AS withdrawals
+                WHERE rank <= $2
+                ORDER BY validator_index, index DESC",
+                &[&validators, &i64::try_from(limit)?],
+            )
+            .await?;
+        let withdrawals = rows
+            .into_iter()
+            .map(PostgresWithdrawal::try_from)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(Withdrawal::from)
+            .collect();
+        Ok(withdrawals)
+    }
+
+    #[tracing::instrument(skip(self, page))]
+    async fn get_withdrawal_page(
+        &self,
+        validator: Option<u64>,
+        address: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<Page<Withdrawal>> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["withdrawal", "get_withdrawal_page"]);
+        let client = self.pool.get().await?;
+        keyset_page(
+            &client,
+            "SELECT index, slot, validator_index, address, amount, index AS cursor_value, index AS cursor_index
+            FROM withdrawal
+            WHERE ($1::NUMERIC(20,0) IS NULL OR validator_index = $1) AND ($2::VARCHAR IS NULL OR address = $2)",
+            &[&validator.map(Decimal::from), &address],
+            SortDirection::Asc,
+            page,
+            |row| Ok(Withdrawal::from(PostgresWithdrawal::try_from(row)?)),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, validators))]
+    async fn withdrawal_total_for_validators(&self, validators: &[u64]) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["withdrawal", "withdrawal_total_for_validators"]);
+        let client = self.pool.get().await?;
+        let validators = validators
+            .iter()
+            .map(|validator| Decimal::from(*validator))
+            .collect::<Vec<_>>();
+        let row = client
+            .query_one(
+                "SELECT COALESCE(SUM(amount), 0) AS total FROM withdrawal
+                WHERE validator_index = ANY($1::NUMERIC(20,0)[])",
+                &[&validators],
+            )
+            .await?;
+        row.get::<_, Decimal>("total")
+            .to_u64()
+            .ok_or(anyhow!("Invalid withdrawal total"))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn withdrawal_total_for_address(&self, address: &str) -> Result<u64> {
+        let _timer = metrics::DB_QUERY_DURATION.start_timer(&["withdrawal", "withdrawal_total_for_address"]);
+        let client = self.pool.get().await?;
+        let row = client
+            .query_one(
+                "SELECT COALESCE(SUM(amount), 0) AS total FROM withdrawal
+                WHERE address = $1",
+                &[&address],
+            )
+            .await?;
+        row.get::<_, Decimal>("total")
+            .to_u64()
+            .ok_or(anyhow!("Invalid withdrawal total"))
+    }
+}